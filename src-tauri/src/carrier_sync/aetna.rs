@@ -0,0 +1,256 @@
+use async_trait::async_trait;
+
+use crate::error::AppError;
+use crate::models::PortalMember;
+
+use super::{CarrierPortal, FetchParams};
+
+pub struct AetnaPortal;
+
+const LOGIN_URL: &str = "https://www.aetna.com/producer_public/login.fcc";
+
+/// Auto-login script: fills and submits the Producer World login form.
+/// MFA (push/SMS) still has to be completed by the user.
+const AUTO_LOGIN_SCRIPT: &str = r#"
+(function() {
+    if (!window.__compass_creds) return;
+    function tryLogin() {
+        var passField = document.querySelector('input[type="password"]');
+        if (!passField) return false;
+        var form = passField.closest('form');
+        var userField = form
+            ? form.querySelector('input[type="text"], input[type="email"]')
+            : document.querySelector('input[type="text"], input[type="email"]');
+        if (!userField) return false;
+        var nativeSet = Object.getOwnPropertyDescriptor(HTMLInputElement.prototype, 'value').set;
+        nativeSet.call(userField, window.__compass_creds.username);
+        userField.dispatchEvent(new Event('input', { bubbles: true }));
+        userField.dispatchEvent(new Event('change', { bubbles: true }));
+        nativeSet.call(passField, window.__compass_creds.password);
+        passField.dispatchEvent(new Event('input', { bubbles: true }));
+        passField.dispatchEvent(new Event('change', { bubbles: true }));
+        var submit = form
+            ? (form.querySelector('button[type="submit"], input[type="submit"]') || form.querySelector('button'))
+            : document.querySelector('button[type="submit"], input[type="submit"]');
+        if (submit) { submit.click(); return true; }
+        return false;
+    }
+    setTimeout(function() {
+        var iv = setInterval(function() { if (tryLogin()) clearInterval(iv); }, 500);
+        setTimeout(function() { clearInterval(iv); }, 15000);
+    }, 1000);
+})();
+"#;
+
+/// Intercept fetch/XHR to capture the Bearer token and book-of-business
+/// API URL used by the Producer World SPA.
+const INIT_SCRIPT: &str = r#"
+(function() {
+    function isBobUrl(url) {
+        return /bookofbusiness|book-of-business|\/bob\//i.test(url);
+    }
+    function capture(url, auth) {
+        if (!isBobUrl(url)) return;
+        window.__compass_aetna_api_url = url.split('?')[0];
+        if (auth && auth.startsWith('Bearer '))
+            window.__compass_aetna_token = auth.substring(7);
+    }
+
+    const origFetch = window.fetch;
+    window.fetch = function(resource, init) {
+        try {
+            const url = typeof resource === 'string' ? resource :
+                         (resource instanceof Request ? resource.url : String(resource));
+            let headers = init && init.headers;
+            if (!headers && resource instanceof Request) headers = resource.headers;
+            let auth = null;
+            if (headers instanceof Headers) {
+                auth = headers.get('Authorization');
+            } else if (Array.isArray(headers)) {
+                const e = headers.find(([k]) => k.toLowerCase() === 'authorization');
+                auth = e ? e[1] : null;
+            } else if (headers) {
+                auth = headers['Authorization'] || headers['authorization'];
+            }
+            capture(url, auth);
+        } catch (e) {}
+        return origFetch.apply(this, arguments);
+    };
+
+    const origOpen = XMLHttpRequest.prototype.open;
+    const origSetHeader = XMLHttpRequest.prototype.setRequestHeader;
+    XMLHttpRequest.prototype.open = function(method, url) {
+        this.__compass_url = typeof url === 'string' ? url : String(url);
+        try { capture(this.__compass_url, null); } catch (e) {}
+        return origOpen.apply(this, arguments);
+    };
+    XMLHttpRequest.prototype.setRequestHeader = function(name, value) {
+        try {
+            if (name.toLowerCase() === 'authorization')
+                capture(this.__compass_url || '', value);
+        } catch (e) {}
+        return origSetHeader.apply(this, arguments);
+    };
+})();
+"#;
+
+/// API page size and page cap when the settings don't override them
+const DEFAULT_PAGE_SIZE: u32 = 100;
+const DEFAULT_MAX_PAGES: u32 = 200;
+
+/// Pull the book of business from the API the portal itself calls, using the
+/// token captured by the init script. Falls back to scraping the rendered
+/// results table when no API call was observed.
+const FETCH_SCRIPT: &str = r#"
+(async () => {
+    try {
+        function toIso(dateStr) {
+            if (!dateStr) return null;
+            var m = String(dateStr).match(/^(\d{1,2})\/(\d{1,2})\/(\d{4})/);
+            if (m) return m[3] + '-' + m[1].padStart(2, '0') + '-' + m[2].padStart(2, '0');
+            m = String(dateStr).match(/^(\d{4}-\d{2}-\d{2})/);
+            return m ? m[1] : null;
+        }
+
+        function titleCase(s) {
+            if (!s) return s;
+            return s.replace(/\w\S*/g, function(w) {
+                return w.charAt(0).toUpperCase() + w.substr(1).toLowerCase();
+            });
+        }
+
+        // Case-insensitive lookup over a list of candidate keys
+        function pick(obj, keys) {
+            var lower = {};
+            Object.keys(obj).forEach(function(k) { lower[k.toLowerCase().replace(/[\s_]/g, '')] = obj[k]; });
+            for (var i = 0; i < keys.length; i++) {
+                var v = lower[keys[i].toLowerCase().replace(/[\s_]/g, '')];
+                if (v !== undefined && v !== null && v !== '') return String(v).trim();
+            }
+            return null;
+        }
+
+        function toMember(row) {
+            var first = pick(row, ['firstName', 'memberFirstName', 'First Name']);
+            var last = pick(row, ['lastName', 'memberLastName', 'Last Name']);
+            if (!first && !last) {
+                var full = pick(row, ['memberName', 'name', 'Member Name']) || '';
+                var comma = full.indexOf(',');
+                if (comma !== -1) {
+                    last = full.substring(0, comma);
+                    first = full.substring(comma + 1);
+                } else {
+                    var parts = full.trim().split(/\s+/);
+                    first = parts.shift() || '';
+                    last = parts.join(' ');
+                }
+            }
+            var status = (pick(row, ['status', 'memberStatus', 'policyStatus', 'Status']) || 'active').toLowerCase();
+            var isActive = status.indexOf('active') === 0;
+            return {
+                first_name: titleCase((first || '').trim()),
+                last_name: titleCase((last || '').trim()),
+                member_id: pick(row, ['memberId', 'memberID', 'Member ID', 'subscriberId']),
+                dob: toIso(pick(row, ['dateOfBirth', 'dob', 'birthDate', 'Date of Birth', 'DOB'])),
+                plan_name: pick(row, ['planName', 'productName', 'Plan Name', 'Plan', 'Product']),
+                effective_date: toIso(pick(row, ['effectiveDate', 'coverageEffectiveDate', 'Effective Date'])),
+                end_date: isActive ? null : toIso(pick(row, ['terminationDate', 'termDate', 'Termination Date', 'Term Date'])),
+                status: isActive ? 'active' : status,
+                policy_status: pick(row, ['policyStatus', 'Policy Status']),
+                state: pick(row, ['state', 'State']),
+                city: pick(row, ['city', 'City']),
+                phone: pick(row, ['phone', 'phoneNumber', 'Phone']),
+                email: pick(row, ['email', 'emailAddress', 'Email'])
+            };
+        }
+
+        var members = [];
+        var confirmedEmpty = false;
+        var token = window.__compass_aetna_token;
+        var apiUrl = window.__compass_aetna_api_url;
+
+        if (token && apiUrl) {
+            var pageSize = {{PAGE_SIZE}};
+            for (var page = 1; page <= {{MAX_PAGES}}; page++) {
+                if (Date.now() > (window.__compassFetchDeadline || Infinity)) throw new Error('Sync timed out');
+                var resp = await fetch(apiUrl + '?page=' + page + '&pageSize=' + pageSize, {
+                    headers: { 'Authorization': 'Bearer ' + token, 'Accept': 'application/json' },
+                    credentials: 'include'
+                });
+                if (!resp.ok) throw new Error('Aetna API returned ' + resp.status);
+                var data = await resp.json();
+                var rows = Array.isArray(data) ? data :
+                    (data.members || data.results || data.items || data.data || []);
+                rows.forEach(function(r) { members.push(toMember(r)); });
+                var total = data.totalCount || data.totalRecords || data.total;
+                if (page === 1 && rows.length === 0 && total === 0) confirmedEmpty = true;
+                if (rows.length < pageSize || (total && members.length >= total)) break;
+            }
+        } else {
+            var table = document.querySelector('table');
+            if (!table) {
+                throw new Error('No book-of-business data found. Make sure you are logged in and on the Book of Business page.');
+            }
+            var headers = Array.from(table.querySelectorAll('thead th')).map(function(th) {
+                return th.textContent.trim();
+            });
+            table.querySelectorAll('tbody tr').forEach(function(tr) {
+                var cells = tr.querySelectorAll('td');
+                if (cells.length === 0) return;
+                var row = {};
+                headers.forEach(function(h, i) {
+                    if (cells[i]) row[h] = cells[i].textContent.trim();
+                });
+                members.push(toMember(row));
+            });
+        }
+
+        members = members.filter(function(m) { return m.first_name || m.last_name; });
+        if (members.length === 0 && !confirmedEmpty) {
+            throw new Error('Aetna returned no members. Open the Book of Business page and try again.');
+        }
+
+        window.location.href = 'http://compass-sync.localhost/data?members=' +
+            encodeURIComponent(JSON.stringify(members)) +
+            (confirmedEmpty ? '&empty=confirmed' : '');
+    } catch (e) {
+        window.location.href = 'http://compass-sync.localhost/error?message=' +
+            encodeURIComponent(e.toString());
+    }
+})();
+"#;
+
+#[async_trait]
+impl CarrierPortal for AetnaPortal {
+    fn carrier_id(&self) -> &str {
+        "carrier-aetna"
+    }
+
+    fn carrier_name(&self) -> &str {
+        "Aetna"
+    }
+
+    fn login_url(&self) -> &str {
+        LOGIN_URL
+    }
+
+    fn init_script(&self) -> &str {
+        INIT_SCRIPT
+    }
+
+    fn fetch_script(&self, params: &FetchParams) -> String {
+        params.render(FETCH_SCRIPT, DEFAULT_PAGE_SIZE, DEFAULT_MAX_PAGES)
+    }
+
+    fn auto_login_script(&self) -> &str {
+        AUTO_LOGIN_SCRIPT
+    }
+
+    fn sync_instruction(&self) -> &str {
+        "Log in (approve the MFA prompt), open Book of Business, then click Sync Now."
+    }
+
+    async fn fetch_members(&self, _cookies: &str) -> Result<Vec<PortalMember>, AppError> {
+        Err(AppError::CarrierSync("Aetna reqwest fallback not implemented yet".into()))
+    }
+}
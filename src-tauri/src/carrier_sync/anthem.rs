@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use crate::error::AppError;
 use crate::models::PortalMember;
 
-use super::CarrierPortal;
+use super::{CarrierPortal, FetchParams};
 
 pub struct AnthemPortal;
 
@@ -95,6 +95,10 @@ const INIT_SCRIPT: &str = r#"
 })();
 "#;
 
+/// Lazy-load scrolls (one page of cards each) when the settings don't
+/// override it. The portal sets its own page size.
+const DEFAULT_MAX_PAGES: u32 = 30;
+
 /// Scrape the Anthem Producer Toolbox "Book of Business" card layout.
 /// Each member is a div.expandCard containing:
 ///   - Name in `.rowHeading h3 a`
@@ -155,7 +159,7 @@ const FETCH_SCRIPT: &str = r#"
                 bodyTextSample: document.body ? document.body.innerText.substring(0, 500) : 'none'
             };
             throw new Error(
-                'Could not find member cards (.expandCard). Make sure you are logged in and on the Book of Business page. Debug: ' +
+                'Timed out after 15 seconds waiting for member cards (.expandCard). Make sure you are logged in and on the Book of Business page. Debug: ' +
                 JSON.stringify(dbg)
             );
         }
@@ -171,7 +175,8 @@ const FETCH_SCRIPT: &str = r#"
 
         // Scroll down to load more if the portal uses lazy loading
         if (!allRecordsVisible()) {
-            for (var scroll = 0; scroll < 30; scroll++) {
+            for (var scroll = 0; scroll < {{MAX_PAGES}}; scroll++) {
+                if (Date.now() > (window.__compassFetchDeadline || Infinity)) throw new Error('Sync timed out');
                 window.scrollTo(0, document.body.scrollHeight);
                 await new Promise(function(r) { setTimeout(r, 1000); });
                 if (allRecordsVisible()) break;
@@ -266,8 +271,8 @@ impl CarrierPortal for AnthemPortal {
         INIT_SCRIPT
     }
 
-    fn fetch_script(&self) -> &str {
-        FETCH_SCRIPT
+    fn fetch_script(&self, params: &FetchParams) -> String {
+        params.render(FETCH_SCRIPT, 0, DEFAULT_MAX_PAGES)
     }
 
     fn auto_login_script(&self) -> &str {
@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use crate::error::AppError;
 use crate::models::PortalMember;
 
-use super::CarrierPortal;
+use super::{CarrierPortal, FetchParams};
 
 pub struct CareSourcePortal;
 
@@ -204,8 +204,8 @@ impl CarrierPortal for CareSourcePortal {
         LOGIN_URL
     }
 
-    fn fetch_script(&self) -> &str {
-        FETCH_SCRIPT
+    fn fetch_script(&self, _params: &FetchParams) -> String {
+        FETCH_SCRIPT.to_string()
     }
 
     fn auto_login_script(&self) -> &str {
@@ -5,7 +5,7 @@ use serde::Deserialize;
 use crate::error::AppError;
 use crate::models::PortalMember;
 
-use super::CarrierPortal;
+use super::{CarrierPortal, FetchParams};
 
 pub struct DevotedPortal;
 
@@ -70,10 +70,14 @@ window.__compassFetchDevoted = async function(silent) {
 
         // Step 2: Fetch members
         let allMembers = [];
+        // Set once the API returns a contact list, so an empty book can be
+        // told apart from a fetch that never got one
+        let listPresent = false;
         let page = 1;
         let hasNext = true;
 
         while (hasNext) {
+            if (Date.now() > (window.__compassFetchDeadline || Infinity)) throw new Error('Sync timed out');
             const resp = await fetch('/graphql/agents/', {
                 method: 'POST',
                 headers: {
@@ -86,7 +90,7 @@ window.__compassFetchDevoted = async function(silent) {
                 body: JSON.stringify({
                     operationName: 'ListBookOfBusinessContacts',
                     variables: {
-                        limit: 100,
+                        limit: window.__compassDevotedPageSize || 100,
                         page: page,
                         order_by: [
                             { by: 'LAST_NAME', direction: 'ASC' },
@@ -118,6 +122,7 @@ window.__compassFetchDevoted = async function(silent) {
             }
 
             const result = json.data.ListBookOfBusinessContacts;
+            if (Array.isArray(result.items)) listPresent = true;
             for (const c of result.items) {
                 allMembers.push({
                     _id: c.id || null,
@@ -129,7 +134,8 @@ window.__compassFetchDevoted = async function(silent) {
                     effective_date: c.current_pbp ? c.current_pbp.start_date : null,
                     end_date: c.current_pbp ? c.current_pbp.end_date : null,
                     status: c.status || null,
-                    policy_status: c.aor_policy_status || null,
+                    policy_status: null,
+                    aor_status: c.aor_policy_status || null,
                     state: c.state || null,
                     city: c.city || null,
                     phone: c.primary_phone || null,
@@ -151,6 +157,8 @@ window.__compassFetchDevoted = async function(silent) {
 
             hasNext = result.page_info.has_next_page;
             page++;
+            window.location.href = 'http://compass-sync.localhost/progress?done=' + allMembers.length +
+                '&total=' + (result.page_info.total_item_count || '');
         }
 
         // Fetch detail for each member (parallel, batched by 5)
@@ -206,7 +214,8 @@ window.__compassFetchDevoted = async function(silent) {
 
         window.__compassBobFetched = true;
         window.location.href = 'http://compass-sync.localhost/data?members=' +
-            encodeURIComponent(JSON.stringify(allMembers));
+            encodeURIComponent(JSON.stringify(allMembers)) +
+            (allMembers.length === 0 && listPresent ? '&empty=confirmed' : '');
     } catch (e) {
         if (!silent) {
             window.location.href = 'http://compass-sync.localhost/error?message=' +
@@ -250,9 +259,11 @@ const AUTO_LOGIN_SCRIPT: &str = r#"
 })();
 "#;
 
-/// Manual fetch script: resets flag and runs with error reporting.
+/// Manual fetch script: resets flag, sets the page size, and runs with error
+/// reporting. The silent fetch on page load keeps the default page size.
 const FETCH_SCRIPT: &str = r#"
 window.__compassBobFetched = false;
+window.__compassDevotedPageSize = {{PAGE_SIZE}};
 window.__compassFetchDevoted(false);
 "#;
 
@@ -365,8 +376,9 @@ impl CarrierPortal for DevotedPortal {
         INIT_SCRIPT
     }
 
-    fn fetch_script(&self) -> &str {
-        FETCH_SCRIPT
+    fn fetch_script(&self, params: &FetchParams) -> String {
+        // Pages until the API reports no next page, so there is no page limit
+        params.render(FETCH_SCRIPT, PAGE_LIMIT as u32, 0)
     }
 
     fn auto_login_script(&self) -> &str {
@@ -472,10 +484,13 @@ impl CarrierPortal for DevotedPortal {
                         member_id: contact.member_id.clone(),
                         dob: contact.birth_date.clone(),
                         plan_name,
+                        contract_number: None,
+                        pbp_number: None,
                         effective_date,
                         end_date,
                         status: contact.status.clone(),
-                        policy_status: contact.aor_policy_status.clone(),
+                        policy_status: None,
+                        aor_status: contact.aor_policy_status.clone(),
                         state: contact.state.clone(),
                         city: contact.city.clone(),
                         phone: contact.primary_phone.clone(),
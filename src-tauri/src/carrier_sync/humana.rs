@@ -1,13 +1,18 @@
 use async_trait::async_trait;
+use serde::Deserialize;
 
 use crate::error::AppError;
 use crate::models::PortalMember;
 
-use super::CarrierPortal;
+use super::{CarrierPortal, FetchParams};
 
 pub struct HumanaPortal;
 
 const LOGIN_URL: &str = "https://account.humana.com/";
+const SEARCH_URL: &str =
+    "https://agentportal.humana.com/Vantage/api/businesscenter/search-policies-and-applications";
+/// Fixed app credential the Vantage SPA sends with every API call
+const VANTAGE_AUTH: &str = "Basic VmFudGFnZVdlYkFwcDpwN1JFdmVkIzE=";
 
 /// Auto-login script: fills and submits the Humana login form.
 /// Uses a global flag to prevent re-submitting after a failed login attempt
@@ -104,6 +109,10 @@ const AUTO_LOGIN_SCRIPT: &str = r#"
 })();
 "#;
 
+/// Vantage API page size and page cap when the settings don't override them
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const DEFAULT_MAX_PAGES: u32 = 100;
+
 /// Fetch Humana Vantage member data via the business center API.
 /// This is more reliable than DOM scraping since the Vantage React SPA
 /// has click-handling issues in the Tauri webview.
@@ -129,10 +138,11 @@ const FETCH_SCRIPT: &str = r#"
         // Fetch all pages from the Vantage API
         var allRecords = [];
         var page = 0;
-        var pageSize = 50;
+        var pageSize = {{PAGE_SIZE}};
         var totalRecords = null;
 
         while (true) {
+            if (Date.now() > (window.__compassFetchDeadline || Infinity)) throw new Error('Sync timed out');
             var resp = await fetch('/Vantage/api/businesscenter/search-policies-and-applications', {
                 method: 'POST',
                 headers: {
@@ -170,10 +180,12 @@ const FETCH_SCRIPT: &str = r#"
 
             allRecords = allRecords.concat(data.records);
             totalRecords = data.totalRecords || allRecords.length;
+            window.location.href = 'http://compass-sync.localhost/progress?done=' + allRecords.length +
+                '&total=' + totalRecords;
 
             if (allRecords.length >= totalRecords) break;
             page++;
-            if (page > 100) break; // safety limit
+            if (page >= {{MAX_PAGES}}) break; // safety limit
         }
 
         var members = allRecords.map(function(r) {
@@ -205,6 +217,80 @@ const FETCH_SCRIPT: &str = r#"
 })();
 "#;
 
+// ── Vantage API response types (for the reqwest fallback) ───────────────────
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResponse {
+    #[serde(default)]
+    records: Vec<VantageRecord>,
+    total_records: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VantageRecord {
+    mbr_name: Option<String>,
+    umid: Option<String>,
+    birth_date: Option<String>,
+    plan_alt_desc: Option<String>,
+    plan_type: Option<String>,
+    sales_product: Option<String>,
+    cov_eff_date: Option<String>,
+    cov_term_date: Option<String>,
+    status_reason_desc: Option<String>,
+    status: Option<String>,
+    mbr_prim_phone: Option<String>,
+    mbr_email: Option<String>,
+}
+
+impl From<VantageRecord> for PortalMember {
+    /// Same mapping as the fetch script
+    fn from(r: VantageRecord) -> Self {
+        let name = r.mbr_name.unwrap_or_default();
+        let (last_name, first_name) = match name.split_once(',') {
+            Some((last, first)) => (last.trim().to_string(), first.trim().to_string()),
+            None => (String::new(), name.trim().to_string()),
+        };
+        let plan_name = r.plan_alt_desc.filter(|p| !p.is_empty()).or_else(|| {
+            let parts: Vec<String> = [r.plan_type, r.sales_product]
+                .into_iter()
+                .flatten()
+                .filter(|p| !p.is_empty())
+                .collect();
+            (!parts.is_empty()).then(|| parts.join(" - "))
+        });
+        PortalMember {
+            first_name,
+            last_name,
+            member_id: r.umid.filter(|id| !id.is_empty()),
+            dob: r.birth_date.map(|d| iso_date(&d)),
+            plan_name,
+            effective_date: r.cov_eff_date.map(|d| iso_date(&d)),
+            end_date: r.cov_term_date.map(|d| iso_date(&d)),
+            status: Some(
+                r.status_reason_desc
+                    .clone()
+                    .filter(|s| !s.is_empty())
+                    .or_else(|| r.status.clone().filter(|s| !s.is_empty()))
+                    .unwrap_or_else(|| "Active".into()),
+            ),
+            policy_status: r.status.filter(|s| !s.is_empty()),
+            phone: r.mbr_prim_phone.filter(|p| !p.is_empty() && p != "Unavailable"),
+            email: r.mbr_email.filter(|e| !e.is_empty()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Keep the date part of an ISO datetime like "2025-08-01T00:00:00Z"
+fn iso_date(date: &str) -> String {
+    match date.get(..10) {
+        Some(day) if date.as_bytes().get(4) == Some(&b'-') => day.to_string(),
+        _ => date.to_string(),
+    }
+}
+
 #[async_trait]
 impl CarrierPortal for HumanaPortal {
     fn carrier_id(&self) -> &str {
@@ -219,8 +305,8 @@ impl CarrierPortal for HumanaPortal {
         LOGIN_URL
     }
 
-    fn fetch_script(&self) -> &str {
-        FETCH_SCRIPT
+    fn fetch_script(&self, params: &FetchParams) -> String {
+        params.render(FETCH_SCRIPT, DEFAULT_PAGE_SIZE, DEFAULT_MAX_PAGES)
     }
 
     fn auto_login_script(&self) -> &str {
@@ -231,7 +317,39 @@ impl CarrierPortal for HumanaPortal {
         "Log in, navigate to My Humana Business, then click Sync Now."
     }
 
-    async fn fetch_members(&self, _cookies: &str) -> Result<Vec<PortalMember>, AppError> {
-        Err(AppError::CarrierSync("Humana reqwest fallback not implemented yet".into()))
+    async fn fetch_members(&self, cookies: &str) -> Result<Vec<PortalMember>, AppError> {
+        let client = super::cookie_client()?;
+        let mut records = Vec::new();
+
+        for page in 0..DEFAULT_MAX_PAGES {
+            let request = client
+                .post(SEARCH_URL)
+                .header(reqwest::header::AUTHORIZATION, VANTAGE_AUTH)
+                .header(reqwest::header::ACCEPT, "application/json")
+                .json(&serde_json::json!({
+                    "filters": { "dateFilter": null, "filterValuesIds": [] },
+                    "insightId": "all",
+                    "resultPaging": { "amount": DEFAULT_PAGE_SIZE, "page": page },
+                    "resultSort": { "columnId": 49, "order": "asc" }
+                }));
+            let body = super::send_with_cookies("Humana", request, cookies).await?;
+            let data: SearchResponse = serde_json::from_str(&body)
+                .map_err(|e| AppError::CarrierSync(format!("Unexpected Humana API response: {}", e)))?;
+
+            if data.records.is_empty() {
+                if page == 0 {
+                    return Err(AppError::CarrierSync(
+                        "No records returned from the API. Make sure you are logged in as an agent.".into(),
+                    ));
+                }
+                break;
+            }
+            records.extend(data.records);
+            if records.len() >= data.total_records.unwrap_or(records.len()) {
+                break;
+            }
+        }
+
+        Ok(records.into_iter().map(PortalMember::from).collect())
     }
 }
@@ -3,11 +3,12 @@ use async_trait::async_trait;
 use crate::error::AppError;
 use crate::models::PortalMember;
 
-use super::CarrierPortal;
+use super::{CarrierPortal, FetchParams};
 
 pub struct MedMutualPortal;
 
 const LOGIN_URL: &str = "https://mybrokerlink.com/";
+const BOB_URL: &str = "https://mybrokerlink.com/mybusiness/bookofbusiness";
 
 /// Auto-login script: fills and submits the MyBrokerLink login form.
 /// The form has simple text + password inputs and a "Log In" button.
@@ -129,8 +130,10 @@ window.__compassFetchBoB = async function(silent) {
         }
 
         window.__compassBobFetched = true;
+        // The member table was found; zero rows means an empty book of business
         window.location.href = 'http://compass-sync.localhost/data?members=' +
-            encodeURIComponent(JSON.stringify(members));
+            encodeURIComponent(JSON.stringify(members)) +
+            (rows.length === 0 ? '&empty=confirmed' : '');
     } catch (e) {
         if (!silent) {
             window.location.href = 'http://compass-sync.localhost/error?message=' +
@@ -165,8 +168,8 @@ impl CarrierPortal for MedMutualPortal {
         INIT_SCRIPT
     }
 
-    fn fetch_script(&self) -> &str {
-        FETCH_SCRIPT
+    fn fetch_script(&self, _params: &FetchParams) -> String {
+        FETCH_SCRIPT.to_string()
     }
 
     fn auto_login_script(&self) -> &str {
@@ -181,7 +184,99 @@ impl CarrierPortal for MedMutualPortal {
         "Log in to MyBrokerLink — data will sync automatically."
     }
 
-    async fn fetch_members(&self, _cookies: &str) -> Result<Vec<PortalMember>, AppError> {
-        Err(AppError::CarrierSync("Medical Mutual reqwest fallback not implemented yet".into()))
+    async fn fetch_members(&self, cookies: &str) -> Result<Vec<PortalMember>, AppError> {
+        super::fetch_with_cookies("Medical Mutual", BOB_URL, cookies, parse_member_table).await
+    }
+}
+
+/// Parse the server-rendered `#member-table` without a DOM, mirroring the
+/// fields the fetch script reads from each `td[data-col-name]`.
+fn parse_member_table(html: &str) -> Result<Vec<PortalMember>, AppError> {
+    let start = html.find("id=\"member-table\"").ok_or_else(|| {
+        AppError::CarrierSync("Could not find the member table. Make sure you are logged in to MyBrokerLink.".into())
+    })?;
+    let table = &html[start..];
+    let table = &table[..table.find("</table>").unwrap_or(table.len())];
+    let body = &table[table.find("<tbody").unwrap_or(0)..];
+
+    let members = body
+        .split("<tr")
+        .skip(1)
+        .map(|row| {
+            let full_name = cell_text(row, "Name").unwrap_or_default();
+            let (first_name, last_name) = match full_name.split_once(char::is_whitespace) {
+                Some((first, last)) => (first.to_string(), last.trim().to_string()),
+                None => (full_name.clone(), String::new()),
+            };
+            PortalMember {
+                first_name,
+                last_name,
+                member_id: cell_text(row, "GroupNumber"),
+                dob: cell_text(row, "DateOfBirth").map(|d| to_iso(&d)),
+                plan_name: cell_text(row, "MarketSegment"),
+                effective_date: cell_text(row, "EffectiveDate").map(|d| to_iso(&d)),
+                status: Some(attention_status(row).unwrap_or_else(|| "Active".into())),
+                state: cell_text(row, "State"),
+                city: cell_text(row, "City"),
+                phone: cell_text(row, "Phone"),
+                email: cell_text(row, "Email"),
+                ..Default::default()
+            }
+        })
+        .collect();
+    Ok(members)
+}
+
+/// Text content of the `<td data-col-name="{col}">` cell in a table row.
+fn cell_text(row: &str, col: &str) -> Option<String> {
+    strip_tags(cell_html(row, col)?)
+}
+
+/// Status badge in the Attention cell. Like the fetch script, only the
+/// `<button>` text counts; anything else in the cell is ignored.
+fn attention_status(row: &str) -> Option<String> {
+    let cell = cell_html(row, "Attention")?;
+    let button = &cell[cell.find("<button")?..];
+    let button = &button[button.find('>')? + 1..];
+    strip_tags(&button[..button.find("</button>").unwrap_or(button.len())])
+}
+
+/// Inner HTML of the `<td data-col-name="{col}">` cell in a table row.
+fn cell_html<'a>(row: &'a str, col: &str) -> Option<&'a str> {
+    let marker = format!("data-col-name=\"{}\"", col);
+    let cell = &row[row.find(&marker)? + marker.len()..];
+    let cell = &cell[cell.find('>')? + 1..];
+    Some(&cell[..cell.find("</td>").unwrap_or(cell.len())])
+}
+
+/// Text of an HTML fragment with tags dropped, entities decoded and
+/// whitespace collapsed; `None` when nothing is left.
+fn strip_tags(html: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&amp;", "&")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() { None } else { Some(text) }
+}
+
+/// Convert MM/DD/YYYY to YYYY-MM-DD, passing anything else through.
+fn to_iso(date: &str) -> String {
+    let parts: Vec<&str> = date.split('/').collect();
+    match parts.as_slice() {
+        [m, d, y] if y.len() == 4 => format!("{}-{:0>2}-{:0>2}", y, m, d),
+        _ => date.to_string(),
     }
 }
@@ -1,15 +1,36 @@
+pub mod aetna;
 pub mod anthem;
 pub mod caresource;
 pub mod devoted;
 pub mod humana;
 pub mod medmutual;
 pub mod uhc;
+pub mod wellcare;
 
 use async_trait::async_trait;
 
 use crate::error::AppError;
 use crate::models::PortalMember;
 
+/// Runtime values for fetch scripts, read per carrier from the
+/// `carrier_sync_page_size_<carrier_id>` and `carrier_sync_max_pages_<carrier_id>`
+/// settings. `None` keeps the carrier's built-in default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FetchParams {
+    pub page_size: Option<u32>,
+    pub max_pages: Option<u32>,
+}
+
+impl FetchParams {
+    /// Fill the `{{PAGE_SIZE}}` and `{{MAX_PAGES}}` placeholders in a fetch
+    /// script, falling back to the carrier's defaults
+    pub fn render(&self, script: &str, default_page_size: u32, default_max_pages: u32) -> String {
+        script
+            .replace("{{PAGE_SIZE}}", &self.page_size.unwrap_or(default_page_size).to_string())
+            .replace("{{MAX_PAGES}}", &self.max_pages.unwrap_or(default_max_pages).to_string())
+    }
+}
+
 /// Trait that each carrier portal integration must implement.
 #[async_trait]
 pub trait CarrierPortal: Send + Sync {
@@ -39,8 +60,23 @@ pub trait CarrierPortal: Send + Sync {
     ///   `http://compass-sync.localhost/data?members=<encodeURIComponent(JSON)>`
     /// on success, or:
     ///   `http://compass-sync.localhost/error?message=<encodeURIComponent(msg)>`
-    /// on failure.
-    fn fetch_script(&self) -> &str;
+    /// on failure. Commission fetch scripts report their failures through the
+    /// same path with `&source=commission`, which keeps them out of the sync log.
+    ///
+    /// An empty member list is only trusted when the portal itself reported
+    /// zero members; in that case the script appends `&empty=confirmed`.
+    /// Without it, an empty list is treated as a scrape/parse failure and
+    /// no disenrollment candidates are produced.
+    ///
+    /// Multi-page fetches may report progress along the way by navigating to
+    /// `http://compass-sync.localhost/progress?done=<n>&total=<n>` (total is
+    /// optional); it is re-emitted as a `carrier-sync-progress` event.
+    ///
+    /// Paged fetches take their page size and page limit from `params`
+    /// (see `FetchParams::render`). Loops that page, scroll, or poll should
+    /// throw once `Date.now()` passes `window.__compassFetchDeadline` (set by
+    /// `trigger_carrier_fetch`; unset for silent auto-fetches).
+    fn fetch_script(&self, params: &FetchParams) -> String;
 
     /// Whether this carrier auto-fetches data after login (via init_script).
     /// When true, the UI skips the manual "Sync Now" step and shows
@@ -61,12 +97,70 @@ pub trait CarrierPortal: Send + Sync {
 /// Look up the carrier portal implementation by carrier_id.
 pub fn get_portal(carrier_id: &str) -> Option<Box<dyn CarrierPortal>> {
     match carrier_id {
+        "carrier-aetna" => Some(Box::new(aetna::AetnaPortal)),
         "carrier-anthem" => Some(Box::new(anthem::AnthemPortal)),
         "carrier-devoted" => Some(Box::new(devoted::DevotedPortal)),
         "carrier-caresource" => Some(Box::new(caresource::CareSourcePortal)),
         "carrier-medmutual" => Some(Box::new(medmutual::MedMutualPortal)),
         "carrier-uhc" => Some(Box::new(uhc::UhcPortal)),
         "carrier-humana" => Some(Box::new(humana::HumanaPortal)),
+        "carrier-wellcare" => Some(Box::new(wellcare::WellcarePortal)),
         _ => None,
     }
 }
+
+/// Shared reqwest fallback: GET `url` with the webview's cookies and hand the
+/// response body to `parse`.
+pub async fn fetch_with_cookies<F>(
+    carrier_name: &str,
+    url: &str,
+    cookies: &str,
+    parse: F,
+) -> Result<Vec<PortalMember>, AppError>
+where
+    F: FnOnce(&str) -> Result<Vec<PortalMember>, AppError>,
+{
+    let body = send_with_cookies(carrier_name, cookie_client()?.get(url), cookies).await?;
+    parse(&body)
+}
+
+/// Client for the reqwest fallbacks. Redirects are not followed, so a bounce
+/// to the login page surfaces as an expired session alongside 401/403.
+pub fn cookie_client() -> Result<reqwest::Client, AppError> {
+    Ok(reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?)
+}
+
+/// Send `request` with the webview's cookies and return the response body,
+/// mapping 401/403 and redirects to a "session expired" error. For fallbacks
+/// that need more than a GET (POST bodies, extra headers, paging).
+pub async fn send_with_cookies(
+    carrier_name: &str,
+    request: reqwest::RequestBuilder,
+    cookies: &str,
+) -> Result<String, AppError> {
+    let resp = request
+        .header(reqwest::header::COOKIE, cookies)
+        .send()
+        .await?;
+
+    let status = resp.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED
+        || status == reqwest::StatusCode::FORBIDDEN
+        || status.is_redirection()
+    {
+        return Err(AppError::CarrierSync(format!(
+            "{} session expired. Log in to the portal again and retry.",
+            carrier_name
+        )));
+    }
+    if !status.is_success() {
+        return Err(AppError::CarrierSync(format!(
+            "{} returned HTTP {}",
+            carrier_name, status
+        )));
+    }
+
+    Ok(resp.text().await?)
+}
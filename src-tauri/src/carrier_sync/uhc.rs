@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use crate::error::AppError;
 use crate::models::PortalMember;
 
-use super::CarrierPortal;
+use super::{CarrierPortal, FetchParams};
 
 pub struct UhcPortal;
 
@@ -44,11 +44,13 @@ const INIT_SCRIPT: &str = r#"
         });
     }
 
-    function sendData(members) {
+    // Zero members is only confirmed empty when the response carried the list
+    function sendData(members, listPresent) {
         if (window.__compassBobFetched) return;
         window.__compassBobFetched = true;
         window.location.href = 'http://compass-sync.localhost/data?members=' +
-            encodeURIComponent(JSON.stringify(members));
+            encodeURIComponent(JSON.stringify(members)) +
+            (members.length === 0 && listPresent ? '&empty=confirmed' : '');
     }
 
     function sendError(message) {
@@ -83,7 +85,7 @@ const INIT_SCRIPT: &str = r#"
             var data = JSON.parse(text);
             if (data.bookOfBusinessList) {
                 var members = mapMembers(data.bookOfBusinessList);
-                sendData(members);
+                sendData(members, Array.isArray(data.bookOfBusinessList));
                 return true;
             }
         } catch (e) {}
@@ -192,7 +194,9 @@ window.__compassFetchUhc = async function(silent) {
                             })(replayData.bookOfBusinessList);
                             window.__compassBobFetched = true;
                             window.location.href = 'http://compass-sync.localhost/data?members=' +
-                                encodeURIComponent(JSON.stringify(members));
+                                encodeURIComponent(JSON.stringify(members)) +
+                                (members.length === 0 && Array.isArray(replayData.bookOfBusinessList)
+                                    ? '&empty=confirmed' : '');
                             return;
                         }
                     } catch (e) {
@@ -326,6 +330,9 @@ window.__compassFetchUhc = async function(silent) {
             throw new Error('API errors: ' + data.errors.join('; '));
         }
 
+        // Only an explicit empty list counts as a confirmed empty book;
+        // a missing list means the response shape wasn't recognised.
+        var listPresent = Array.isArray(data.bookOfBusinessList);
         var list = data.bookOfBusinessList || [];
         function toIso(dateStr) {
             if (!dateStr) return null;
@@ -353,7 +360,8 @@ window.__compassFetchUhc = async function(silent) {
 
         window.__compassBobFetched = true;
         window.location.href = 'http://compass-sync.localhost/data?members=' +
-            encodeURIComponent(JSON.stringify(members));
+            encodeURIComponent(JSON.stringify(members)) +
+            (members.length === 0 && listPresent ? '&empty=confirmed' : '');
     } catch (e) {
         if (!silent) {
             window.location.href = 'http://compass-sync.localhost/error?message=' +
@@ -459,8 +467,8 @@ impl CarrierPortal for UhcPortal {
         INIT_SCRIPT
     }
 
-    fn fetch_script(&self) -> &str {
-        FETCH_SCRIPT
+    fn fetch_script(&self, _params: &FetchParams) -> String {
+        FETCH_SCRIPT.to_string()
     }
 
     fn auto_login_script(&self) -> &str {
@@ -0,0 +1,235 @@
+use async_trait::async_trait;
+
+use crate::error::AppError;
+use crate::models::PortalMember;
+
+use super::{CarrierPortal, FetchParams};
+
+pub struct WellcarePortal;
+
+const LOGIN_URL: &str = "https://www.wellcare.com/en/Brokers";
+
+/// Auto-login script: fills and submits the Centene PingOne sign-in form.
+/// Uses a global flag so a failed attempt is not re-submitted on reload.
+const AUTO_LOGIN_SCRIPT: &str = r#"
+(function() {
+    if (!window.__compass_creds) return;
+    if (window.__compass_login_submitted) return;
+    function tryLogin() {
+        var passField = document.querySelector('input[type="password"]');
+        if (!passField) return false;
+        var form = passField.closest('form');
+        var userField = form
+            ? form.querySelector('input[type="text"], input[type="email"]')
+            : document.querySelector('input[type="text"], input[type="email"]');
+        if (!userField) return false;
+        var nativeSet = Object.getOwnPropertyDescriptor(HTMLInputElement.prototype, 'value').set;
+        nativeSet.call(userField, window.__compass_creds.username);
+        userField.dispatchEvent(new Event('input', { bubbles: true }));
+        userField.dispatchEvent(new Event('change', { bubbles: true }));
+        nativeSet.call(passField, window.__compass_creds.password);
+        passField.dispatchEvent(new Event('input', { bubbles: true }));
+        passField.dispatchEvent(new Event('change', { bubbles: true }));
+        var submit = form
+            ? (form.querySelector('button[type="submit"], input[type="submit"]') || form.querySelector('button'))
+            : document.querySelector('button[type="submit"], input[type="submit"]');
+        if (submit) {
+            window.__compass_login_submitted = true;
+            submit.click();
+            return true;
+        }
+        return false;
+    }
+    setTimeout(function() {
+        var iv = setInterval(function() { if (tryLogin()) clearInterval(iv); }, 500);
+        setTimeout(function() { clearInterval(iv); }, 15000);
+    }, 1500);
+})();
+"#;
+
+/// Grid pages read when the settings don't override it. The grid sets its own
+/// page size, so there is no page size to tune.
+const DEFAULT_MAX_PAGES: u32 = 100;
+
+/// Scrape the Wellcare agent portal "Book of Business" grid.
+/// Columns are matched by header text, and the grid is paged through
+/// with its "Next" button until it is disabled.
+const FETCH_SCRIPT: &str = r#"
+(async () => {
+    try {
+        function waitFor(fn, ms) {
+            return new Promise(function(resolve) {
+                const start = Date.now();
+                const iv = setInterval(function() {
+                    const result = fn();
+                    if (result) { clearInterval(iv); resolve(result); }
+                    else if (Date.now() - start > ms) { clearInterval(iv); resolve(null); }
+                }, 300);
+            });
+        }
+
+        // Convert MM/DD/YYYY to YYYY-MM-DD
+        function toIso(dateStr) {
+            if (!dateStr) return null;
+            var m = dateStr.match(/^(\d{1,2})\/(\d{1,2})\/(\d{4})$/);
+            if (m) return m[3] + '-' + m[1].padStart(2, '0') + '-' + m[2].padStart(2, '0');
+            m = dateStr.match(/^\d{4}-\d{2}-\d{2}$/);
+            return m ? dateStr : null;
+        }
+
+        function titleCase(s) {
+            if (!s) return s;
+            return s.replace(/\w\S*/g, function(w) {
+                return w.charAt(0).toUpperCase() + w.substr(1).toLowerCase();
+            });
+        }
+
+        function findTable() {
+            var tables = document.querySelectorAll('table');
+            for (var i = 0; i < tables.length; i++) {
+                var text = tables[i].querySelector('thead') ? tables[i].querySelector('thead').textContent : '';
+                if (/member/i.test(text) && /(name|first)/i.test(text)) return tables[i];
+            }
+            return null;
+        }
+
+        function findNext() {
+            return document.querySelector('button[aria-label*="Next" i], a[aria-label*="Next" i], li.next a, .pagination-next');
+        }
+
+        var table = await waitFor(findTable, 15000);
+        if (!table) {
+            throw new Error('Timed out after 15 seconds waiting for the member table. Make sure you are logged in and on the Book of Business page.');
+        }
+
+        var headers = Array.from(table.querySelectorAll('thead th')).map(function(th) {
+            return th.textContent.trim().toLowerCase();
+        });
+        function col(names) {
+            for (var i = 0; i < names.length; i++) {
+                var idx = headers.indexOf(names[i]);
+                if (idx !== -1) return idx;
+            }
+            return -1;
+        }
+        var idx = {
+            name: col(['member name', 'name']),
+            first: col(['first name']),
+            last: col(['last name']),
+            id: col(['member id', 'medicare id', 'subscriber id']),
+            dob: col(['date of birth', 'dob', 'birth date']),
+            plan: col(['plan name', 'plan', 'product']),
+            eff: col(['effective date', 'eff date']),
+            term: col(['term date', 'termination date', 'disenrollment date']),
+            status: col(['status', 'member status', 'enrollment status']),
+            state: col(['state']),
+            city: col(['city']),
+            phone: col(['phone', 'phone number'])
+        };
+
+        var allMembers = [];
+        var seen = {};
+        for (var page = 0; page < {{MAX_PAGES}}; page++) {
+            if (Date.now() > (window.__compassFetchDeadline || Infinity)) throw new Error('Sync timed out');
+            var rows = findTable().querySelectorAll('tbody tr');
+            for (var r = 0; r < rows.length; r++) {
+                var cells = rows[r].querySelectorAll('td');
+                if (cells.length === 0) continue;
+                function cell(i) { return i >= 0 && cells[i] ? cells[i].textContent.trim() || null : null; }
+
+                var first = cell(idx.first);
+                var last = cell(idx.last);
+                if (!first && !last) {
+                    var full = cell(idx.name) || '';
+                    var comma = full.indexOf(',');
+                    if (comma !== -1) {
+                        last = full.substring(0, comma).trim();
+                        first = full.substring(comma + 1).trim();
+                    } else {
+                        var parts = full.split(/\s+/);
+                        first = parts.shift() || '';
+                        last = parts.join(' ');
+                    }
+                }
+                if (!first && !last) continue;
+
+                var memberId = cell(idx.id);
+                var key = memberId || (first + '|' + last + '|' + cell(idx.dob));
+                if (seen[key]) continue;
+                seen[key] = true;
+
+                var status = (cell(idx.status) || 'active').toLowerCase();
+                var isActive = status.indexOf('active') === 0;
+                allMembers.push({
+                    first_name: titleCase(first),
+                    last_name: titleCase(last),
+                    member_id: memberId,
+                    dob: toIso(cell(idx.dob)),
+                    plan_name: cell(idx.plan),
+                    effective_date: toIso(cell(idx.eff)),
+                    end_date: isActive ? null : toIso(cell(idx.term)),
+                    status: isActive ? 'active' : status,
+                    policy_status: cell(idx.status),
+                    state: cell(idx.state),
+                    city: cell(idx.city),
+                    phone: cell(idx.phone),
+                    email: null
+                });
+            }
+
+            var next = findNext();
+            if (!next || next.disabled || next.getAttribute('aria-disabled') === 'true' ||
+                (next.parentElement && next.parentElement.classList.contains('disabled'))) break;
+            var firstRowBefore = rows.length > 0 ? rows[0].textContent : '';
+            next.click();
+            var changed = await waitFor(function() {
+                var t = findTable();
+                var fr = t ? t.querySelector('tbody tr') : null;
+                return fr && fr.textContent !== firstRowBefore;
+            }, 10000);
+            if (!changed) break;
+        }
+
+        if (allMembers.length === 0) {
+            throw new Error('Found the member table but could not read any members.');
+        }
+
+        window.location.href = 'http://compass-sync.localhost/data?members=' +
+            encodeURIComponent(JSON.stringify(allMembers));
+    } catch (e) {
+        window.location.href = 'http://compass-sync.localhost/error?message=' +
+            encodeURIComponent(e.toString());
+    }
+})();
+"#;
+
+#[async_trait]
+impl CarrierPortal for WellcarePortal {
+    fn carrier_id(&self) -> &str {
+        "carrier-wellcare"
+    }
+
+    fn carrier_name(&self) -> &str {
+        "WellCare"
+    }
+
+    fn login_url(&self) -> &str {
+        LOGIN_URL
+    }
+
+    fn fetch_script(&self, params: &FetchParams) -> String {
+        params.render(FETCH_SCRIPT, 0, DEFAULT_MAX_PAGES)
+    }
+
+    fn auto_login_script(&self) -> &str {
+        AUTO_LOGIN_SCRIPT
+    }
+
+    fn sync_instruction(&self) -> &str {
+        "Sign in to the agent portal, open Book of Business, then click Sync Now."
+    }
+
+    async fn fetch_members(&self, _cookies: &str) -> Result<Vec<PortalMember>, AppError> {
+        Err(AppError::CarrierSync("WellCare reqwest fallback not implemented yet".into()))
+    }
+}
@@ -0,0 +1,15 @@
+use tauri::State;
+use crate::db::DbState;
+use crate::models::AuditEntry;
+use crate::services::audit_service;
+
+#[tauri::command]
+pub async fn get_audit_log(
+    entity_type: String,
+    entity_id: Option<String>,
+    state: State<'_, DbState>,
+) -> Result<Vec<AuditEntry>, String> {
+    state.with_read_conn(|conn| {
+        audit_service::get_audit_log(conn, &entity_type, entity_id.as_deref())
+    }).map_err(|e| e.to_string())
+}
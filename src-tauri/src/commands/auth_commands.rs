@@ -1,7 +1,7 @@
 use tauri::State;
 
 use crate::db::DbState;
-use crate::services::auth_service;
+use crate::services::{auth_service, backup_service};
 use crate::AppDataDir;
 
 #[tauri::command]
@@ -10,44 +10,77 @@ pub fn check_first_run(app_data_dir: State<'_, AppDataDir>) -> Result<bool, Stri
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, err)]
 pub async fn create_account(
     password: String,
     app_data_dir: State<'_, AppDataDir>,
     db_state: State<'_, DbState>,
 ) -> Result<(), String> {
     let data_dir = app_data_dir.0.clone();
-    let conn = tauri::async_runtime::spawn_blocking(move || {
+    let db = tauri::async_runtime::spawn_blocking(move || {
         auth_service::create_database(&data_dir, &password)
     })
     .await
     .map_err(|e| e.to_string())?
     .map_err(|e| e.to_string())?;
 
-    db_state.set_connection(conn).map_err(|e| e.to_string())?;
+    db_state.set_connection(db.conn, db.readers).map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, err)]
 pub async fn login(
     password: String,
     app_data_dir: State<'_, AppDataDir>,
     db_state: State<'_, DbState>,
 ) -> Result<(), String> {
     let data_dir = app_data_dir.0.clone();
-    let conn = tauri::async_runtime::spawn_blocking(move || {
-        auth_service::unlock_database(&data_dir, &password)
+    let db = tauri::async_runtime::spawn_blocking(move || {
+        let db = auth_service::unlock_database(&data_dir, &password)?;
+        // Best effort: a failed automatic backup must never block login
+        if let Err(e) = backup_service::run_auto_backup(&db.conn) {
+            tracing::warn!("Automatic backup failed: {}", e);
+        }
+        Ok::<_, crate::error::AppError>(db)
     })
     .await
     .map_err(|e| e.to_string())?
     .map_err(|e| e.to_string())?;
 
-    db_state.set_connection(conn).map_err(|e| e.to_string())?;
+    db_state.set_connection(db.conn, db.readers).map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
+/// Verify the current password, then rekey the database with the new one
 #[tauri::command]
+#[tracing::instrument(skip_all, err)]
+pub async fn change_password(
+    current_password: String,
+    new_password: String,
+    app_data_dir: State<'_, AppDataDir>,
+    db_state: State<'_, DbState>,
+) -> Result<(), String> {
+    let data_dir = app_data_dir.0.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        auth_service::verify_password(&data_dir, &current_password)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    // Retire the old readers first so none is mid-query with the old key
+    db_state.replace_readers(Vec::new()).map_err(|e| e.to_string())?;
+    let readers = db_state
+        .with_conn(|conn| auth_service::change_password(conn, &app_data_dir.0, &new_password))
+        .map_err(|e| e.to_string())?;
+    db_state.replace_readers(readers).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all)]
 pub fn logout(db_state: State<'_, DbState>) -> Result<(), String> {
     db_state.clear_connection().map_err(|e| e.to_string())?;
     Ok(())
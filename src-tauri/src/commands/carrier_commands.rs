@@ -1,6 +1,6 @@
 use tauri::State;
 use crate::db::DbState;
-use crate::models::Carrier;
+use crate::models::{Carrier, CarrierWithCounts};
 use crate::repositories::carrier_repo;
 
 #[tauri::command]
@@ -10,6 +10,13 @@ pub fn get_carriers(state: State<'_, DbState>) -> Result<Vec<Carrier>, String> {
     }).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_carriers_with_counts(state: State<'_, DbState>) -> Result<Vec<CarrierWithCounts>, String> {
+    state.with_conn(|conn| {
+        carrier_repo::get_carriers_with_counts(conn)
+    }).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn update_carrier_expected_active(
     state: State<'_, DbState>,
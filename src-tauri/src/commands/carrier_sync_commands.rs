@@ -1,8 +1,23 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use tauri::{AppHandle, Emitter, Manager, State, WebviewWindowBuilder, WebviewUrl, webview::PageLoadEvent};
 
 use crate::carrier_sync;
 use crate::db::DbState;
-use crate::models::{CarrierSyncInfo, ConfirmDisenrollmentResult, ImportLogEntry, ImportPortalResult, PortalCredentials, PortalMember, SyncLogEntry, SyncResult};
+use crate::services::carrier_sync_service;
+use crate::models::{CarrierSyncInfo, ConfirmDisenrollmentResult, ImportLogEntry, ImportPortalResult, PortalCredentials, PortalFetchSummary, PortalMember, SyncAorLoss, SyncLogEntry, SyncResult, SyncReviewAction, SyncReviewItem, SyncSnapshot};
+
+/// Token of the manual fetch still waiting on a `/data` or `/error` callback
+/// (0 when none). The timeout task only fires if its token is still pending.
+static PENDING_FETCH: AtomicU64 = AtomicU64::new(0);
+static FETCH_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Overall fetch timeout when `carrier_sync_timeout_secs` is not set.
+const DEFAULT_FETCH_TIMEOUT_SECS: u64 = 180;
+
+/// Extra time the backstop timer gives a script to report its own timeout
+/// before the webview is closed.
+const FETCH_TIMEOUT_GRACE_SECS: u64 = 5;
 
 fn emit_log(app: &AppHandle, level: &str, phase: &str, message: &str, detail: Option<&str>) {
     let entry = ImportLogEntry {
@@ -15,10 +30,22 @@ fn emit_log(app: &AppHandle, level: &str, phase: &str, message: &str, detail: Op
     let _ = app.emit("commission-import-log", &entry);
 }
 
+/// Add a FAILED row to the sync history. Best effort: the failure has
+/// already been reported to the user.
+fn record_sync_failure(app: &AppHandle, carrier_id: &str, message: &str) {
+    let result = app.state::<DbState>().with_conn(|conn| {
+        carrier_sync_service::log_sync_failure(conn, carrier_id, message)
+    });
+    if let Err(e) = result {
+        tracing::warn!("Failed to record sync failure: {}", e);
+    }
+}
+
 /// Open a webview window to the carrier's login portal.
 /// Sets up a navigation interceptor to catch sync results from injected JS.
 /// If saved credentials exist, injects auto-login script.
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(carrier_id = %carrier_id), err)]
 pub async fn open_carrier_login(
     app: AppHandle,
     carrier_id: String,
@@ -40,7 +67,10 @@ pub async fn open_carrier_login(
         if current_host == login_host {
             let _ = existing.set_focus();
             // Re-inject the fetch script so auto-fetch carriers re-sync
-            let _ = existing.eval(portal.fetch_script());
+            let params = state
+                .with_conn(|conn| carrier_sync_service::fetch_params(conn, &carrier_id))
+                .map_err(|e| e.to_string())?;
+            let _ = existing.eval(portal.fetch_script(&params));
             return Ok(url);
         }
 
@@ -102,6 +132,7 @@ pub async fn open_carrier_login(
 
     let page_load_handle = app.clone();
     let nav_handle = app.clone();
+    let nav_carrier_id = carrier_id.clone();
     let dl_handle = app.clone();
     let dl_log_handle = app.clone();
     // Shared state: the JS sends the detected statement month via a callback
@@ -120,9 +151,21 @@ pub async fn open_carrier_login(
             if host == "compass-sync.localhost" {
                 let path = nav_url.path();
                 tracing::info!("[navigation] compass-sync intercepted: path={}, full={}", path, nav_url);
+                if path == "/data" || path == "/error" {
+                    PENDING_FETCH.store(0, Ordering::SeqCst);
+                }
                 if path == "/data" {
                     if let Some(members_val) = nav_url.query_pairs().find(|(k, _)| k == "members") {
-                        let _ = nav_handle.emit("carrier-sync-data", members_val.1.to_string());
+                        let empty_confirmed = nav_url
+                            .query_pairs()
+                            .any(|(k, v)| k == "empty" && v == "confirmed");
+                        let _ = nav_handle.emit(
+                            "carrier-sync-data",
+                            serde_json::json!({
+                                "members": members_val.1.to_string(),
+                                "empty_confirmed": empty_confirmed,
+                            }),
+                        );
                     }
                 } else if path == "/commission" {
                     if let Some(val) = nav_url.query_pairs().find(|(k, _)| k == "statements") {
@@ -151,9 +194,31 @@ pub async fn open_carrier_login(
                         Ok(_) => tracing::info!("[navigation] carrier-page-ready emitted OK (csv={})", csv_count),
                         Err(e) => tracing::error!("[navigation] Failed to emit carrier-page-ready: {}", e),
                     }
+                } else if path == "/progress" {
+                    // Page progress from long-running fetch scripts
+                    let param = |name: &str| {
+                        nav_url
+                            .query_pairs()
+                            .find(|(k, _)| k == name)
+                            .and_then(|(_, v)| v.parse::<i64>().ok())
+                    };
+                    if let Some(done) = param("done") {
+                        let _ = nav_handle.emit(
+                            "carrier-sync-progress",
+                            serde_json::json!({ "done": done, "total": param("total") }),
+                        );
+                    }
                 } else if path == "/error" {
                     if let Some(err_val) = nav_url.query_pairs().find(|(k, _)| k == "message") {
                         emit_log(&nav_handle, "error", "portal", &err_val.1, None);
+                        // Commission scripts report through the same path and
+                        // tag themselves with `source=commission`
+                        let from_commission = nav_url
+                            .query_pairs()
+                            .any(|(k, v)| k == "source" && v == "commission");
+                        if !from_commission {
+                            record_sync_failure(&nav_handle, &nav_carrier_id, &err_val.1);
+                        }
                         let _ = nav_handle.emit("carrier-sync-error", err_val.1.to_string());
                     }
                 }
@@ -259,9 +324,19 @@ pub async fn open_carrier_login(
 
 /// Inject the fetch script into the carrier login webview.
 /// The script fetches member data using the browser's cookies and navigates
-/// to a callback URL that on_navigation intercepts.
+/// to a callback URL that on_navigation intercepts. Scripts get the deadline
+/// as `window.__compassFetchDeadline` and abort their loops once it passes.
+/// If neither a data nor an error callback has arrived shortly after
+/// `carrier_sync_timeout_secs` (default 180), a `carrier-sync-error` is
+/// emitted and the webview is closed, so a stuck script can't deliver data
+/// after the sync was reported as timed out.
 #[tauri::command]
-pub async fn trigger_carrier_fetch(app: AppHandle, carrier_id: String) -> Result<(), String> {
+#[tracing::instrument(skip_all, fields(carrier_id = %carrier_id), err)]
+pub async fn trigger_carrier_fetch(
+    app: AppHandle,
+    carrier_id: String,
+    state: State<'_, DbState>,
+) -> Result<(), String> {
     let portal = carrier_sync::get_portal(&carrier_id)
         .ok_or_else(|| format!("No portal integration for carrier: {}", carrier_id))?;
 
@@ -269,37 +344,150 @@ pub async fn trigger_carrier_fetch(app: AppHandle, carrier_id: String) -> Result
         .get_webview_window("carrier-login")
         .ok_or("Carrier login window is not open. Open the portal and log in first.")?;
 
-    webview
-        .eval(portal.fetch_script())
+    let (timeout_secs, params) = state
+        .with_conn(|conn| {
+            let timeout_secs = conn
+                .query_row(
+                    "SELECT value FROM app_settings WHERE key = 'carrier_sync_timeout_secs'",
+                    [],
+                    |row| row.get::<_, String>(0),
+                )
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|&secs| secs > 0)
+                .unwrap_or(DEFAULT_FETCH_TIMEOUT_SECS);
+            Ok((timeout_secs, carrier_sync_service::fetch_params(conn, &carrier_id)?))
+        })
         .map_err(|e| e.to_string())?;
 
+    let token = FETCH_SEQ.fetch_add(1, Ordering::SeqCst) + 1;
+    PENDING_FETCH.store(token, Ordering::SeqCst);
+
+    let script = format!(
+        "window.__compassFetchDeadline = Date.now() + {};\n{}",
+        timeout_secs * 1000,
+        portal.fetch_script(&params)
+    );
+    webview.eval(&script).map_err(|e| e.to_string())?;
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(timeout_secs + FETCH_TIMEOUT_GRACE_SECS)).await;
+        if PENDING_FETCH
+            .compare_exchange(token, 0, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            let message = format!("Sync timed out after {} seconds", timeout_secs);
+            emit_log(&app, "error", "portal", &message, None);
+            record_sync_failure(&app, &carrier_id, &message);
+            if let Some(webview) = app.get_webview_window("carrier-login") {
+                let _ = webview.close();
+            }
+            let _ = app.emit("carrier-sync-error", message);
+        }
+    });
+
     Ok(())
 }
 
+/// Abandon an in-progress fetch by closing the carrier login webview.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+pub fn cancel_carrier_fetch(app: AppHandle) -> Result<(), String> {
+    PENDING_FETCH.store(0, Ordering::SeqCst);
+    if let Some(webview) = app.get_webview_window("carrier-login") {
+        webview.close().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Count and sample the members the webview fetched, so the agent can
+/// confirm the fetch before anything is compared.
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(carrier_id = %carrier_id, bytes = members_json.len()), err)]
+pub async fn parse_portal_members(
+    carrier_id: String,
+    members_json: String,
+    state: State<'_, DbState>,
+) -> Result<PortalFetchSummary, String> {
+    let portal = carrier_sync::get_portal(&carrier_id)
+        .ok_or_else(|| format!("No portal integration for carrier: {}", carrier_id))?;
+
+    state
+        .with_read_conn(|conn| {
+            carrier_sync_service::parse_portal_members(conn, &carrier_id, portal.carrier_name(), &members_json)
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Dry run of `process_portal_members`: the same comparison, rolled back.
+/// Nothing is logged on failure either.
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(carrier_id = %carrier_id, bytes = members_json.len()), err)]
+pub fn preview_portal_sync(
+    carrier_id: String,
+    members_json: String,
+    empty_confirmed: Option<bool>,
+    state: State<'_, DbState>,
+) -> Result<SyncResult, String> {
+    let portal = carrier_sync::get_portal(&carrier_id)
+        .ok_or_else(|| format!("No portal integration for carrier: {}", carrier_id))?;
+
+    state
+        .with_conn(|conn| {
+            let members = carrier_sync_service::decode_portal_members(&members_json)?;
+            carrier_sync_service::preview_sync(
+                conn,
+                &carrier_id,
+                portal.carrier_name(),
+                &members,
+                empty_confirmed.unwrap_or(false),
+            )
+        })
+        .map_err(|e| e.to_string())
+}
+
 /// Process portal member data that was fetched by the webview JS.
 /// Compares against local enrollments and auto-updates disenrolled records.
+/// `empty_confirmed` must be set for an empty member list to be trusted.
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(carrier_id = %carrier_id, bytes = members_json.len()), err)]
 pub fn process_portal_members(
     carrier_id: String,
     members_json: String,
+    empty_confirmed: Option<bool>,
     state: State<'_, DbState>,
 ) -> Result<SyncResult, String> {
     let portal = carrier_sync::get_portal(&carrier_id)
         .ok_or_else(|| format!("No portal integration for carrier: {}", carrier_id))?;
 
-    let portal_members: Vec<PortalMember> =
-        serde_json::from_str(&members_json).map_err(|e| format!("Failed to parse member data: {}", e))?;
-
     let carrier_name = portal.carrier_name().to_string();
 
     state
         .with_conn(|conn| {
-            crate::services::carrier_sync_service::run_sync(
-                conn,
-                &carrier_id,
-                &carrier_name,
-                &portal_members,
-            )
+            // All of the sync's writes land together or not at all; a failure
+            // is logged only after its partial writes are rolled back
+            let tx = conn.unchecked_transaction()?;
+            let result = carrier_sync_service::decode_portal_members(&members_json)
+                .and_then(|portal_members| {
+                    carrier_sync_service::run_sync(
+                        &tx,
+                        &carrier_id,
+                        &carrier_name,
+                        &portal_members,
+                        empty_confirmed.unwrap_or(false),
+                    )
+                });
+            match result {
+                Ok(result) => {
+                    tx.commit()?;
+                    Ok(result)
+                }
+                Err(e) => {
+                    tx.rollback()?;
+                    let _ = carrier_sync_service::log_sync_failure(conn, &carrier_id, &e.to_string());
+                    Err(e)
+                }
+            }
         })
         .map_err(|e| e.to_string())
 }
@@ -327,6 +515,7 @@ pub fn get_carrier_sync_info(carrier_id: String) -> Result<CarrierSyncInfo, Stri
 
 /// Import selected portal members as new clients with enrollments.
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(carrier_id = %carrier_id, bytes = members_json.len()), err)]
 pub fn import_portal_members(
     carrier_id: String,
     members_json: String,
@@ -344,6 +533,7 @@ pub fn import_portal_members(
 
 /// Confirm disenrollment for selected enrollment IDs.
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(count = enrollment_ids.len()), err)]
 pub fn confirm_disenrollments(
     enrollment_ids: Vec<String>,
     state: State<'_, DbState>,
@@ -355,6 +545,14 @@ pub fn confirm_disenrollments(
         .map_err(|e| e.to_string())
 }
 
+/// Active enrollments where the carrier shows the agent is no longer the AOR.
+#[tauri::command]
+pub async fn get_lost_aor_enrollments(state: State<'_, DbState>) -> Result<Vec<SyncAorLoss>, String> {
+    state
+        .with_read_conn(carrier_sync_service::get_lost_aor_enrollments)
+        .map_err(|e| e.to_string())
+}
+
 /// Get sync log history.
 #[tauri::command]
 pub fn get_sync_logs(
@@ -368,6 +566,42 @@ pub fn get_sync_logs(
         .map_err(|e| e.to_string())
 }
 
+/// Get the raw portal member list recorded for a sync.
+#[tauri::command]
+pub fn get_sync_snapshot(
+    sync_log_id: String,
+    state: State<'_, DbState>,
+) -> Result<SyncSnapshot, String> {
+    state
+        .with_conn(|conn| {
+            crate::services::carrier_sync_service::get_sync_snapshot(conn, &sync_log_id)
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Pending sync review items (new portal members, possible matches).
+#[tauri::command]
+pub async fn get_sync_review_items(
+    carrier_id: Option<String>,
+    state: State<'_, DbState>,
+) -> Result<Vec<SyncReviewItem>, String> {
+    state
+        .with_read_conn(|conn| carrier_sync_service::get_sync_review_items(conn, carrier_id.as_deref()))
+        .map_err(|e| e.to_string())
+}
+
+/// Resolve a review item: `confirm_match`, `create_new`, or `ignore`.
+#[tauri::command]
+pub fn resolve_sync_review_item(
+    id: String,
+    action: SyncReviewAction,
+    state: State<'_, DbState>,
+) -> Result<SyncReviewItem, String> {
+    state
+        .with_conn(|conn| carrier_sync_service::resolve_sync_review_item(conn, &id, action))
+        .map_err(|e| e.to_string())
+}
+
 /// Save portal credentials for a carrier (stored in app_settings).
 #[tauri::command]
 pub fn save_portal_credentials(
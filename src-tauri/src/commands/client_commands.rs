@@ -1,28 +1,43 @@
 use tauri::State;
 use crate::db::DbState;
-use crate::models::{Client, ClientFilters, ClientListItem, CreateClientInput, PaginatedResult, UpdateClientInput};
-use crate::services::client_service;
+use crate::models::{AgingInClient, Client, ClientFilters, ClientListItem, CreateClientInput, PaginatedResult, SepHints, UpdateClientInput};
+use crate::services::{client_service, sep_service};
 use crate::services::matching::{DuplicateCandidate, DuplicateGroup};
 
 #[tauri::command]
-pub fn get_clients(
+pub async fn get_clients(
     filters: ClientFilters,
     page: i32,
     per_page: i32,
     state: State<'_, DbState>,
 ) -> Result<PaginatedResult<ClientListItem>, String> {
-    state.with_conn(|conn| {
+    state.with_read_conn(|conn| {
         client_service::get_clients(conn, &filters, page, per_page)
     }).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn get_client(id: String, state: State<'_, DbState>) -> Result<Client, String> {
-    state.with_conn(|conn| {
+pub async fn count_clients(filters: ClientFilters, state: State<'_, DbState>) -> Result<i64, String> {
+    state.with_read_conn(|conn| {
+        client_service::count_clients(conn, &filters)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_client(id: String, state: State<'_, DbState>) -> Result<Client, String> {
+    state.with_read_conn(|conn| {
         client_service::get_client(conn, &id)
     }).map_err(|e| e.to_string())
 }
 
+/// Heuristic SEP eligibility hints for one client
+#[tauri::command]
+pub async fn get_sep_hints(client_id: String, state: State<'_, DbState>) -> Result<SepHints, String> {
+    state.with_read_conn(|conn| {
+        sep_service::get_sep_hints(conn, &client_id)
+    }).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn create_client(input: CreateClientInput, state: State<'_, DbState>) -> Result<Client, String> {
     state.with_conn(|conn| {
@@ -37,6 +52,32 @@ pub fn update_client(id: String, input: UpdateClientInput, state: State<'_, DbSt
     }).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn add_client_tag(id: String, tag: String, state: State<'_, DbState>) -> Result<Client, String> {
+    state.with_conn(|conn| {
+        client_service::add_client_tag(conn, &id, &tag)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn remove_client_tag(id: String, tag: String, state: State<'_, DbState>) -> Result<Client, String> {
+    state.with_conn(|conn| {
+        client_service::remove_client_tag(conn, &id, &tag)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn bulk_tag_clients(
+    client_ids: Vec<String>,
+    tag: String,
+    add: bool,
+    state: State<'_, DbState>,
+) -> Result<usize, String> {
+    state.with_conn(|conn| {
+        client_service::bulk_tag_clients(conn, &client_ids, &tag, add)
+    }).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn delete_client(id: String, state: State<'_, DbState>) -> Result<(), String> {
     state.with_conn(|conn| {
@@ -51,6 +92,22 @@ pub fn hard_delete_client(id: String, state: State<'_, DbState>) -> Result<(), S
     }).map_err(|e| e.to_string())
 }
 
+/// Permanently delete clients soft-deleted more than `older_than_days` ago.
+#[tauri::command]
+pub fn purge_inactive_clients(older_than_days: i64, state: State<'_, DbState>) -> Result<usize, String> {
+    state.with_conn(|conn| {
+        client_service::purge_inactive_clients(conn, older_than_days)
+    }).map_err(|e| e.to_string())
+}
+
+/// Clients turning 65 in the next `months_ahead` months (IEP pipeline).
+#[tauri::command]
+pub fn get_aging_in_clients(months_ahead: i64, state: State<'_, DbState>) -> Result<Vec<AgingInClient>, String> {
+    state.with_conn(|conn| {
+        client_service::get_aging_in_clients(conn, months_ahead)
+    }).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn merge_clients(keeper_id: String, source_id: String, state: State<'_, DbState>) -> Result<Client, String> {
     state.with_conn(|conn| {
@@ -84,18 +141,38 @@ pub fn find_duplicate_clients(state: State<'_, DbState>) -> Result<Vec<Duplicate
     }).map_err(|e| e.to_string())
 }
 
+/// Backfill `is_dual_eligible` from `dual_status_code` across the whole book.
+/// Returns the number of clients whose flag changed.
+#[tauri::command]
+pub fn recompute_dual_eligibility(state: State<'_, DbState>) -> Result<usize, String> {
+    state.with_conn(|conn| {
+        client_service::recompute_dual_eligibility(conn)
+    }).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn delete_all_clients(state: State<'_, DbState>) -> Result<serde_json::Value, String> {
     state.with_conn(|conn| {
         // Delete related data first (foreign key children)
         conn.execute("DELETE FROM conversation_entries WHERE client_id IN (SELECT id FROM clients)", [])?;
         conn.execute("DELETE FROM conversations WHERE client_id IN (SELECT id FROM clients)", [])?;
+        conn.execute(
+            "DELETE FROM sync_review_items WHERE client_id IS NOT NULL OR enrollment_id IS NOT NULL",
+            [],
+        )?;
         conn.execute("DELETE FROM enrollments WHERE client_id IN (SELECT id FROM clients)", [])?;
         let count: i64 = conn.query_row("SELECT COUNT(*) FROM clients", [], |r| r.get(0))
             .unwrap_or(0);
         conn.execute("DELETE FROM clients", [])?;
         // Rebuild FTS index
         conn.execute("INSERT INTO clients_fts(clients_fts) VALUES('rebuild')", [])?;
+        crate::services::audit_service::record(
+            conn,
+            crate::services::audit_service::CLIENT,
+            "*",
+            "PURGE",
+            Some(serde_json::json!({ "all_clients": count })),
+        )?;
         Ok(serde_json::json!({ "deleted": count }))
     }).map_err(|e| e.to_string())
 }
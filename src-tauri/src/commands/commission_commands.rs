@@ -396,7 +396,7 @@ const COMMISSION_SUBMIT_TEMPLATE: &str = r#"
 
     } catch (e) {
         console.error('[Compass] Phase 1 ERROR: ' + e.toString());
-        window.location.href = 'http://compass-sync.localhost/error?message=' +
+        window.location.href = 'http://compass-sync.localhost/error?source=commission&message=' +
             encodeURIComponent('Commission fetch: ' + e.toString());
     }
 })();
@@ -505,7 +505,7 @@ const COMMISSION_DOWNLOAD_SCRIPT: &str = r#"
 
     } catch (e) {
         console.error('[Compass] Phase 2 ERROR:', e);
-        window.location.href = 'http://compass-sync.localhost/error?message=' +
+        window.location.href = 'http://compass-sync.localhost/error?source=commission&message=' +
             encodeURIComponent('Commission download: ' + e.toString());
     }
 })();
@@ -2,8 +2,9 @@ use tauri::State;
 
 use crate::db::DbState;
 use crate::models::{
-    Conversation, ConversationEntry, ConversationListItem, CreateConversationEntryInput,
-    CreateConversationInput, TimelineEntry, UpdateConversationEntryInput, UpdateConversationInput,
+    ClientNextAction, Conversation, ConversationEntry, ConversationListItem, ConversationStatus,
+    CreateConversationEntryInput, CreateConversationInput, EntryType, TimelineEntry, UpdateConversationEntryInput,
+    UpdateConversationInput,
 };
 use crate::services::conversation_service;
 
@@ -45,6 +46,17 @@ pub fn update_conversation(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn bulk_update_conversation_status(
+    ids: Vec<String>,
+    status: ConversationStatus,
+    state: State<'_, DbState>,
+) -> Result<usize, String> {
+    state
+        .with_conn(|conn| conversation_service::bulk_update_conversation_status(conn, &ids, status))
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_conversation_entries(
     conversation_id: String,
@@ -79,7 +91,7 @@ pub fn update_conversation_entry(
 #[tauri::command]
 pub fn get_client_timeline(
     client_id: String,
-    entry_type_filter: Option<String>,
+    entry_type_filter: Option<EntryType>,
     limit: Option<i64>,
     offset: Option<i64>,
     state: State<'_, DbState>,
@@ -89,7 +101,7 @@ pub fn get_client_timeline(
             conversation_service::get_client_timeline(
                 conn,
                 &client_id,
-                entry_type_filter.as_deref(),
+                entry_type_filter,
                 limit,
                 offset,
             )
@@ -100,10 +112,27 @@ pub fn get_client_timeline(
 #[tauri::command]
 pub fn get_pending_follow_ups(
     client_id: Option<String>,
+    include_overdue: Option<bool>,
     state: State<'_, DbState>,
 ) -> Result<Vec<TimelineEntry>, String> {
     state
-        .with_conn(|conn| conversation_service::get_pending_follow_ups(conn, client_id.as_deref()))
+        .with_conn(|conn| {
+            conversation_service::get_pending_follow_ups(
+                conn,
+                client_id.as_deref(),
+                include_overdue.unwrap_or(false),
+            )
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_client_next_action(
+    client_id: String,
+    state: State<'_, DbState>,
+) -> Result<Option<ClientNextAction>, String> {
+    state
+        .with_conn(|conn| conversation_service::get_client_next_action(conn, &client_id))
         .map_err(|e| e.to_string())
 }
 
@@ -1,18 +1,39 @@
 use tauri::State;
 use crate::db::DbState;
-use crate::models::{CreateEnrollmentInput, Enrollment, EnrollmentListItem, UpdateEnrollmentInput};
+use crate::models::{CreateEnrollmentInput, Enrollment, EnrollmentHistoryItem, EnrollmentListItem, PaginatedResult, UpdateEnrollmentInput};
 use crate::services::enrollment_service;
 
 #[tauri::command]
-pub fn get_enrollments(
+pub async fn get_enrollments(
     client_id: Option<String>,
     state: State<'_, DbState>,
 ) -> Result<Vec<EnrollmentListItem>, String> {
-    state.with_conn(|conn| {
+    state.with_read_conn(|conn| {
         enrollment_service::get_enrollments(conn, client_id.as_deref())
     }).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn get_enrollment_history(
+    client_id: String,
+    state: State<'_, DbState>,
+) -> Result<Vec<EnrollmentHistoryItem>, String> {
+    state.with_read_conn(|conn| {
+        enrollment_service::get_enrollment_history(conn, &client_id)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_enrollments_page(
+    page: i32,
+    per_page: i32,
+    state: State<'_, DbState>,
+) -> Result<PaginatedResult<EnrollmentListItem>, String> {
+    state.with_read_conn(|conn| {
+        enrollment_service::get_enrollments_page(conn, page, per_page)
+    }).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn create_enrollment(input: CreateEnrollmentInput, state: State<'_, DbState>) -> Result<Enrollment, String> {
     state.with_conn(|conn| {
@@ -26,3 +47,31 @@ pub fn update_enrollment(id: String, input: UpdateEnrollmentInput, state: State<
         enrollment_service::update_enrollment(conn, &id, &input)
     }).map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub fn delete_enrollment(id: String, state: State<'_, DbState>) -> Result<(), String> {
+    state.with_conn(|conn| {
+        enrollment_service::delete_enrollment(conn, &id)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn restore_enrollment(id: String, state: State<'_, DbState>) -> Result<Enrollment, String> {
+    state.with_conn(|conn| {
+        enrollment_service::restore_enrollment(conn, &id)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn detect_renewable_enrollments(plan_year: i32, state: State<'_, DbState>) -> Result<Vec<EnrollmentListItem>, String> {
+    state.with_conn(|conn| {
+        enrollment_service::detect_renewable_enrollments(conn, plan_year)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn renew_enrollment(id: String, new_effective_date: String, state: State<'_, DbState>) -> Result<Enrollment, String> {
+    state.with_conn(|conn| {
+        enrollment_service::renew_enrollment(conn, &id, &new_effective_date)
+    }).map_err(|e| e.to_string())
+}
@@ -3,58 +3,129 @@ use tauri::State;
 
 use crate::db::DbState;
 use crate::services::import_service;
-use crate::services::import_service::ActivityImportResult;
+use crate::services::import_service::{ActivityImportResult, ImportMappingPreset, UndoImportResult};
 
+/// Parse the file and suggest a mapping. A saved preset whose columns are all
+/// present is used first; `auto_map_columns` fills in any other headers.
 #[tauri::command]
-pub fn parse_import_file(file_path: String) -> Result<serde_json::Value, String> {
+#[tracing::instrument(skip_all, fields(file = %file_path), err)]
+pub fn parse_import_file(
+    file_path: String,
+    state: State<'_, DbState>,
+) -> Result<serde_json::Value, String> {
     let parsed = import_service::parse_file(&file_path).map_err(|e| e.to_string())?;
-    let mapping = import_service::auto_map_columns(&parsed.headers);
+    let preset = state
+        .with_read_conn(|conn| import_service::find_preset_for_headers(conn, &parsed.headers))
+        .map_err(|e| e.to_string())?;
+
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    let mut confidence: HashMap<String, f64> = HashMap::new();
+    let mut preset_info = serde_json::Value::Null;
+    if let Some(preset) = preset {
+        for (header, target) in preset.mapping {
+            confidence.insert(header.clone(), 1.0);
+            mapping.insert(header, target);
+        }
+        preset_info = serde_json::json!({ "id": preset.id, "name": preset.name });
+    }
+    for (header, guess) in import_service::auto_map_columns(&parsed.headers) {
+        if mapping.contains_key(&header) || mapping.values().any(|t| *t == guess.target) {
+            continue;
+        }
+        confidence.insert(header.clone(), guess.confidence);
+        mapping.insert(header, guess.target);
+    }
 
     serde_json::to_value(serde_json::json!({
         "headers": parsed.headers,
         "sample_rows": parsed.sample_rows,
         "total_rows": parsed.total_rows,
         "auto_mapping": mapping,
+        "mapping_confidence": confidence,
+        "preset": preset_info,
     }))
     .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(name = %name), err)]
+pub fn save_import_mapping(
+    name: String,
+    mapping: HashMap<String, String>,
+    state: State<'_, DbState>,
+) -> Result<ImportMappingPreset, String> {
+    state
+        .with_conn(|conn| import_service::save_import_mapping(conn, &name, &mapping))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+pub async fn list_import_mappings(
+    state: State<'_, DbState>,
+) -> Result<Vec<ImportMappingPreset>, String> {
+    state
+        .with_read_conn(import_service::list_import_mappings)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+pub async fn get_import_mapping(
+    id: String,
+    state: State<'_, DbState>,
+) -> Result<ImportMappingPreset, String> {
+    state
+        .with_read_conn(|conn| import_service::get_import_mapping(conn, &id))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(file = %file_path), err)]
 pub fn validate_import(
     file_path: String,
     column_mapping: HashMap<String, String>,
+    state: State<'_, DbState>,
 ) -> Result<serde_json::Value, String> {
     let (headers, all_rows) =
         import_service::get_all_rows(&file_path).map_err(|e| e.to_string())?;
+    let known_states = state
+        .with_conn(import_service::load_state_codes)
+        .map_err(|e| e.to_string())?;
 
-    let result = import_service::validate_rows(&all_rows, &headers, &column_mapping);
+    let result = import_service::validate_rows(&all_rows, &headers, &column_mapping, &known_states);
 
     serde_json::to_value(&result).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(file = %file_path), err)]
 pub fn preview_import(
     file_path: String,
     column_mapping: HashMap<String, String>,
     constant_values: Option<HashMap<String, String>>,
+    only_fill_empty: Option<bool>,
     state: State<'_, DbState>,
 ) -> Result<serde_json::Value, String> {
     let constant_values = constant_values.unwrap_or_default();
     let (headers, all_rows) =
         import_service::get_all_rows(&file_path).map_err(|e| e.to_string())?;
 
-    let validation = import_service::validate_rows(&all_rows, &headers, &column_mapping);
-
     state
         .with_conn(|conn| {
+            let known_states = import_service::load_state_codes(conn)?;
+            let validation =
+                import_service::validate_rows(&all_rows, &headers, &column_mapping, &known_states);
             let mut preview = import_service::preview_import(
                 conn,
                 &validation.valid_rows,
                 &headers,
                 &column_mapping,
                 &constant_values,
+                only_fill_empty.unwrap_or(false),
             )?;
             preview.errors = validation.error_rows;
+            preview.warnings = validation.warning_rows;
 
             serde_json::to_value(&preview)
                 .map_err(|e| crate::error::AppError::Import(e.to_string()))
@@ -63,46 +134,60 @@ pub fn preview_import(
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(file = %file_path), err)]
+#[allow(clippy::too_many_arguments)]
 pub fn execute_import(
     file_path: String,
     column_mapping: HashMap<String, String>,
     constant_values: Option<HashMap<String, String>>,
     approved_updates: Option<HashMap<String, Vec<String>>>,
     approved_inserts: Option<Vec<usize>>,
+    import_enrollments: Option<bool>,
+    only_fill_empty: Option<bool>,
     state: State<'_, DbState>,
 ) -> Result<serde_json::Value, String> {
     let constant_values = constant_values.unwrap_or_default();
-    let (headers, all_rows) =
-        import_service::get_all_rows(&file_path).map_err(|e| e.to_string())?;
-
-    // Only import valid rows
-    let validation = import_service::validate_rows(&all_rows, &headers, &column_mapping);
+    let (headers, rows) =
+        import_service::stream_rows(&file_path).map_err(|e| e.to_string())?;
 
     state
         .with_conn(|conn| {
+            // Invalid rows are validated and counted as errors while streaming
+            let known_states = import_service::load_state_codes(conn)?;
+            // The log id doubles as the batch id written onto imported rows
+            let log_id = uuid::Uuid::new_v4().to_string();
+            let filename = std::path::Path::new(&file_path)
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_else(|| file_path.clone());
+            // One transaction for the rows and their log entry: a single fsync
+            // instead of one per statement, no half-imported file if reading
+            // fails part way through, and never a batch without the log row
+            // `undo_import` looks it up by. Dropping `tx` on an error rolls it back.
+            let mut tx = conn.unchecked_transaction()?;
             let result = import_service::execute_import(
-                conn,
-                &validation.valid_rows,
+                &mut tx,
+                rows,
                 &headers,
                 &column_mapping,
                 &constant_values,
+                &known_states,
                 approved_updates.as_ref(),
                 approved_inserts.as_ref(),
+                import_enrollments.unwrap_or(false),
+                only_fill_empty.unwrap_or(false),
+                &log_id,
+                Some(&filename),
             )?;
 
             // Log the import
-            let log_id = uuid::Uuid::new_v4().to_string();
-            let filename = std::path::Path::new(&file_path)
-                .file_name()
-                .map(|f| f.to_string_lossy().to_string())
-                .unwrap_or_else(|| file_path.clone());
             let file_type = if file_path.to_lowercase().ends_with(".csv") {
                 "CSV"
             } else {
                 "XLSX"
             };
 
-            conn.execute(
+            tx.execute(
                 "INSERT INTO import_logs (id, filename, file_type, total_rows, inserted_rows, updated_rows, skipped_rows, error_rows, column_mapping, status)
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 'COMPLETED')",
                 rusqlite::params![
@@ -117,32 +202,38 @@ pub fn execute_import(
                     serde_json::to_string(&column_mapping).unwrap_or_default()
                 ],
             )?;
-
-            // Combine execution error details with validation error details
-            let mut all_error_details = result.error_details;
-            for err_row in &validation.error_rows {
-                all_error_details.push(import_service::ImportRowDetail {
-                    label: format!("Row {}", err_row.row_number),
-                    detail: err_row.errors.join("; "),
-                });
-            }
+            tx.commit()?;
 
             serde_json::to_value(serde_json::json!({
+                "import_log_id": log_id,
                 "inserted": result.inserted,
                 "updated": result.updated,
                 "skipped": result.skipped,
-                "errors": result.errors + validation.error_rows.len(),
-                "total": result.total + validation.error_rows.len(),
+                "errors": result.errors,
+                "total": result.total,
+                "enrollments_created": result.enrollments_created,
                 "inserted_details": result.inserted_details,
                 "updated_details": result.updated_details,
                 "skipped_details": result.skipped_details,
-                "errors_details": all_error_details,
+                "errors_details": result.error_details,
             }))
             .map_err(|e| crate::error::AppError::Import(e.to_string()))
         })
         .map_err(|e| e.to_string())
 }
 
+/// Reverse a completed file import (see `import_service::undo_import`)
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(import_log_id = %import_log_id), err)]
+pub fn undo_import(
+    import_log_id: String,
+    state: State<'_, DbState>,
+) -> Result<UndoImportResult, String> {
+    state
+        .with_conn(|conn| import_service::undo_import(conn, &import_log_id))
+        .map_err(|e| e.to_string())
+}
+
 /// Log an activity import result to the import_logs table.
 fn log_activity_import(
     conn: &rusqlite::Connection,
@@ -171,6 +262,7 @@ fn log_activity_import(
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(source = %source_path), err)]
 pub fn import_call_log(
     source_path: String,
     state: State<'_, DbState>,
@@ -185,6 +277,7 @@ pub fn import_call_log(
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(source = %source_path), err)]
 pub fn import_integrity(
     source_path: String,
     state: State<'_, DbState>,
@@ -199,6 +292,7 @@ pub fn import_integrity(
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(source = %dump_path), err)]
 pub fn import_sirem(
     dump_path: String,
     state: State<'_, DbState>,
@@ -213,6 +307,7 @@ pub fn import_sirem(
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, fields(source = %source_path), err)]
 pub fn enrich_leadsmaster(
     source_path: String,
     state: State<'_, DbState>,
@@ -1,3 +1,4 @@
+pub mod audit_commands;
 pub mod auth_commands;
 pub mod carrier_commands;
 pub mod carrier_sync_commands;
@@ -6,9 +7,11 @@ pub mod commission_commands;
 pub mod conversation_commands;
 pub mod enrollment_commands;
 pub mod import_commands;
+pub mod plan_commands;
 pub mod report_commands;
 pub mod settings_commands;
 
+pub use audit_commands::*;
 pub use auth_commands::*;
 pub use carrier_commands::*;
 pub use carrier_sync_commands::*;
@@ -17,5 +20,6 @@ pub use commission_commands::*;
 pub use conversation_commands::*;
 pub use enrollment_commands::*;
 pub use import_commands::*;
+pub use plan_commands::*;
 pub use report_commands::*;
 pub use settings_commands::*;
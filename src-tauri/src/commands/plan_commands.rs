@@ -0,0 +1,48 @@
+use tauri::State;
+use crate::db::DbState;
+use crate::models::{CreatePlanInput, Plan, PlanListItem, UpdatePlanInput};
+use crate::services::plan_service;
+
+#[tauri::command]
+pub async fn get_plans(
+    carrier_id: Option<String>,
+    plan_year: Option<i32>,
+    state: State<'_, DbState>,
+) -> Result<Vec<PlanListItem>, String> {
+    state.with_read_conn(|conn| {
+        plan_service::get_plans(conn, carrier_id.as_deref(), plan_year)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_plan(id: String, state: State<'_, DbState>) -> Result<Plan, String> {
+    state.with_read_conn(|conn| {
+        plan_service::get_plan(conn, &id)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn find_plan_by_contract_pbp(
+    contract_number: String,
+    pbp_number: String,
+    plan_year: Option<i32>,
+    state: State<'_, DbState>,
+) -> Result<Option<Plan>, String> {
+    state.with_read_conn(|conn| {
+        plan_service::find_plan_by_contract_pbp(conn, &contract_number, &pbp_number, plan_year)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_plan(input: CreatePlanInput, state: State<'_, DbState>) -> Result<Plan, String> {
+    state.with_conn(|conn| {
+        plan_service::create_plan(conn, &input)
+    }).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_plan(id: String, input: UpdatePlanInput, state: State<'_, DbState>) -> Result<Plan, String> {
+    state.with_conn(|conn| {
+        plan_service::update_plan(conn, &id, &input)
+    }).map_err(|e| e.to_string())
+}
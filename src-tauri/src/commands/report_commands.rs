@@ -2,23 +2,26 @@ use tauri::State;
 
 use crate::db::DbState;
 use crate::models::report::{DashboardStats, ReportDefinition};
+use crate::models::ClientFilters;
 use crate::services::{dashboard_service, report_service};
 use crate::AppDataDir;
 
+// Read-only and potentially slow, so these run off the main thread on a
+// pooled reader instead of queueing behind the writer
 #[tauri::command]
-pub fn get_dashboard_stats(state: State<'_, DbState>) -> Result<DashboardStats, String> {
+pub async fn get_dashboard_stats(state: State<'_, DbState>) -> Result<DashboardStats, String> {
     state
-        .with_conn(|conn| dashboard_service::get_dashboard_stats(conn))
+        .with_read_conn(|conn| dashboard_service::get_dashboard_stats(conn))
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn get_report(
+pub async fn get_report(
     definition: ReportDefinition,
     state: State<'_, DbState>,
 ) -> Result<serde_json::Value, String> {
     state
-        .with_conn(|conn| report_service::run_report(conn, &definition))
+        .with_read_conn(|conn| report_service::run_report(conn, &definition))
         .map_err(|e| e.to_string())
 }
 
@@ -32,3 +35,40 @@ pub fn export_report_pdf(
         .with_conn(|conn| report_service::generate_pdf(conn, &definition, &app_data_dir.0))
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub fn export_report_xlsx(
+    definition: ReportDefinition,
+    app_data_dir: State<'_, AppDataDir>,
+    state: State<'_, DbState>,
+) -> Result<String, String> {
+    state
+        .with_conn(|conn| report_service::generate_xlsx(conn, &definition, &app_data_dir.0))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn export_clients_vcard(
+    filters: ClientFilters,
+    output_path: String,
+    state: State<'_, DbState>,
+) -> Result<usize, String> {
+    state
+        .with_conn(|conn| {
+            report_service::export_clients_vcard(conn, &filters, std::path::Path::new(&output_path))
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn export_clients_csv(
+    filters: ClientFilters,
+    output_path: String,
+    state: State<'_, DbState>,
+) -> Result<usize, String> {
+    state
+        .with_conn(|conn| {
+            report_service::export_clients_csv(conn, &filters, std::path::Path::new(&output_path))
+        })
+        .map_err(|e| e.to_string())
+}
@@ -1,18 +1,37 @@
 use serde::Serialize;
 use tauri::State;
 
-use crate::db::DbState;
+use crate::db::{migrations, DbState};
+use crate::services::{backup_service, client_service, log_service};
 use crate::AppDataDir;
 
 #[derive(Serialize)]
 pub struct DatabaseInfo {
     pub db_path: String,
     pub db_size_bytes: u64,
+    /// Size of `compass.db-wal`; 0 when there is none or it's been checkpointed
+    pub wal_size_bytes: u64,
     pub client_count: i64,
     pub enrollment_count: i64,
+    /// Every row in the tables that tend to grow, soft-deleted ones included
+    pub table_row_counts: Vec<TableRowCount>,
     pub last_backup: Option<String>,
 }
 
+#[derive(Serialize)]
+pub struct TableRowCount {
+    pub table: &'static str,
+    pub row_count: i64,
+}
+
+/// Tables broken out in `DatabaseInfo` to show what is taking up space
+const SIZE_BREAKDOWN_TABLES: &[&str] = &[
+    "conversations",
+    "conversation_entries",
+    "import_logs",
+    "carrier_sync_logs",
+];
+
 #[tauri::command]
 pub fn get_database_info(
     db_state: State<'_, DbState>,
@@ -24,6 +43,9 @@ pub fn get_database_info(
     let db_size_bytes = std::fs::metadata(&db_path)
         .map(|m| m.len())
         .unwrap_or(0);
+    let wal_size_bytes = std::fs::metadata(app_data_dir.0.join("compass.db-wal"))
+        .map(|m| m.len())
+        .unwrap_or(0);
 
     db_state
         .with_conn(|conn| {
@@ -43,6 +65,15 @@ pub fn get_database_info(
                 )
                 .map_err(|e| crate::error::AppError::Database(e.to_string()))?;
 
+            let table_row_counts = SIZE_BREAKDOWN_TABLES
+                .iter()
+                .map(|&table| {
+                    conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+                        .map(|row_count| TableRowCount { table, row_count })
+                        .map_err(|e| crate::error::AppError::Database(e.to_string()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
             let last_backup: Option<String> = conn
                 .query_row(
                     "SELECT value FROM app_settings WHERE key = 'last_backup_at'",
@@ -54,14 +85,174 @@ pub fn get_database_info(
             Ok(DatabaseInfo {
                 db_path: db_path_str,
                 db_size_bytes,
+                wal_size_bytes,
                 client_count,
                 enrollment_count,
+                table_row_counts,
                 last_backup,
             })
         })
         .map_err(|e| e.to_string())
 }
 
+#[derive(Serialize)]
+pub struct HealthReport {
+    pub app_version: String,
+    pub db_initialized: bool,
+    /// `PRAGMA user_version`; None while the database is locked
+    pub schema_version: Option<i32>,
+    pub expected_schema_version: i32,
+    pub carrier_count: Option<i64>,
+    pub state_count: Option<i64>,
+    pub plan_type_count: Option<i64>,
+    /// Unlocked, fully migrated, and every reference table seeded
+    pub ok: bool,
+}
+
+/// One-shot diagnostic for support. Never fails: a locked database just
+/// reports `db_initialized: false`.
+#[tauri::command]
+pub fn health_check(db_state: State<'_, DbState>) -> Result<HealthReport, String> {
+    let mut report = HealthReport {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        db_initialized: db_state.is_initialized(),
+        schema_version: None,
+        expected_schema_version: migrations::latest_version(),
+        carrier_count: None,
+        state_count: None,
+        plan_type_count: None,
+        ok: false,
+    };
+    if !report.db_initialized {
+        return Ok(report);
+    }
+
+    let _ = db_state.with_conn(|conn| {
+        let count = |table: &str| {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get::<_, i64>(0))
+                .ok()
+        };
+        report.schema_version = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .ok();
+        report.carrier_count = count("carriers");
+        report.state_count = count("states");
+        report.plan_type_count = count("plan_types");
+        Ok(())
+    });
+
+    report.ok = report.schema_version == Some(report.expected_schema_version)
+        && [report.carrier_count, report.state_count, report.plan_type_count]
+            .iter()
+            .all(|c| c.is_some_and(|n| n > 0));
+    Ok(report)
+}
+
+#[derive(Serialize)]
+pub struct OptimizeResult {
+    pub size_before_bytes: u64,
+    pub size_after_bytes: u64,
+}
+
+/// Combined size of the database file and its WAL, which is where recent
+/// writes live until they are checkpointed.
+fn db_files_size(app_data_dir: &std::path::Path) -> u64 {
+    ["compass.db", "compass.db-wal"]
+        .iter()
+        .filter_map(|name| std::fs::metadata(app_data_dir.join(name)).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Refresh query-planner statistics, rebuild the database file to reclaim
+/// free pages, and fold the WAL back into the main file.
+///
+/// VACUUM cannot run inside a transaction or while another connection has a
+/// read open. Holding the writer through `with_conn` keeps out other writes;
+/// pooled readers only run short queries, and rusqlite's default busy
+/// timeout covers waiting for one to finish. Nothing in the closure may have
+/// a statement or transaction open.
+#[tauri::command]
+pub fn optimize_database(
+    db_state: State<'_, DbState>,
+    app_data_dir: State<'_, AppDataDir>,
+) -> Result<OptimizeResult, String> {
+    db_state
+        .with_conn(|conn| {
+            let size_before_bytes = db_files_size(&app_data_dir.0);
+
+            conn.execute_batch("PRAGMA optimize; VACUUM;")?;
+            // wal_checkpoint returns a (busy, log, checkpointed) row
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+
+            Ok(OptimizeResult {
+                size_before_bytes,
+                size_after_bytes: db_files_size(&app_data_dir.0),
+            })
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Rebuild and optimize the client search index, e.g. when search results
+/// look stale after bulk changes
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+pub fn optimize_search_index(db_state: State<'_, DbState>) -> Result<(), String> {
+    db_state
+        .with_conn(client_service::optimize_search_index)
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Serialize)]
+pub struct ForeignKeyViolation {
+    pub table: String,
+    pub rowid: Option<i64>,
+    pub parent: String,
+}
+
+#[derive(Serialize)]
+pub struct IntegrityReport {
+    /// True when both checks came back clean
+    pub ok: bool,
+    /// Messages from `PRAGMA integrity_check` (empty when it reports "ok")
+    pub integrity_errors: Vec<String>,
+    pub foreign_key_violations: Vec<ForeignKeyViolation>,
+}
+
+/// Run SQLite's integrity and foreign-key checks so users can self-diagnose
+/// a database that misbehaves after an unclean shutdown.
+#[tauri::command]
+pub fn check_database_integrity(db_state: State<'_, DbState>) -> Result<IntegrityReport, String> {
+    db_state
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+            let integrity_errors: Vec<String> = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .filter(|msg| msg != "ok")
+                .collect();
+
+            let mut stmt = conn.prepare("PRAGMA foreign_key_check")?;
+            let foreign_key_violations = stmt
+                .query_map([], |row| {
+                    Ok(ForeignKeyViolation {
+                        table: row.get(0)?,
+                        rowid: row.get(1)?,
+                        parent: row.get(2)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(IntegrityReport {
+                ok: integrity_errors.is_empty() && foreign_key_violations.is_empty(),
+                integrity_errors,
+                foreign_key_violations,
+            })
+        })
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_settings(state: State<'_, DbState>) -> Result<serde_json::Value, String> {
     state
@@ -212,23 +403,57 @@ pub fn save_agent_profile(
 #[tauri::command]
 pub fn backup_database(
     destination: String,
-    app_data_dir: State<'_, AppDataDir>,
     db_state: State<'_, DbState>,
 ) -> Result<(), String> {
-    let db_path = app_data_dir.0.join("compass.db");
-    std::fs::copy(&db_path, &destination).map_err(|e| format!("Backup failed: {}", e))?;
+    db_state
+        .with_conn(|conn| backup_service::create_backup(conn, std::path::Path::new(&destination)))
+        .map_err(|e| e.to_string())
+}
 
-    // Record the backup timestamp
+/// Write a de-identified, unencrypted copy of the database for support
+#[tauri::command]
+pub fn export_anonymized_copy(
+    destination: String,
+    db_state: State<'_, DbState>,
+) -> Result<(), String> {
     db_state
         .with_conn(|conn| {
-            conn.execute(
-                "INSERT INTO app_settings (key, value) VALUES ('last_backup_at', datetime('now')) ON CONFLICT(key) DO UPDATE SET value = datetime('now'), updated_at = datetime('now')",
-                [],
-            )
-            .map_err(|e| crate::error::AppError::Database(e.to_string()))?;
-            Ok(())
+            backup_service::export_anonymized_copy(conn, std::path::Path::new(&destination))
         })
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())
+}
+
+/// Zip the daily log files to a user-selected location for support. Works
+/// before login, since the logs aren't in the encrypted database.
+#[tauri::command]
+pub fn export_logs(
+    destination: String,
+    app_data_dir: State<'_, AppDataDir>,
+) -> Result<usize, String> {
+    log_service::export_logs(&app_data_dir.0, std::path::Path::new(&destination))
+        .map_err(|e| e.to_string())
+}
 
-    Ok(())
+/// Maintainer-only recovery from a bad upgrade: roll the schema back to
+/// `target_version`. Refused unless the app was started with
+/// `COMPASS_MAINTENANCE=1`, and a full backup is written next to the
+/// database first, since rolled-back columns and tables lose their data.
+#[tauri::command]
+pub fn rollback_migrations(
+    target_version: i32,
+    db_state: State<'_, DbState>,
+    app_data_dir: State<'_, AppDataDir>,
+) -> Result<(), String> {
+    if std::env::var("COMPASS_MAINTENANCE").as_deref() != Ok("1") {
+        return Err("Migration rollback is only available when the app is started with COMPASS_MAINTENANCE=1".to_string());
+    }
+    db_state
+        .with_conn(|conn| {
+            let current: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+            let backup = app_data_dir.0.join(format!("compass_pre_rollback_v{}.db", current));
+            backup_service::backup_to(conn, &backup)?;
+            backup_service::verify_backup(conn, &backup)?;
+            migrations::rollback_to(conn, target_version)
+        })
+        .map_err(|e| e.to_string())
 }
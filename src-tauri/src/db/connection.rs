@@ -1,31 +1,78 @@
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 
 use crate::error::AppError;
 
+/// Idle timeout used when `session_timeout_minutes` has never been set
+const DEFAULT_SESSION_TIMEOUT_MINUTES: u64 = 30;
+
+/// Read-only connections opened alongside the writer. `generation` is bumped
+/// whenever the session is locked, closed or rekeyed, so a connection that
+/// was checked out at the time is dropped instead of returned to the pool.
+#[derive(Default)]
+struct ReadPool {
+    generation: u64,
+    idle: Vec<Connection>,
+}
+
+/// SQLite allows one writer, so every write goes through the single `conn`.
+/// Read-only paths can use `with_read_conn` to run alongside it (the
+/// database is in WAL mode, so readers never block the writer).
 pub struct DbState {
     pub conn: Mutex<Option<Connection>>,
+    readers: Mutex<ReadPool>,
+    /// When a command last went through `with_conn`
+    last_activity: Mutex<Instant>,
 }
 
 impl DbState {
     pub fn new() -> Self {
         DbState {
             conn: Mutex::new(None),
+            readers: Mutex::new(ReadPool::default()),
+            last_activity: Mutex::new(Instant::now()),
         }
     }
 
+    /// Lock the writer. A command that panicked while holding it poisons the
+    /// mutex; rather than failing every later command until restart, take the
+    /// connection back, roll back anything the panic left open, and carry on.
+    fn lock_writer(&self) -> MutexGuard<'_, Option<Connection>> {
+        self.conn.lock().unwrap_or_else(|poisoned| {
+            tracing::warn!("Database lock was poisoned by a panicking command; recovering");
+            self.conn.clear_poison();
+            let guard = poisoned.into_inner();
+            if let Some(conn) = guard.as_ref().filter(|conn| !conn.is_autocommit()) {
+                if let Err(e) = conn.execute_batch("ROLLBACK") {
+                    tracing::warn!("Failed to roll back after recovering the database lock: {}", e);
+                }
+            }
+            guard
+        })
+    }
+
+    fn touch(&self) {
+        if let Ok(mut last) = self.last_activity.lock() {
+            *last = Instant::now();
+        }
+    }
+
+    /// Whether the database is unlocked
+    pub fn is_initialized(&self) -> bool {
+        self.lock_writer().is_some()
+    }
+
     /// Execute a closure with a reference to the database connection.
-    /// Returns an error if the database is not initialized or the mutex is poisoned.
+    /// Returns an error if the database is not initialized.
     pub fn with_conn<F, T>(&self, f: F) -> Result<T, AppError>
     where
         F: FnOnce(&Connection) -> Result<T, AppError>,
     {
-        let guard = self
-            .conn
-            .lock()
-            .map_err(|e| AppError::Database(format!("Failed to acquire database lock: {}", e)))?;
+        let guard = self.lock_writer();
 
+        self.touch();
         match guard.as_ref() {
             Some(conn) => f(conn),
             None => Err(AppError::Database(
@@ -34,25 +81,134 @@ impl DbState {
         }
     }
 
-    /// Set the database connection.
-    pub fn set_connection(&self, connection: Connection) -> Result<(), AppError> {
-        let mut guard = self
-            .conn
-            .lock()
-            .map_err(|e| AppError::Database(format!("Failed to acquire database lock: {}", e)))?;
+    /// Execute a read-only closure on a pooled reader connection, falling
+    /// back to the writer when every reader is busy (or none were opened).
+    /// Readers are `query_only`, so the closure must not write.
+    pub fn with_read_conn<F, T>(&self, f: F) -> Result<T, AppError>
+    where
+        F: FnOnce(&Connection) -> Result<T, AppError>,
+    {
+        let checked_out = {
+            let mut pool = self
+                .readers
+                .lock()
+                .map_err(|e| AppError::Database(format!("Failed to acquire reader pool lock: {}", e)))?;
+            let generation = pool.generation;
+            pool.idle.pop().map(|conn| (generation, conn))
+        };
+        let Some((generation, reader)) = checked_out else {
+            return self.with_conn(f);
+        };
+
+        self.touch();
+        let result = f(&reader);
+
+        if let Ok(mut pool) = self.readers.lock() {
+            if pool.generation == generation {
+                pool.idle.push(reader);
+            }
+        }
+        result
+    }
+
+    /// Set the database connection and the read-only pool opened with it.
+    pub fn set_connection(&self, connection: Connection, readers: Vec<Connection>) -> Result<(), AppError> {
+        let mut guard = self.lock_writer();
 
         *guard = Some(connection);
+        self.replace_readers(readers)?;
+        self.touch();
         Ok(())
     }
 
+    /// Swap out the read-only pool, e.g. after a rekey left the old readers
+    /// holding the previous key. Readers currently checked out are dropped
+    /// when they come back.
+    pub fn replace_readers(&self, readers: Vec<Connection>) -> Result<(), AppError> {
+        let mut pool = self
+            .readers
+            .lock()
+            .map_err(|e| AppError::Database(format!("Failed to acquire reader pool lock: {}", e)))?;
+        pool.generation += 1;
+        pool.idle = readers;
+        Ok(())
+    }
+
+    /// Drop the connection if nothing has used it for longer than the
+    /// `session_timeout_minutes` setting (0 disables the timeout). Returns
+    /// true when the session was locked.
+    ///
+    /// Reads the setting directly rather than through `with_conn`, so the
+    /// idle check itself doesn't count as activity.
+    pub fn lock_if_idle(&self) -> Result<bool, AppError> {
+        let mut guard = self.lock_writer();
+
+        let Some(conn) = guard.as_ref() else {
+            return Ok(false);
+        };
+        let minutes = conn
+            .query_row(
+                "SELECT value FROM app_settings WHERE key = 'session_timeout_minutes'",
+                [],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?
+            .flatten()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_SESSION_TIMEOUT_MINUTES);
+        if minutes == 0 {
+            return Ok(false);
+        }
+
+        let idle = self
+            .last_activity
+            .lock()
+            .map(|last| last.elapsed())
+            .unwrap_or_default();
+        if idle < Duration::from_secs(minutes * 60) {
+            return Ok(false);
+        }
+
+        *guard = None;
+        self.replace_readers(Vec::new())?;
+        tracing::info!("Session locked after {} idle minutes", minutes);
+        Ok(true)
+    }
+
     /// Clear the database connection (used for logout).
     pub fn clear_connection(&self) -> Result<(), AppError> {
-        let mut guard = self
-            .conn
-            .lock()
-            .map_err(|e| AppError::Database(format!("Failed to acquire database lock: {}", e)))?;
+        let mut guard = self.lock_writer();
 
         *guard = None;
-        Ok(())
+        self.replace_readers(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn with_conn_recovers_after_a_panicking_closure() {
+        let state = DbState::new();
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (x INTEGER);").unwrap();
+        state.set_connection(conn, Vec::new()).unwrap();
+
+        let panicked = catch_unwind(AssertUnwindSafe(|| {
+            let _ = state.with_conn(|conn| -> Result<(), AppError> {
+                conn.execute_batch("BEGIN; INSERT INTO t VALUES (1);")?;
+                panic!("boom");
+            });
+        }));
+        assert!(panicked.is_err());
+        assert!(state.conn.is_poisoned());
+
+        let count: i64 = state
+            .with_conn(|conn| Ok(conn.query_row("SELECT COUNT(*) FROM t", [], |row| row.get(0))?))
+            .unwrap();
+        assert_eq!(count, 0, "the half-finished transaction should be rolled back");
+        assert!(!state.conn.is_poisoned());
     }
 }
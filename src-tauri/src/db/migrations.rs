@@ -5,71 +5,141 @@ use crate::error::AppError;
 struct Migration {
     version: i32,
     sql: &'static str,
+    /// Undoes `sql`, for `rollback_to`. None where the change can't be
+    /// reversed cleanly (table rebuilds, the initial schema).
+    down_sql: Option<&'static str>,
 }
 
 const MIGRATIONS: &[Migration] = &[
     Migration {
         version: 1,
         sql: include_str!("migrations/v001_initial.sql"),
+        down_sql: None,
     },
     Migration {
         version: 2,
         sql: include_str!("migrations/v002_conversations.sql"),
+        down_sql: None,
     },
     Migration {
         version: 3,
         sql: include_str!("migrations/v003_carrier_sync.sql"),
+        down_sql: None,
     },
     Migration {
         version: 4,
         sql: include_str!("migrations/v004_caresource_enrollments.sql"),
+        down_sql: None,
     },
     Migration {
         version: 5,
         sql: include_str!("migrations/v005_expected_active.sql"),
+        down_sql: Some(include_str!("migrations/v005_expected_active.down.sql")),
     },
     Migration {
         version: 6,
         sql: include_str!("migrations/v006_member_details.sql"),
+        down_sql: Some(include_str!("migrations/v006_member_details.down.sql")),
     },
     Migration {
         version: 7,
         sql: include_str!("migrations/v007_commissions.sql"),
+        down_sql: None,
     },
     Migration {
         version: 8,
         sql: include_str!("migrations/v008_deposits_allow_multiple.sql"),
+        down_sql: None,
     },
     Migration {
         version: 9,
         sql: include_str!("migrations/v009_raw_data.sql"),
+        down_sql: Some(include_str!("migrations/v009_raw_data.down.sql")),
+    },
+    Migration {
+        version: 10,
+        sql: include_str!("migrations/v010_follow_up_recurrence.sql"),
+        down_sql: Some(include_str!("migrations/v010_follow_up_recurrence.down.sql")),
+    },
+    Migration {
+        version: 11,
+        sql: include_str!("migrations/v011_enrollment_commissions.sql"),
+        down_sql: Some(include_str!("migrations/v011_enrollment_commissions.down.sql")),
+    },
+    Migration {
+        version: 12,
+        sql: include_str!("migrations/v012_sync_snapshots.sql"),
+        down_sql: Some(include_str!("migrations/v012_sync_snapshots.down.sql")),
+    },
+    Migration {
+        version: 13,
+        sql: include_str!("migrations/v013_audit_log.sql"),
+        down_sql: Some(include_str!("migrations/v013_audit_log.down.sql")),
+    },
+    Migration {
+        version: 14,
+        sql: include_str!("migrations/v014_sync_log_errors.sql"),
+        down_sql: Some(include_str!("migrations/v014_sync_log_errors.down.sql")),
+    },
+    Migration {
+        version: 15,
+        sql: include_str!("migrations/v015_sync_review_items.sql"),
+        down_sql: Some(include_str!("migrations/v015_sync_review_items.down.sql")),
+    },
+    Migration {
+        version: 16,
+        sql: include_str!("migrations/v016_import_mappings.sql"),
+        down_sql: Some(include_str!("migrations/v016_import_mappings.down.sql")),
+    },
+    Migration {
+        version: 17,
+        sql: include_str!("migrations/v017_import_batches.sql"),
+        down_sql: Some(include_str!("migrations/v017_import_batches.down.sql")),
+    },
+    Migration {
+        version: 18,
+        sql: include_str!("migrations/v018_aor_status.sql"),
+        down_sql: Some(include_str!("migrations/v018_aor_status.down.sql")),
     },
 ];
 
+/// The schema version a fully migrated database is at
+pub fn latest_version() -> i32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
 /// Run all pending migrations against the database.
 /// Uses PRAGMA user_version to track which migrations have been applied.
 pub fn run_migrations(conn: &Connection) -> Result<(), AppError> {
+    apply_migrations(conn, MIGRATIONS)
+}
+
+/// Each migration and its `user_version` bump share one transaction, so a
+/// migration that fails halfway leaves neither its changes nor a new version.
+fn apply_migrations(conn: &Connection, migrations: &[Migration]) -> Result<(), AppError> {
     let current_version: i32 = conn
         .pragma_query_value(None, "user_version", |row| row.get(0))
         .map_err(|e| AppError::Database(format!("Failed to read user_version: {}", e)))?;
 
     tracing::info!("Current database version: {}", current_version);
 
-    for migration in MIGRATIONS {
+    for migration in migrations {
         if migration.version > current_version {
             tracing::info!("Applying migration V{}...", migration.version);
 
-            conn.execute_batch(migration.sql).map_err(|e| {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute_batch(migration.sql).map_err(|e| {
                 AppError::Database(format!(
                     "Failed to apply migration V{}: {}",
                     migration.version, e
                 ))
             })?;
 
-            conn.pragma_update(None, "user_version", migration.version)
+            tx.pragma_update(None, "user_version", migration.version)
                 .map_err(|e| {
                     AppError::Database(format!("Failed to update user_version: {}", e))
                 })?;
+            tx.commit()?;
 
             tracing::info!("Migration V{} applied successfully", migration.version);
         }
@@ -77,3 +147,87 @@ pub fn run_migrations(conn: &Connection) -> Result<(), AppError> {
 
     Ok(())
 }
+
+/// Step the schema back to `target_version` by applying down scripts newest
+/// first, all in one transaction. Refused if any migration in the range has
+/// no down script. Maintenance only: the dropped columns and tables lose
+/// their data, and the next login migrates forward again unless an older
+/// build is installed.
+pub fn rollback_to(conn: &Connection, target_version: i32) -> Result<(), AppError> {
+    let current_version: i32 = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| AppError::Database(format!("Failed to read user_version: {}", e)))?;
+    if target_version < 1 || target_version >= current_version {
+        return Err(AppError::Validation(format!(
+            "Rollback target must be between 1 and {}, got {}",
+            current_version - 1,
+            target_version
+        )));
+    }
+
+    let steps: Vec<&Migration> = MIGRATIONS
+        .iter()
+        .rev()
+        .filter(|m| m.version > target_version && m.version <= current_version)
+        .collect();
+    if let Some(m) = steps.iter().find(|m| m.down_sql.is_none()) {
+        return Err(AppError::Validation(format!(
+            "Migration V{} has no down script and can't be rolled back",
+            m.version
+        )));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    for migration in steps {
+        tracing::warn!("Rolling back migration V{}...", migration.version);
+        tx.execute_batch(migration.down_sql.unwrap_or_default()).map_err(|e| {
+            AppError::Database(format!(
+                "Failed to roll back migration V{}: {}",
+                migration.version, e
+            ))
+        })?;
+        tx.pragma_update(None, "user_version", migration.version - 1)
+            .map_err(|e| AppError::Database(format!("Failed to update user_version: {}", e)))?;
+    }
+    tx.commit()?;
+
+    tracing::warn!("Database rolled back to V{}", target_version);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failing_migration_is_rolled_back() {
+        let conn = Connection::open_in_memory().unwrap();
+        let migrations = [
+            Migration {
+                version: 1,
+                sql: "CREATE TABLE a (x INTEGER);",
+                down_sql: None,
+            },
+            Migration {
+                version: 2,
+                sql: "CREATE TABLE b (x INTEGER); INSERT INTO missing VALUES (1);",
+                down_sql: None,
+            },
+        ];
+
+        assert!(apply_migrations(&conn, &migrations).is_err());
+
+        let version: i32 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, 1);
+        let has_b: bool = conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE name = 'b')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!has_b, "the failed migration's table should not survive");
+    }
+}
@@ -0,0 +1 @@
+ALTER TABLE carriers DROP COLUMN expected_active;
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_client_providers_client;
+DROP TABLE IF EXISTS client_providers;
+ALTER TABLE clients DROP COLUMN member_record_locator;
@@ -0,0 +1 @@
+ALTER TABLE commission_entries DROP COLUMN raw_data;
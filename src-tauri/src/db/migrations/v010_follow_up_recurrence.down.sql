@@ -0,0 +1,2 @@
+ALTER TABLE conversation_entries DROP COLUMN follow_up_until;
+ALTER TABLE conversation_entries DROP COLUMN follow_up_recurrence;
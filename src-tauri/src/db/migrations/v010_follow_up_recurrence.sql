@@ -0,0 +1,6 @@
+-- Recurring follow-ups: the entry's follow_up_date is the first occurrence,
+-- repeating on follow_up_recurrence until follow_up_until (inclusive).
+-- NULL recurrence behaves like NONE.
+ALTER TABLE conversation_entries ADD COLUMN follow_up_recurrence TEXT
+    CHECK (follow_up_recurrence IN ('NONE', 'WEEKLY', 'MONTHLY', 'QUARTERLY'));
+ALTER TABLE conversation_entries ADD COLUMN follow_up_until TEXT;
@@ -0,0 +1,3 @@
+ALTER TABLE enrollments DROP COLUMN commission_paid_date;
+ALTER TABLE enrollments DROP COLUMN commission_status;
+ALTER TABLE enrollments DROP COLUMN commission_amount;
@@ -0,0 +1,6 @@
+-- Expected / received commission per enrollment. CHARGEBACK marks a paid
+-- commission the carrier clawed back after a disenrollment.
+ALTER TABLE enrollments ADD COLUMN commission_amount REAL;
+ALTER TABLE enrollments ADD COLUMN commission_status TEXT
+    CHECK (commission_status IN ('PENDING', 'PAID', 'CHARGEBACK'));
+ALTER TABLE enrollments ADD COLUMN commission_paid_date TEXT;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_sync_snapshots_carrier;
+DROP TABLE IF EXISTS carrier_sync_snapshots;
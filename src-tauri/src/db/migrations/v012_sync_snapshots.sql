@@ -0,0 +1,11 @@
+-- Raw portal member list fetched for each sync, kept so a disputed
+-- disenrollment can be checked against what the portal actually returned.
+-- Only the most recent snapshots per carrier are retained.
+CREATE TABLE IF NOT EXISTS carrier_sync_snapshots (
+    sync_log_id  TEXT PRIMARY KEY REFERENCES carrier_sync_logs(id) ON DELETE CASCADE,
+    carrier_id   TEXT NOT NULL REFERENCES carriers(id),
+    members_json TEXT NOT NULL,
+    created_at   TEXT DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_snapshots_carrier ON carrier_sync_snapshots(carrier_id, created_at);
@@ -0,0 +1,13 @@
+-- Append-only trail of client, enrollment and conversation mutations.
+-- `changes` is JSON: the new record's fields on CREATE, and
+-- {"field": {"old": ..., "new": ...}} for each changed field on UPDATE.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type TEXT NOT NULL,
+    entity_id   TEXT NOT NULL,
+    action      TEXT NOT NULL,
+    changes     TEXT,
+    created_at  TEXT DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON audit_log(entity_type, entity_id, created_at);
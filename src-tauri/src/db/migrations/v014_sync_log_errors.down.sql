@@ -0,0 +1 @@
+ALTER TABLE carrier_sync_logs DROP COLUMN error_message;
@@ -0,0 +1,2 @@
+-- Failed sync attempts are logged with status FAILED and the reason
+ALTER TABLE carrier_sync_logs ADD COLUMN error_message TEXT;
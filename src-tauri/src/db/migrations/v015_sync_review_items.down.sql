@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_sync_review_items_status;
+DROP TABLE IF EXISTS sync_review_items;
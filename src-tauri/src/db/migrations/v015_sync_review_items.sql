@@ -0,0 +1,20 @@
+-- Sync results that need an agent's decision (portal members with no local
+-- match, name-similarity pairings), kept until resolved so the list survives
+-- closing the sync window. member_key is "first|last|dob", lowercased, and
+-- lets an ignored member stay ignored on later syncs.
+CREATE TABLE IF NOT EXISTS sync_review_items (
+    id            TEXT PRIMARY KEY,
+    sync_log_id   TEXT REFERENCES carrier_sync_logs(id) ON DELETE SET NULL,
+    carrier_id    TEXT NOT NULL REFERENCES carriers(id),
+    kind          TEXT NOT NULL CHECK (kind IN ('NEW_IN_PORTAL', 'POSSIBLE_MATCH')),
+    member_key    TEXT NOT NULL,
+    member_json   TEXT NOT NULL,
+    client_id     TEXT REFERENCES clients(id),
+    enrollment_id TEXT REFERENCES enrollments(id),
+    score         REAL,
+    status        TEXT NOT NULL DEFAULT 'PENDING' CHECK (status IN ('PENDING', 'MATCHED', 'CREATED', 'IGNORED')),
+    created_at    TEXT DEFAULT (datetime('now')),
+    resolved_at   TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_review_items_status ON sync_review_items(carrier_id, status);
@@ -0,0 +1,10 @@
+-- Saved column mappings for the general file import, so a carrier's export
+-- format only has to be mapped once. mapping_json is a {"source header":
+-- "target field"} object.
+CREATE TABLE IF NOT EXISTS import_mappings (
+    id           TEXT PRIMARY KEY,
+    name         TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    mapping_json TEXT NOT NULL,
+    created_at   TEXT DEFAULT (datetime('now')),
+    updated_at   TEXT DEFAULT (datetime('now'))
+);
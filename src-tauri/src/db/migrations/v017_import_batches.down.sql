@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_enrollments_import_batch;
+DROP INDEX IF EXISTS idx_clients_import_batch;
+ALTER TABLE enrollments DROP COLUMN import_batch_id;
+ALTER TABLE clients DROP COLUMN import_batch_id;
@@ -0,0 +1,8 @@
+-- The file import that last created or updated each client, and that created
+-- each enrollment. Holds the import_logs id; an undo reads the matching
+-- IMPORT entries in audit_log for the values it overwrote.
+ALTER TABLE clients ADD COLUMN import_batch_id TEXT;
+ALTER TABLE enrollments ADD COLUMN import_batch_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_clients_import_batch ON clients(import_batch_id);
+CREATE INDEX IF NOT EXISTS idx_enrollments_import_batch ON enrollments(import_batch_id);
@@ -0,0 +1,4 @@
+-- The carrier's agent-of-record status for each enrollment, as last reported
+-- by a portal sync (PortalMember.aor_status). A terminal value on an
+-- enrollment that is still active means the agent is no longer the AOR.
+ALTER TABLE enrollments ADD COLUMN aor_status TEXT;
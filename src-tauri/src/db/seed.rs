@@ -67,6 +67,7 @@ fn seed_enrollment_statuses(conn: &Connection) -> Result<(), AppError> {
         ("DISENROLLED_DECEASED",     "Disenrolled - Deceased",     "Member is deceased",                           1),
         ("DISENROLLED_PLAN_TERM",    "Disenrolled - Plan Terminated", "Plan was terminated by CMS or carrier",     1),
         ("DISENROLLED_OTHER_COV",    "Disenrolled - Other Coverage", "Member obtained other creditable coverage",  1),
+        ("RENEWED",                  "Renewed",                     "Replaced by a renewal for the next plan year", 1),
     ];
 
     for (code, name, desc, is_terminal) in statuses {
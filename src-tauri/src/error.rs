@@ -25,9 +25,23 @@ pub enum AppError {
     CarrierSync(String),
 }
 
+// HTTP is only used by the carrier portal fallbacks (e.g. devoted.rs), so
+// network failures surface as carrier sync errors and `?` works on
+// `send()` / `json()` directly.
 impl From<reqwest::Error> for AppError {
     fn from(err: reqwest::Error) -> Self {
-        AppError::CarrierSync(err.to_string())
+        let kind = if err.is_timeout() {
+            "request timed out"
+        } else if err.is_connect() {
+            "could not connect"
+        } else if err.is_decode() {
+            "unexpected response body"
+        } else if err.is_status() {
+            "portal returned an error status"
+        } else {
+            "request failed"
+        };
+        AppError::CarrierSync(format!("{}: {}", kind, err))
     }
 }
 
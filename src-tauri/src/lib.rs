@@ -2,20 +2,20 @@ pub mod carrier_sync;
 pub mod commands;
 pub mod db;
 pub mod error;
+pub mod logging;
 pub mod models;
 pub mod repositories;
 pub mod services;
 
 use std::path::PathBuf;
-use tauri::Manager;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
 use db::DbState;
 
 pub struct AppDataDir(pub PathBuf);
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tracing_subscriber::fmt::init();
-
     let db_state = DbState::new();
 
     tauri::Builder::default()
@@ -33,44 +33,87 @@ pub fn run() {
             std::fs::create_dir_all(&app_data_dir)
                 .expect("Failed to create app data directory");
 
+            logging::init(&app_data_dir);
             tracing::info!("App data directory: {:?}", app_data_dir);
 
             app.manage(AppDataDir(app_data_dir));
 
+            // Idle auto-lock: drop the decrypted connection once no command
+            // has touched it for `session_timeout_minutes`
+            let handle = app.handle().clone();
+            std::thread::spawn(move || loop {
+                std::thread::sleep(Duration::from_secs(30));
+                match handle.state::<DbState>().lock_if_idle() {
+                    Ok(true) => {
+                        let _ = handle.emit("session-locked", ());
+                    }
+                    Ok(false) => {}
+                    Err(e) => tracing::warn!("Idle session check failed: {}", e),
+                }
+            });
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::check_first_run,
+            commands::get_audit_log,
             commands::create_account,
             commands::login,
+            commands::change_password,
             commands::logout,
             commands::get_clients,
+            commands::count_clients,
             commands::get_client,
+            commands::get_sep_hints,
             commands::create_client,
             commands::update_client,
+            commands::add_client_tag,
+            commands::remove_client_tag,
+            commands::bulk_tag_clients,
             commands::delete_client,
             commands::hard_delete_client,
             commands::merge_clients,
             commands::check_client_duplicates,
             commands::find_duplicate_clients,
             commands::delete_all_clients,
+            commands::recompute_dual_eligibility,
+            commands::purge_inactive_clients,
+            commands::get_aging_in_clients,
             commands::get_enrollments,
+            commands::get_enrollments_page,
+            commands::get_enrollment_history,
             commands::create_enrollment,
             commands::update_enrollment,
+            commands::delete_enrollment,
+            commands::restore_enrollment,
+            commands::detect_renewable_enrollments,
+            commands::renew_enrollment,
             commands::get_conversations,
             commands::get_conversation,
             commands::create_conversation,
             commands::update_conversation,
+            commands::bulk_update_conversation_status,
             commands::get_conversation_entries,
             commands::create_conversation_entry,
             commands::update_conversation_entry,
             commands::get_client_timeline,
             commands::get_pending_follow_ups,
+            commands::get_client_next_action,
             commands::create_system_event,
             commands::get_carriers,
+            commands::get_carriers_with_counts,
+            commands::get_plans,
+            commands::get_plan,
+            commands::find_plan_by_contract_pbp,
+            commands::create_plan,
+            commands::update_plan,
             commands::parse_import_file,
             commands::validate_import,
             commands::preview_import,
+            commands::undo_import,
+            commands::save_import_mapping,
+            commands::list_import_mappings,
+            commands::get_import_mapping,
             commands::execute_import,
             commands::import_call_log,
             commands::import_integrity,
@@ -79,20 +122,37 @@ pub fn run() {
             commands::get_dashboard_stats,
             commands::get_report,
             commands::export_report_pdf,
+            commands::export_report_xlsx,
+            commands::export_clients_vcard,
+            commands::export_clients_csv,
             commands::get_settings,
             commands::update_settings,
             commands::get_agent_profile,
             commands::save_agent_profile,
             commands::backup_database,
+            commands::export_logs,
+            commands::export_anonymized_copy,
+            commands::health_check,
+            commands::rollback_migrations,
             commands::get_database_info,
+            commands::optimize_database,
+            commands::optimize_search_index,
+            commands::check_database_integrity,
             commands::open_carrier_login,
             commands::trigger_carrier_fetch,
+            commands::cancel_carrier_fetch,
+            commands::parse_portal_members,
+            commands::preview_portal_sync,
             commands::process_portal_members,
             commands::get_carrier_login_url,
             commands::get_carrier_sync_info,
             commands::import_portal_members,
             commands::confirm_disenrollments,
             commands::get_sync_logs,
+            commands::get_lost_aor_enrollments,
+            commands::get_sync_snapshot,
+            commands::get_sync_review_items,
+            commands::resolve_sync_review_item,
             commands::update_carrier_expected_active,
             commands::save_portal_credentials,
             commands::get_portal_credentials,
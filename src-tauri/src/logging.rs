@@ -0,0 +1,106 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Log files live in `<app_data_dir>/logs`, one per day
+const LOG_DIR: &str = "logs";
+pub const LOG_FILE_PREFIX: &str = "compass.log.";
+/// Daily files older than this are deleted when the log rotates
+const LOG_KEEP_DAYS: i64 = 14;
+
+pub fn log_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(LOG_DIR)
+}
+
+/// Install the global subscriber: the usual stdout output, plus a plain-text
+/// copy in the daily log file so failures survive a restart. If the log
+/// directory can't be created, only stdout is used.
+///
+/// Commands wrapped in `#[tracing::instrument]` log when they start and, on
+/// close, how long they took; failures are logged by the `err` option.
+pub fn init(app_data_dir: &Path) {
+    let stdout = tracing_subscriber::fmt::layer()
+        .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE);
+
+    let dir = log_dir(app_data_dir);
+    let file = match std::fs::create_dir_all(&dir) {
+        Ok(()) => Some(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE)
+                .with_writer(Mutex::new(DailyLogFile::new(dir))),
+        ),
+        Err(e) => {
+            eprintln!("Failed to create log directory: {}", e);
+            None
+        }
+    };
+
+    let _ = tracing_subscriber::registry().with(stdout).with(file).try_init();
+}
+
+/// Appends to `compass.log.YYYY-MM-DD`, switching files when the date
+/// changes and pruning files past `LOG_KEEP_DAYS` at each switch.
+struct DailyLogFile {
+    dir: PathBuf,
+    date: String,
+    file: Option<File>,
+}
+
+impl DailyLogFile {
+    fn new(dir: PathBuf) -> Self {
+        DailyLogFile {
+            dir,
+            date: String::new(),
+            file: None,
+        }
+    }
+
+    fn current(&mut self) -> io::Result<&mut File> {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        if self.file.is_none() || self.date != today {
+            let path = self.dir.join(format!("{}{}", LOG_FILE_PREFIX, today));
+            self.file = Some(OpenOptions::new().create(true).append(true).open(path)?);
+            self.date = today;
+            self.prune();
+        }
+        Ok(self.file.as_mut().expect("log file was just opened"))
+    }
+
+    fn prune(&self) {
+        let cutoff = (chrono::Local::now() - chrono::Duration::days(LOG_KEEP_DAYS))
+            .format("%Y-%m-%d")
+            .to_string();
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Dates sort lexically, so a plain string compare finds old files
+            if name
+                .strip_prefix(LOG_FILE_PREFIX)
+                .is_some_and(|date| date < cutoff.as_str())
+            {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+impl Write for DailyLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.current()?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
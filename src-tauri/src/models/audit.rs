@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    /// client, enrollment, conversation, or conversation_entry
+    pub entity_type: String,
+    pub entity_id: String,
+    /// CREATE, UPDATE, DELETE, RESTORE, or PURGE
+    pub action: String,
+    /// JSON; see v013_audit_log.sql for the shape
+    pub changes: Option<String>,
+    pub created_at: Option<String>,
+}
@@ -1,17 +1,25 @@
 use serde::{Deserialize, Serialize};
+use crate::models::conversation::text_enum;
 
 /// A member record as returned by a carrier portal.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PortalMember {
     pub first_name: String,
     pub last_name: String,
     pub member_id: Option<String>,
     pub dob: Option<String>,
     pub plan_name: Option<String>,
+    /// CMS contract and PBP, for linking to the plan catalog when the
+    /// portal exposes them
+    pub contract_number: Option<String>,
+    pub pbp_number: Option<String>,
     pub effective_date: Option<String>,
     pub end_date: Option<String>,
     pub status: Option<String>,
     pub policy_status: Option<String>,
+    /// Agent-of-record status, from portals that report it apart from the
+    /// policy status (Devoted's `aor_policy_status`)
+    pub aor_status: Option<String>,
     pub state: Option<String>,
     pub city: Option<String>,
     pub phone: Option<String>,
@@ -30,6 +38,18 @@ pub struct PortalMember {
     pub provider_last_name: Option<String>,
 }
 
+/// What a portal fetch returned, shown before the sync compares it against
+/// local enrollments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortalFetchSummary {
+    pub carrier_name: String,
+    pub member_count: usize,
+    /// Active and pending local enrollments the sync will compare against
+    pub local_count: usize,
+    /// The first few members as parsed, to check the scrape looks right
+    pub sample: Vec<PortalMember>,
+}
+
 /// The result of comparing portal data against local enrollments.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncResult {
@@ -39,7 +59,59 @@ pub struct SyncResult {
     pub matched: usize,
     pub matched_members: Vec<SyncMatch>,
     pub disenrolled: Vec<SyncDisenrollment>,
+    pub terminated_in_portal: Vec<SyncTermination>,
     pub new_in_portal: Vec<PortalMember>,
+    /// Name-similarity pairings for the agent to confirm; neither side is
+    /// counted as matched, disenrolled, or new
+    pub possible_matches: Vec<SyncPossibleMatch>,
+    /// Matched enrollments whose agent-of-record status ended in this sync
+    #[serde(default)]
+    pub aor_lost: Vec<SyncAorLoss>,
+}
+
+/// An unmatched portal member whose name is close to an unmatched local
+/// enrollment's client (see the `carrier_sync_fuzzy_threshold` setting).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPossibleMatch {
+    pub client_name: String,
+    pub client_id: String,
+    pub enrollment_id: String,
+    pub plan_name: Option<String>,
+    pub portal_member: PortalMember,
+    /// Jaro-Winkler similarity of the full names, 0.0–1.0
+    pub score: f64,
+    /// The matching entry in the review queue
+    pub review_item_id: Option<String>,
+}
+
+text_enum!(
+    /// How a pending review item is resolved
+    SyncReviewAction {
+        ConfirmMatch => "confirm_match",
+        CreateNew => "create_new",
+        Ignore => "ignore",
+    }
+);
+
+/// A sync result waiting in the review queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncReviewItem {
+    pub id: String,
+    pub carrier_id: String,
+    pub carrier_name: Option<String>,
+    pub sync_log_id: Option<String>,
+    /// NEW_IN_PORTAL or POSSIBLE_MATCH
+    pub kind: String,
+    pub portal_member: PortalMember,
+    /// For POSSIBLE_MATCH: the local client/enrollment it may be
+    pub client_id: Option<String>,
+    pub client_name: Option<String>,
+    pub enrollment_id: Option<String>,
+    pub score: Option<f64>,
+    /// PENDING, MATCHED, CREATED, or IGNORED
+    pub status: String,
+    pub created_at: Option<String>,
+    pub resolved_at: Option<String>,
 }
 
 /// A portal member that was matched to a local enrollment.
@@ -60,6 +132,30 @@ pub struct SyncDisenrollment {
     pub plan_name: Option<String>,
 }
 
+/// A matched local enrollment the portal shows as termed; updated during sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncTermination {
+    pub client_name: String,
+    pub client_id: String,
+    pub enrollment_id: String,
+    pub plan_name: Option<String>,
+    pub status_code: String,
+    pub termination_date: String,
+}
+
+/// An active enrollment the carrier still shows the member in, but with the
+/// agent's policy (agent-of-record) status ended, so the commission has
+/// likely gone to another agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncAorLoss {
+    pub client_name: String,
+    pub client_id: String,
+    pub enrollment_id: String,
+    pub carrier_name: Option<String>,
+    pub plan_name: Option<String>,
+    pub aor_status: String,
+}
+
 /// Result of importing portal members as new clients + enrollments.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportPortalResult {
@@ -100,5 +196,17 @@ pub struct SyncLogEntry {
     pub matched: i64,
     pub disenrolled: i64,
     pub new_found: i64,
+    /// COMPLETED, or FAILED with `error_message` set
     pub status: String,
+    pub has_snapshot: bool,
+    pub error_message: Option<String>,
+}
+
+/// The raw member list a portal returned for one sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSnapshot {
+    pub sync_log_id: String,
+    pub carrier_id: String,
+    pub created_at: String,
+    pub members: Vec<PortalMember>,
 }
@@ -1,3 +1,4 @@
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,10 +29,88 @@ pub struct Client {
     pub lead_source: Option<String>,
     pub member_record_locator: Option<String>,
     pub is_active: Option<bool>,
+    /// Comma-separated, see `split_tags`
     pub tags: Option<String>,
     pub notes: Option<String>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    /// Derived from `dob`; `None` when it is missing or unparseable
+    #[serde(default, skip_deserializing)]
+    pub age: Option<i32>,
+    #[serde(default, skip_deserializing)]
+    pub turns_65_on: Option<String>,
+    /// See `LAST_CONTACT_SQL`
+    #[serde(default, skip_deserializing)]
+    pub last_contact_at: Option<String>,
+}
+
+/// Parse a stored `YYYY-MM-DD` date of birth
+pub fn parse_dob(dob: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(dob.trim(), "%Y-%m-%d").ok()
+}
+
+/// Age in whole years on `today`
+pub fn age_on(dob: NaiveDate, today: NaiveDate) -> i32 {
+    let had_birthday = (today.month(), today.day()) >= (dob.month(), dob.day());
+    today.year() - dob.year() - if had_birthday { 0 } else { 1 }
+}
+
+/// Tags are stored in one text column as a comma-separated list ("VIP, Spanish")
+pub const TAG_DELIMITER: char = ',';
+
+/// Split a stored tags value into trimmed, non-empty tags
+pub fn split_tags(tags: &str) -> Vec<String> {
+    tags.split(TAG_DELIMITER)
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Store form of a tag list; `None` when there are no tags left
+pub fn join_tags(tags: &[String]) -> Option<String> {
+    if tags.is_empty() {
+        None
+    } else {
+        Some(tags.join(", "))
+    }
+}
+
+/// Case-insensitive whole-tag match of `c.tags` against the upper-cased tag
+/// bound at parameter `idx`
+pub fn tag_match_sql(idx: usize) -> String {
+    format!(
+        "instr(',' || REPLACE(REPLACE(UPPER(c.tags), ', ', ','), ' ,', ',') || ',', ',' || ?{} || ',') > 0",
+        idx
+    )
+}
+
+/// Comparable form of a county name: upper case with any trailing "COUNTY"
+/// dropped, so "Cuyahoga" and "CUYAHOGA COUNTY" match
+pub fn normalize_county(county: &str) -> String {
+    let upper = county.trim().to_uppercase();
+    upper.strip_suffix(" COUNTY").unwrap_or(&upper).trim().to_string()
+}
+
+/// SQL twin of `normalize_county` for the stored `c.county` column
+pub const COUNTY_MATCH_SQL: &str = "TRIM(REPLACE(UPPER(TRIM(c.county)) || '#', ' COUNTY#', ''), '# ')";
+
+/// When the client was last contacted: the latest `occurred_at` among their
+/// active conversation entries, leaving out SYSTEM entries the app logs itself
+pub const LAST_CONTACT_SQL: &str = "(SELECT MAX(ce.occurred_at) FROM conversation_entries ce
+     WHERE ce.client_id = c.id AND ce.is_active = 1 AND ce.entry_type != 'SYSTEM')";
+
+/// Clients with no contact on or after the YYYY-MM-DD date bound at parameter
+/// `idx`, including those never contacted
+pub fn no_contact_since_sql(idx: usize) -> String {
+    format!("COALESCE({}, '') < ?{}", LAST_CONTACT_SQL, idx)
+}
+
+/// 65th birthday. A Feb 29 birthday falls on Mar 1 in non-leap years,
+/// matching SQLite's `date(dob, '+65 years')`.
+pub fn turns_65_on(dob: NaiveDate) -> Option<NaiveDate> {
+    dob.with_year(dob.year() + 65)
+        .or_else(|| NaiveDate::from_ymd_opt(dob.year() + 65, 3, 1))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,6 +183,25 @@ pub struct ClientListItem {
     pub carrier_name: Option<String>,
     pub plan_name: Option<String>,
     pub is_active: Option<bool>,
+    pub last_contact_at: Option<String>,
+    /// The searched text in context, matches wrapped in `<mark>`; only set
+    /// with `ClientFilters::include_snippets`
+    pub match_snippet: Option<String>,
+}
+
+/// A client whose 65th birthday (and so their Initial Enrollment Period)
+/// is coming up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgingInClient {
+    pub id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub dob: String,
+    pub turns_65_on: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    /// False for prospects with no enrollment on file yet
+    pub has_enrollment: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -114,8 +212,26 @@ pub struct ClientFilters {
     pub status_code: Option<String>,
     pub state: Option<String>,
     pub zip: Option<String>,
+    /// Matched case-insensitively, ignoring a trailing "County"
+    pub county: Option<String>,
+    /// A single tag; matches clients carrying it, ignoring case
+    pub tag: Option<String>,
     pub is_dual_eligible: Option<bool>,
     pub is_active: Option<bool>,
+    /// `NONE` keeps only clients without an active or pending enrollment
+    pub enrollment_status: Option<String>,
+    /// Inclusive YYYY-MM-DD bounds on the client's created date
+    pub created_after: Option<String>,
+    pub created_before: Option<String>,
+    /// YYYY-MM-DD; keeps clients not contacted on or after this date
+    pub no_contact_since: Option<String>,
+    /// Fill `ClientListItem::match_snippet` for a `search`. Off by default to
+    /// keep the plain list query lean.
+    pub include_snippets: Option<bool>,
+    /// One of `last_name`, `first_name`, `created_at`, `city`, `state`, `dob`
+    pub sort_by: Option<String>,
+    /// `asc` or `desc`
+    pub sort_dir: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
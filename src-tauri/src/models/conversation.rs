@@ -1,5 +1,104 @@
 use serde::{Deserialize, Serialize};
 
+// ── Typed status / kind columns ──────────────────────────────────────────────
+
+/// Define an enum stored as a fixed TEXT value (matching the column's CHECK
+/// constraint). Serde and rusqlite both use the same string, so unknown values
+/// are rejected when a command's input is deserialized.
+macro_rules! text_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+        pub enum $name {
+            $(#[serde(rename = $value)] $variant),+
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $value),+
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl rusqlite::types::ToSql for $name {
+            fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+                Ok(rusqlite::types::ToSqlOutput::from(self.as_str()))
+            }
+        }
+
+        impl rusqlite::types::FromSql for $name {
+            fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+                match value.as_str()? {
+                    $($value => Ok(Self::$variant),)+
+                    other => Err(rusqlite::types::FromSqlError::Other(
+                        format!("invalid {} value: {}", stringify!($name), other).into(),
+                    )),
+                }
+            }
+        }
+    };
+}
+pub(crate) use text_enum;
+
+text_enum!(
+    /// `conversations.status`
+    ConversationStatus {
+        Open => "OPEN",
+        Closed => "CLOSED",
+        Archived => "ARCHIVED",
+    }
+);
+
+text_enum!(
+    /// `conversation_entries.entry_type`
+    EntryType {
+        Call => "CALL",
+        Email => "EMAIL",
+        Meeting => "MEETING",
+        Sms => "SMS",
+        Note => "NOTE",
+        System => "SYSTEM",
+    }
+);
+
+text_enum!(
+    /// `conversation_entries.call_direction`, which also holds the direction
+    /// of SMS entries
+    CallDirection {
+        Inbound => "INBOUND",
+        Outbound => "OUTBOUND",
+    }
+);
+
+text_enum!(
+    /// `conversation_entries.call_outcome`
+    CallOutcome {
+        Answered => "ANSWERED",
+        NoAnswer => "NO_ANSWER",
+        Voicemail => "VOICEMAIL",
+        Busy => "BUSY",
+        CallbackRequested => "CALLBACK_REQUESTED",
+        WrongNumber => "WRONG_NUMBER",
+    }
+);
+
+text_enum!(
+    /// `conversation_entries.follow_up_recurrence`
+    FollowUpRecurrence {
+        None => "NONE",
+        Weekly => "WEEKLY",
+        Monthly => "MONTHLY",
+        Quarterly => "QUARTERLY",
+    }
+);
+
 // ── Conversation (thread container) ──────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,7 +106,7 @@ pub struct Conversation {
     pub id: String,
     pub client_id: String,
     pub title: String,
-    pub status: String,
+    pub status: ConversationStatus,
     pub is_pinned: bool,
     pub is_active: bool,
     pub created_at: Option<String>,
@@ -23,7 +122,7 @@ pub struct CreateConversationInput {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateConversationInput {
     pub title: Option<String>,
-    pub status: Option<String>,
+    pub status: Option<ConversationStatus>,
     pub is_pinned: Option<bool>,
     pub is_active: Option<bool>,
 }
@@ -33,7 +132,7 @@ pub struct ConversationListItem {
     pub id: String,
     pub client_id: String,
     pub title: String,
-    pub status: String,
+    pub status: ConversationStatus,
     pub is_pinned: bool,
     pub entry_count: i64,
     pub last_entry_at: Option<String>,
@@ -47,15 +146,17 @@ pub struct ConversationEntry {
     pub id: String,
     pub conversation_id: String,
     pub client_id: String,
-    pub entry_type: String,
+    pub entry_type: EntryType,
     pub subject: Option<String>,
     pub body: Option<String>,
     pub occurred_at: Option<String>,
     pub follow_up_date: Option<String>,
     pub follow_up_note: Option<String>,
-    pub call_direction: Option<String>,
+    pub follow_up_recurrence: Option<FollowUpRecurrence>,
+    pub follow_up_until: Option<String>,
+    pub call_direction: Option<CallDirection>,
     pub call_duration: Option<i64>,
-    pub call_outcome: Option<String>,
+    pub call_outcome: Option<CallOutcome>,
     pub call_phone_number: Option<String>,
     pub meeting_location: Option<String>,
     pub meeting_type: Option<String>,
@@ -72,15 +173,17 @@ pub struct ConversationEntry {
 pub struct CreateConversationEntryInput {
     pub conversation_id: String,
     pub client_id: String,
-    pub entry_type: String,
+    pub entry_type: EntryType,
     pub subject: Option<String>,
     pub body: Option<String>,
     pub occurred_at: Option<String>,
     pub follow_up_date: Option<String>,
     pub follow_up_note: Option<String>,
-    pub call_direction: Option<String>,
+    pub follow_up_recurrence: Option<FollowUpRecurrence>,
+    pub follow_up_until: Option<String>,
+    pub call_direction: Option<CallDirection>,
     pub call_duration: Option<i64>,
-    pub call_outcome: Option<String>,
+    pub call_outcome: Option<CallOutcome>,
     pub call_phone_number: Option<String>,
     pub meeting_location: Option<String>,
     pub meeting_type: Option<String>,
@@ -95,9 +198,11 @@ pub struct UpdateConversationEntryInput {
     pub occurred_at: Option<String>,
     pub follow_up_date: Option<String>,
     pub follow_up_note: Option<String>,
-    pub call_direction: Option<String>,
+    pub follow_up_recurrence: Option<FollowUpRecurrence>,
+    pub follow_up_until: Option<String>,
+    pub call_direction: Option<CallDirection>,
     pub call_duration: Option<i64>,
-    pub call_outcome: Option<String>,
+    pub call_outcome: Option<CallOutcome>,
     pub call_phone_number: Option<String>,
     pub meeting_location: Option<String>,
     pub meeting_type: Option<String>,
@@ -114,15 +219,17 @@ pub struct TimelineEntry {
     pub conversation_id: String,
     pub conversation_title: String,
     pub client_id: String,
-    pub entry_type: String,
+    pub entry_type: EntryType,
     pub subject: Option<String>,
     pub body: Option<String>,
     pub occurred_at: Option<String>,
     pub follow_up_date: Option<String>,
     pub follow_up_note: Option<String>,
-    pub call_direction: Option<String>,
+    pub follow_up_recurrence: Option<FollowUpRecurrence>,
+    pub follow_up_until: Option<String>,
+    pub call_direction: Option<CallDirection>,
     pub call_duration: Option<i64>,
-    pub call_outcome: Option<String>,
+    pub call_outcome: Option<CallOutcome>,
     pub call_phone_number: Option<String>,
     pub meeting_location: Option<String>,
     pub meeting_type: Option<String>,
@@ -132,3 +239,18 @@ pub struct TimelineEntry {
     pub system_event_data: Option<String>,
     pub created_at: Option<String>,
 }
+
+// ── Next action (client summary line) ────────────────────────────────────────
+
+/// The client's soonest upcoming follow-up plus when they were last in touch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientNextAction {
+    pub entry_id: String,
+    pub conversation_id: String,
+    pub entry_type: EntryType,
+    pub subject: Option<String>,
+    pub follow_up_date: String,
+    pub follow_up_note: Option<String>,
+    /// `occurred_at` of the client's most recent non-system entry
+    pub last_entry_at: Option<String>,
+}
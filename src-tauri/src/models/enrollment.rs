@@ -19,6 +19,13 @@ pub struct Enrollment {
     pub premium: Option<f64>,
     pub confirmation_number: Option<String>,
     pub enrollment_source: Option<String>,
+    pub commission_amount: Option<f64>,
+    /// PENDING, PAID, or CHARGEBACK
+    pub commission_status: Option<String>,
+    pub commission_paid_date: Option<String>,
+    /// Agent-of-record status from the last carrier sync
+    #[serde(default, skip_deserializing)]
+    pub aor_status: Option<String>,
     pub is_active: Option<bool>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
@@ -42,9 +49,12 @@ pub struct CreateEnrollmentInput {
     pub premium: Option<f64>,
     pub confirmation_number: Option<String>,
     pub enrollment_source: Option<String>,
+    pub commission_amount: Option<f64>,
+    pub commission_status: Option<String>,
+    pub commission_paid_date: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpdateEnrollmentInput {
     pub plan_id: Option<String>,
     pub carrier_id: Option<String>,
@@ -61,6 +71,9 @@ pub struct UpdateEnrollmentInput {
     pub premium: Option<f64>,
     pub confirmation_number: Option<String>,
     pub enrollment_source: Option<String>,
+    pub commission_amount: Option<f64>,
+    pub commission_status: Option<String>,
+    pub commission_paid_date: Option<String>,
     pub is_active: Option<bool>,
 }
 
@@ -75,3 +88,20 @@ pub struct EnrollmentListItem {
     pub effective_date: Option<String>,
     pub termination_date: Option<String>,
 }
+
+/// One row of a client's enrollment history, including terminated, renewed
+/// and deleted records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrollmentHistoryItem {
+    pub id: String,
+    pub plan_name: Option<String>,
+    pub carrier_name: Option<String>,
+    pub plan_type: Option<String>,
+    pub status_code: Option<String>,
+    pub status: Option<String>,
+    pub effective_date: Option<String>,
+    pub termination_date: Option<String>,
+    pub enrollment_source: Option<String>,
+    /// False for soft-deleted enrollments
+    pub is_active: bool,
+}
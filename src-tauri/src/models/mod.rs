@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod carrier;
 pub mod carrier_sync;
 pub mod client;
@@ -7,7 +8,9 @@ pub mod enrollment;
 pub mod plan;
 pub mod provider;
 pub mod report;
+pub mod sep;
 
+pub use audit::*;
 pub use carrier::*;
 pub use carrier_sync::*;
 pub use client::*;
@@ -17,3 +20,4 @@ pub use enrollment::*;
 pub use plan::*;
 pub use provider::*;
 pub use report::*;
+pub use sep::*;
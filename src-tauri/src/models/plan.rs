@@ -29,3 +29,32 @@ pub struct PlanListItem {
     pub plan_year: Option<i32>,
     pub premium: Option<f64>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePlanInput {
+    pub carrier_id: String,
+    pub plan_type_code: String,
+    pub plan_name: String,
+    pub contract_number: Option<String>,
+    pub pbp_number: Option<String>,
+    pub segment_id: Option<String>,
+    pub plan_year: Option<i32>,
+    pub state: Option<String>,
+    pub county_fips: Option<String>,
+    pub premium: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdatePlanInput {
+    pub carrier_id: Option<String>,
+    pub plan_type_code: Option<String>,
+    pub plan_name: Option<String>,
+    pub contract_number: Option<String>,
+    pub pbp_number: Option<String>,
+    pub segment_id: Option<String>,
+    pub plan_year: Option<i32>,
+    pub state: Option<String>,
+    pub county_fips: Option<String>,
+    pub premium: Option<f64>,
+    pub is_active: Option<bool>,
+}
@@ -10,6 +10,10 @@ pub struct ReportDefinition {
     pub sort_by: Option<String>,
     pub sort_dir: Option<String>,
     pub group_by: Option<String>,
+    /// PDF page orientation: "portrait", "landscape", or unset to pick
+    /// landscape for wide reports
+    #[serde(default)]
+    pub orientation: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,10 +22,44 @@ pub struct DashboardStats {
     pub new_this_month: i64,
     pub lost_this_month: i64,
     pub pending_enrollments: i64,
+    pub overdue_follow_ups: i64,
+    pub follow_ups_next_7_days: i64,
+    /// Commission expected from enrollments active now (chargebacks excluded)
+    pub projected_commission_this_month: f64,
+    /// Commission marked paid with a paid date this month
+    pub paid_commission_this_month: f64,
+    /// Call entries per outcome over the last `call_stats_window_days` days
+    pub call_stats: Vec<(String, i64)>,
+    pub total_call_minutes: i64,
+    /// `call_stats_window_days` setting, default 30
+    pub call_stats_window_days: i64,
     pub by_plan_type: Vec<(String, i64)>,
     pub by_carrier: Vec<(String, i64, i64)>,
     pub by_state: Vec<(String, i64)>,
     pub monthly_trend: Vec<MonthlyTrend>,
+    /// Enrollment periods open today, soonest-closing first. Several can
+    /// overlap (MA OEP and GEP both run Jan 1 – Mar 31).
+    pub active_enrollment_periods: Vec<ActiveEnrollmentPeriod>,
+}
+
+/// An `enrollment_periods` row with a fixed yearly window
+#[derive(Debug, Clone)]
+pub struct EnrollmentPeriodWindow {
+    pub code: String,
+    pub name: String,
+    pub start_month: u32,
+    pub start_day: u32,
+    pub end_month: u32,
+    pub end_day: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveEnrollmentPeriod {
+    pub code: String,
+    pub name: String,
+    pub end_date: String,
+    /// Days left after today; 0 on the last day
+    pub days_remaining: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,10 +77,18 @@ impl Default for DashboardStats {
             new_this_month: 0,
             lost_this_month: 0,
             pending_enrollments: 0,
+            overdue_follow_ups: 0,
+            follow_ups_next_7_days: 0,
+            projected_commission_this_month: 0.0,
+            paid_commission_this_month: 0.0,
+            call_stats: Vec::new(),
+            total_call_minutes: 0,
+            call_stats_window_days: 30,
             by_plan_type: Vec::new(),
             by_carrier: Vec::new(), // (name, actual, expected)
             by_state: Vec::new(),
             monthly_trend: Vec::new(),
+            active_enrollment_periods: Vec::new(),
         }
     }
 }
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// One Special Enrollment Period the client may qualify for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SepHint {
+    /// DUAL_LIS, MOVED, LOST_COVERAGE, or NEW_TO_MEDICARE
+    pub code: String,
+    pub title: String,
+    pub explanation: String,
+    /// The stored date the hint is based on (move, termination, Part B start)
+    pub evidence_date: Option<String>,
+    /// Last day of the election window, when it is time-limited
+    pub window_ends: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SepHints {
+    pub client_id: String,
+    pub hints: Vec<SepHint>,
+    pub disclaimer: String,
+}
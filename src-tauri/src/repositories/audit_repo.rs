@@ -0,0 +1,43 @@
+use rusqlite::{params, Connection};
+use crate::error::AppError;
+use crate::models::AuditEntry;
+
+/// Most recent entries returned by `get_audit_log`
+const AUDIT_LOG_LIMIT: i64 = 500;
+
+pub fn insert_audit_entry(
+    conn: &Connection,
+    entity_type: &str,
+    entity_id: &str,
+    action: &str,
+    changes: Option<&str>,
+) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO audit_log (entity_type, entity_id, action, changes) VALUES (?1, ?2, ?3, ?4)",
+        params![entity_type, entity_id, action, changes],
+    )?;
+    Ok(())
+}
+
+/// Newest first. Without `entity_id`, every entry for the entity type.
+pub fn get_audit_log(conn: &Connection, entity_type: &str, entity_id: Option<&str>) -> Result<Vec<AuditEntry>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, entity_type, entity_id, action, changes, created_at
+         FROM audit_log
+         WHERE entity_type = ?1 AND (?2 IS NULL OR entity_id = ?2)
+         ORDER BY id DESC
+         LIMIT ?3",
+    )?;
+    let items = stmt.query_map(params![entity_type, entity_id, AUDIT_LOG_LIMIT], |row| {
+        Ok(AuditEntry {
+            id: row.get(0)?,
+            entity_type: row.get(1)?,
+            entity_id: row.get(2)?,
+            action: row.get(3)?,
+            changes: row.get(4)?,
+            created_at: row.get(5)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+}
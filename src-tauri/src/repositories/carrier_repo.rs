@@ -1,6 +1,6 @@
 use rusqlite::Connection;
 use crate::error::AppError;
-use crate::models::Carrier;
+use crate::models::{Carrier, CarrierWithCounts};
 
 pub fn get_carriers(conn: &Connection) -> Result<Vec<Carrier>, AppError> {
     let sql = "SELECT id, name, short_name, is_active, expected_active FROM carriers WHERE is_active = 1 ORDER BY name";
@@ -18,6 +18,30 @@ pub fn get_carriers(conn: &Connection) -> Result<Vec<Carrier>, AppError> {
     Ok(items)
 }
 
+/// Active carriers with how many active enrollments each has, in one query
+pub fn get_carriers_with_counts(conn: &Connection) -> Result<Vec<CarrierWithCounts>, AppError> {
+    let sql = "SELECT c.id, c.name, c.short_name, c.is_active, c.expected_active, COUNT(e.id)
+               FROM carriers c
+               LEFT JOIN enrollments e ON e.carrier_id = c.id
+                    AND e.status_code = 'ACTIVE' AND e.is_active = 1
+               WHERE c.is_active = 1
+               GROUP BY c.id
+               ORDER BY c.name";
+    let mut stmt = conn.prepare(sql)?;
+    let items = stmt.query_map([], |row| {
+        Ok(CarrierWithCounts {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            short_name: row.get(2)?,
+            is_active: row.get(3)?,
+            expected_active: row.get(4)?,
+            enrollment_count: row.get(5)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+}
+
 pub fn update_expected_active(conn: &Connection, carrier_id: &str, count: i32) -> Result<(), AppError> {
     conn.execute(
         "UPDATE carriers SET expected_active = ?1 WHERE id = ?2",
@@ -1,6 +1,6 @@
 use rusqlite::{params, Connection};
 use crate::error::AppError;
-use crate::models::{Client, ClientListItem, ClientFilters, CreateClientInput, UpdateClientInput, PaginatedResult};
+use crate::models::{client, AgingInClient, Client, ClientListItem, ClientFilters, CreateClientInput, UpdateClientInput, PaginatedResult};
 
 /// Get paginated, filtered list of clients
 pub fn get_clients(
@@ -10,6 +10,90 @@ pub fn get_clients(
     per_page: i32,
 ) -> Result<PaginatedResult<ClientListItem>, AppError> {
     let offset = (page - 1) * per_page;
+    let (where_clause, mut param_values) = filter_clause(filters);
+    let order_by = order_by_clause(filters)?;
+
+    let total = count_matching(conn, &where_clause, &param_values)?;
+
+    // The search term is always ?1 when present (see `filter_clause`)
+    let searching = filters.search.as_deref().is_some_and(|s| !s.is_empty());
+    let snippet = if searching && filters.include_snippets == Some(true) {
+        "(SELECT snippet(clients_fts, -1, '<mark>', '</mark>', '…', 8) FROM clients_fts
+          WHERE clients_fts MATCH ?1 AND clients_fts.rowid = c.rowid)"
+    } else {
+        "NULL"
+    };
+
+    // Fetch page — join latest active enrollment + carrier for list display
+    let limit_idx = param_values.len() + 1;
+    let offset_idx = param_values.len() + 2;
+    let select_sql = format!(
+        "SELECT c.id, c.first_name, c.last_name, c.dob, cr.name, e.plan_name, c.is_active, {} AS last_contact_at,
+                {} AS match_snippet
+         FROM clients c
+         LEFT JOIN enrollments e ON e.client_id = c.id
+           AND e.id = (
+             SELECT e2.id FROM enrollments e2
+             WHERE e2.client_id = c.id AND e2.is_active = 1
+             ORDER BY
+               CASE WHEN e2.status_code IN ('ACTIVE', 'PENDING') THEN 0 ELSE 1 END,
+               e2.effective_date DESC
+             LIMIT 1
+           )
+         LEFT JOIN carriers cr ON e.carrier_id = cr.id
+         {}
+         {}
+         LIMIT ?{} OFFSET ?{}",
+        client::LAST_CONTACT_SQL, snippet, where_clause, order_by, limit_idx, offset_idx
+    );
+
+    param_values.push(Box::new(per_page as i64));
+    param_values.push(Box::new(offset as i64));
+    let params_refs: Vec<&dyn rusqlite::types::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
+
+    let mut stmt = conn.prepare(&select_sql)?;
+    let items = stmt.query_map(params_refs.as_slice(), |row| {
+        Ok(ClientListItem {
+            id: row.get(0)?,
+            first_name: row.get(1)?,
+            last_name: row.get(2)?,
+            dob: row.get(3)?,
+            carrier_name: row.get(4)?,
+            plan_name: row.get(5)?,
+            is_active: row.get(6)?,
+            last_contact_at: row.get(7)?,
+            match_snippet: row.get(8)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PaginatedResult {
+        items,
+        total,
+        page,
+        per_page,
+    })
+}
+
+/// Number of clients matching `filters`, without fetching any rows
+pub fn count_clients(conn: &Connection, filters: &ClientFilters) -> Result<i64, AppError> {
+    let (where_clause, param_values) = filter_clause(filters);
+    count_matching(conn, &where_clause, &param_values)
+}
+
+fn count_matching(
+    conn: &Connection,
+    where_clause: &str,
+    param_values: &[Box<dyn rusqlite::types::ToSql>],
+) -> Result<i64, AppError> {
+    let count_sql = format!("SELECT COUNT(*) FROM clients c {}", where_clause);
+    let params_refs: Vec<&dyn rusqlite::types::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
+    Ok(conn.query_row(&count_sql, params_refs.as_slice(), |row| row.get(0))?)
+}
+
+/// WHERE clause and its parameters for the client list filters, shared by the
+/// paged list, the full exports and custom reports
+pub(crate) fn filter_clause(filters: &ClientFilters) -> (String, Vec<Box<dyn rusqlite::types::ToSql>>) {
     let mut conditions = Vec::new();
     let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
 
@@ -36,6 +120,18 @@ pub fn get_clients(
         param_values.push(Box::new(zip.clone()));
     }
 
+    if let Some(ref county) = filters.county {
+        let idx = param_values.len() + 1;
+        conditions.push(format!("{} = ?{}", client::COUNTY_MATCH_SQL, idx));
+        param_values.push(Box::new(client::normalize_county(county)));
+    }
+
+    if let Some(ref tag) = filters.tag {
+        let idx = param_values.len() + 1;
+        conditions.push(client::tag_match_sql(idx));
+        param_values.push(Box::new(tag.trim().to_uppercase()));
+    }
+
     if let Some(is_dual) = filters.is_dual_eligible {
         let idx = param_values.len() + 1;
         conditions.push(format!("c.is_dual_eligible = ?{}", idx));
@@ -79,107 +175,127 @@ pub fn get_clients(
         param_values.push(Box::new(status_code.clone()));
     }
 
+    // Prospects and orphans: nobody is enrolled in anything current
+    if filters.enrollment_status.as_deref() == Some("NONE") {
+        conditions.push(
+            "c.id NOT IN (SELECT client_id FROM enrollments WHERE is_active = 1 AND status_code IN ('ACTIVE', 'PENDING'))"
+                .to_string(),
+        );
+    }
+
+    if let Some(ref after) = filters.created_after {
+        let idx = param_values.len() + 1;
+        conditions.push(format!("date(c.created_at) >= ?{}", idx));
+        param_values.push(Box::new(after.clone()));
+    }
+
+    if let Some(ref before) = filters.created_before {
+        let idx = param_values.len() + 1;
+        conditions.push(format!("date(c.created_at) <= ?{}", idx));
+        param_values.push(Box::new(before.clone()));
+    }
+
+    if let Some(ref since) = filters.no_contact_since {
+        let idx = param_values.len() + 1;
+        conditions.push(client::no_contact_since_sql(idx));
+        param_values.push(Box::new(since.clone()));
+    }
+
     let where_clause = if conditions.is_empty() {
         String::new()
     } else {
         format!("WHERE {}", conditions.join(" AND "))
     };
+    (where_clause, param_values)
+}
 
-    // Count total
-    let count_sql = format!("SELECT COUNT(*) FROM clients c {}", where_clause);
-    let params_refs: Vec<&dyn rusqlite::types::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
-    let total: i64 = conn.query_row(&count_sql, params_refs.as_slice(), |row| row.get(0))?;
-
-    // Fetch page — join latest active enrollment + carrier for list display
-    let limit_idx = param_values.len() + 1;
-    let offset_idx = param_values.len() + 2;
-    let select_sql = format!(
-        "SELECT c.id, c.first_name, c.last_name, c.dob, cr.name, e.plan_name, c.is_active
-         FROM clients c
-         LEFT JOIN enrollments e ON e.client_id = c.id
-           AND e.id = (
-             SELECT e2.id FROM enrollments e2
-             WHERE e2.client_id = c.id AND e2.is_active = 1
-             ORDER BY
-               CASE WHEN e2.status_code IN ('ACTIVE', 'PENDING') THEN 0 ELSE 1 END,
-               e2.effective_date DESC
-             LIMIT 1
-           )
-         LEFT JOIN carriers cr ON e.carrier_id = cr.id
-         {}
-         ORDER BY c.last_name, c.first_name
-         LIMIT ?{} OFFSET ?{}",
-        where_clause, limit_idx, offset_idx
-    );
-
-    param_values.push(Box::new(per_page as i64));
-    param_values.push(Box::new(offset as i64));
-    let params_refs: Vec<&dyn rusqlite::types::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
-
-    let mut stmt = conn.prepare(&select_sql)?;
-    let items = stmt.query_map(params_refs.as_slice(), |row| {
-        Ok(ClientListItem {
-            id: row.get(0)?,
-            first_name: row.get(1)?,
-            last_name: row.get(2)?,
-            dob: row.get(3)?,
-            carrier_name: row.get(4)?,
-            plan_name: row.get(5)?,
-            is_active: row.get(6)?,
-        })
-    })?
-    .collect::<Result<Vec<_>, _>>()?;
+/// Build the client list ORDER BY from the filter's sort fields. Column names
+/// come from a fixed allow-list, never from the caller's string; names are the
+/// tiebreaker so paging stays stable.
+fn order_by_clause(filters: &ClientFilters) -> Result<String, AppError> {
+    let dir = match filters.sort_dir.as_deref().map(str::to_ascii_lowercase).as_deref() {
+        None | Some("asc") => "ASC",
+        Some("desc") => "DESC",
+        Some(other) => {
+            return Err(AppError::Validation(format!("Invalid sort direction: {}", other)))
+        }
+    };
+    let column = match filters.sort_by.as_deref() {
+        None | Some("last_name") => return Ok(format!("ORDER BY c.last_name {dir}, c.first_name {dir}")),
+        Some("first_name") => "c.first_name",
+        Some("created_at") => "c.created_at",
+        Some("city") => "c.city",
+        Some("state") => "c.state",
+        Some("dob") => "c.dob",
+        Some(other) => {
+            return Err(AppError::Validation(format!("Cannot sort clients by {}", other)))
+        }
+    };
+    Ok(format!("ORDER BY {column} {dir}, c.last_name, c.first_name"))
+}
 
-    Ok(PaginatedResult {
-        items,
-        total,
-        page,
-        per_page,
+/// Every column of `clients`, in the order `map_client` reads them. Queries
+/// add `LAST_CONTACT_SQL` after these as the last column.
+const CLIENT_COLUMNS: &str = "c.id, c.first_name, c.last_name, c.middle_name, c.dob, c.gender, c.phone, c.phone2, c.email,
+               c.address_line1, c.address_line2, c.city, c.state, c.zip, c.county, c.mbi, c.part_a_date, c.part_b_date,
+               c.orec, c.is_dual_eligible, c.dual_status_code, c.lis_level, c.medicaid_id,
+               c.lead_source, c.member_record_locator, c.is_active, c.tags, c.notes,
+               c.created_at, c.updated_at";
+
+fn map_client(row: &rusqlite::Row) -> rusqlite::Result<Client> {
+    Ok(Client {
+        id: row.get(0)?,
+        first_name: row.get(1)?,
+        last_name: row.get(2)?,
+        middle_name: row.get(3)?,
+        dob: row.get(4)?,
+        gender: row.get(5)?,
+        phone: row.get(6)?,
+        phone2: row.get(7)?,
+        email: row.get(8)?,
+        address_line1: row.get(9)?,
+        address_line2: row.get(10)?,
+        city: row.get(11)?,
+        state: row.get(12)?,
+        zip: row.get(13)?,
+        county: row.get(14)?,
+        mbi: row.get(15)?,
+        part_a_date: row.get(16)?,
+        part_b_date: row.get(17)?,
+        orec: row.get(18)?,
+        is_dual_eligible: row.get(19)?,
+        dual_status_code: row.get(20)?,
+        lis_level: row.get(21)?,
+        medicaid_id: row.get(22)?,
+        lead_source: row.get(23)?,
+        member_record_locator: row.get(24)?,
+        is_active: row.get(25)?,
+        tags: row.get(26)?,
+        notes: row.get(27)?,
+        created_at: row.get(28)?,
+        updated_at: row.get(29)?,
+        age: None,
+        turns_65_on: None,
+        last_contact_at: row.get(30)?,
     })
 }
 
 /// Get a single client by ID
 pub fn get_client(conn: &Connection, id: &str) -> Result<Client, AppError> {
-    let sql = "SELECT id, first_name, last_name, middle_name, dob, gender, phone, phone2, email,
-               address_line1, address_line2, city, state, zip, county, mbi, part_a_date, part_b_date,
-               orec, is_dual_eligible, dual_status_code, lis_level, medicaid_id,
-               lead_source, member_record_locator, is_active, tags, notes,
-               created_at, updated_at
-               FROM clients WHERE id = ?1";
+    let sql = format!(
+        "SELECT {}, {} FROM clients c WHERE c.id = ?1",
+        CLIENT_COLUMNS,
+        client::LAST_CONTACT_SQL
+    );
 
-    conn.query_row(sql, params![id], |row| {
-        Ok(Client {
-            id: row.get(0)?,
-            first_name: row.get(1)?,
-            last_name: row.get(2)?,
-            middle_name: row.get(3)?,
-            dob: row.get(4)?,
-            gender: row.get(5)?,
-            phone: row.get(6)?,
-            phone2: row.get(7)?,
-            email: row.get(8)?,
-            address_line1: row.get(9)?,
-            address_line2: row.get(10)?,
-            city: row.get(11)?,
-            state: row.get(12)?,
-            zip: row.get(13)?,
-            county: row.get(14)?,
-            mbi: row.get(15)?,
-            part_a_date: row.get(16)?,
-            part_b_date: row.get(17)?,
-            orec: row.get(18)?,
-            is_dual_eligible: row.get(19)?,
-            dual_status_code: row.get(20)?,
-            lis_level: row.get(21)?,
-            medicaid_id: row.get(22)?,
-            lead_source: row.get(23)?,
-            member_record_locator: row.get(24)?,
-            is_active: row.get(25)?,
-            tags: row.get(26)?,
-            notes: row.get(27)?,
-            created_at: row.get(28)?,
-            updated_at: row.get(29)?,
-        })
+    conn.query_row(&sql, params![id], map_client)
+    .map(|mut client| {
+        if let Some(dob) = client.dob.as_deref().and_then(client::parse_dob) {
+            let today = chrono::Local::now().date_naive();
+            client.age = Some(client::age_on(dob, today));
+            client.turns_65_on = client::turns_65_on(dob).map(|d| d.format("%Y-%m-%d").to_string());
+        }
+        client
     })
     .map_err(|e| match e {
         rusqlite::Error::QueryReturnedNoRows => AppError::NotFound(format!("Client {} not found", id)),
@@ -187,6 +303,71 @@ pub fn get_client(conn: &Connection, id: &str) -> Result<Client, AppError> {
     })
 }
 
+/// Every client matching `filters`, unpaginated, as full records for export
+pub fn get_all_filtered_clients(conn: &Connection, filters: &ClientFilters) -> Result<Vec<Client>, AppError> {
+    let (where_clause, param_values) = filter_clause(filters);
+    let order_by = order_by_clause(filters)?;
+    let sql = format!(
+        "SELECT {}, {} FROM clients c {} {}",
+        CLIENT_COLUMNS,
+        client::LAST_CONTACT_SQL,
+        where_clause,
+        order_by
+    );
+
+    let params_refs: Vec<&dyn rusqlite::types::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let clients = stmt.query_map(params_refs.as_slice(), map_client)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(clients)
+}
+
+/// Active clients turning 65 between today and `months_ahead` months from
+/// now, soonest first. Clients with a missing or unparseable `dob` are
+/// skipped (SQLite's `date()` returns NULL for them).
+pub fn get_aging_in_clients(conn: &Connection, months_ahead: i64) -> Result<Vec<AgingInClient>, AppError> {
+    let sql = "SELECT c.id, c.first_name, c.last_name, c.dob, date(c.dob, '+65 years') AS turns_65_on,
+               c.phone, c.email,
+               EXISTS (SELECT 1 FROM enrollments e WHERE e.client_id = c.id AND e.is_active = 1)
+               FROM clients c
+               WHERE c.is_active = 1
+                 AND date(c.dob, '+65 years') >= date('now')
+                 AND date(c.dob, '+65 years') <= date('now', ?1)
+               ORDER BY turns_65_on, c.last_name, c.first_name";
+
+    let window = format!("+{} months", months_ahead);
+    let mut stmt = conn.prepare(sql)?;
+    let items = stmt
+        .query_map(params![window], |row| {
+            Ok(AgingInClient {
+                id: row.get(0)?,
+                first_name: row.get(1)?,
+                last_name: row.get(2)?,
+                dob: row.get(3)?,
+                turns_65_on: row.get(4)?,
+                phone: row.get(5)?,
+                email: row.get(6)?,
+                has_enrollment: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+}
+
+/// Whether an active client other than `exclude_id` already has this MBI.
+pub fn mbi_exists(conn: &Connection, mbi: &str, exclude_id: Option<&str>) -> Result<bool, AppError> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS (
+             SELECT 1 FROM clients
+             WHERE mbi = ?1 AND is_active = 1 AND (?2 IS NULL OR id != ?2)
+         )",
+        params![mbi, exclude_id],
+        |row| row.get(0),
+    )?;
+    Ok(exists)
+}
+
 /// Create a new client
 pub fn create_client(conn: &Connection, id: &str, input: &CreateClientInput) -> Result<(), AppError> {
     let sql = "INSERT INTO clients (id, first_name, last_name, middle_name, dob, gender, phone, phone2, email,
@@ -270,6 +451,50 @@ pub fn update_client(conn: &Connection, id: &str, input: &UpdateClientInput) ->
     Ok(())
 }
 
+/// Add `tag` to a client's tag set, leaving the other tags alone. Adding a
+/// tag the client already has (in any case) is a no-op. Returns whether the
+/// tags changed.
+pub fn add_tag(conn: &Connection, id: &str, tag: &str) -> Result<bool, AppError> {
+    let mut tags = get_tags(conn, id)?;
+    if tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+        return Ok(false);
+    }
+    tags.push(tag.to_string());
+    set_tags(conn, id, &tags)?;
+    Ok(true)
+}
+
+/// Remove `tag` (matched ignoring case) from a client's tag set. Returns
+/// whether the tags changed.
+pub fn remove_tag(conn: &Connection, id: &str, tag: &str) -> Result<bool, AppError> {
+    let mut tags = get_tags(conn, id)?;
+    let before = tags.len();
+    tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+    if tags.len() == before {
+        return Ok(false);
+    }
+    set_tags(conn, id, &tags)?;
+    Ok(true)
+}
+
+fn get_tags(conn: &Connection, id: &str) -> Result<Vec<String>, AppError> {
+    let tags: Option<String> = conn
+        .query_row("SELECT tags FROM clients WHERE id = ?1", params![id], |row| row.get(0))
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => AppError::NotFound(format!("Client {} not found", id)),
+            _ => AppError::Database(e.to_string()),
+        })?;
+    Ok(tags.as_deref().map(client::split_tags).unwrap_or_default())
+}
+
+fn set_tags(conn: &Connection, id: &str, tags: &[String]) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE clients SET tags = ?1 WHERE id = ?2",
+        params![client::join_tags(tags), id],
+    )?;
+    Ok(())
+}
+
 /// Soft-delete a client
 pub fn delete_client(conn: &Connection, id: &str) -> Result<(), AppError> {
     let rows = conn.execute("UPDATE clients SET is_active = 0 WHERE id = ?1", params![id])?;
@@ -279,6 +504,49 @@ pub fn delete_client(conn: &Connection, id: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Permanently delete soft-deleted clients last touched more than
+/// `older_than_days` ago, together with their enrollments, conversations,
+/// providers, and sync review items. Commission entries are kept but unlinked. Returns the
+/// number of clients removed; the caller rebuilds the FTS index.
+pub fn purge_inactive_clients(conn: &Connection, older_than_days: i64) -> Result<usize, AppError> {
+    let cutoff = format!("-{} days", older_than_days);
+    let purge_set = "SELECT id FROM clients WHERE is_active = 0 AND updated_at < datetime('now', ?1)";
+
+    conn.execute(
+        &format!("DELETE FROM conversation_entries WHERE client_id IN ({})", purge_set),
+        params![cutoff],
+    )?;
+    conn.execute(
+        &format!("DELETE FROM conversations WHERE client_id IN ({})", purge_set),
+        params![cutoff],
+    )?;
+    conn.execute(
+        &format!(
+            "DELETE FROM sync_review_items WHERE client_id IN ({0})
+                OR enrollment_id IN (SELECT id FROM enrollments WHERE client_id IN ({0}))",
+            purge_set
+        ),
+        params![cutoff],
+    )?;
+    conn.execute(
+        &format!("DELETE FROM enrollments WHERE client_id IN ({})", purge_set),
+        params![cutoff],
+    )?;
+    conn.execute(
+        &format!("DELETE FROM client_providers WHERE client_id IN ({})", purge_set),
+        params![cutoff],
+    )?;
+    conn.execute(
+        &format!("UPDATE commission_entries SET client_id = NULL WHERE client_id IN ({})", purge_set),
+        params![cutoff],
+    )?;
+    let purged = conn.execute(
+        "DELETE FROM clients WHERE is_active = 0 AND updated_at < datetime('now', ?1)",
+        params![cutoff],
+    )?;
+    Ok(purged)
+}
+
 /// Hard-delete a client and all related records (enrollments, conversations, etc.)
 pub fn hard_delete_client(conn: &Connection, id: &str) -> Result<(), AppError> {
     // Verify the client exists
@@ -292,12 +560,28 @@ pub fn hard_delete_client(conn: &Connection, id: &str) -> Result<(), AppError> {
     // Delete children in dependency order
     conn.execute("DELETE FROM conversation_entries WHERE client_id = ?1", params![id])?;
     conn.execute("DELETE FROM conversations WHERE client_id = ?1", params![id])?;
+    conn.execute(
+        "DELETE FROM sync_review_items WHERE client_id = ?1
+            OR enrollment_id IN (SELECT id FROM enrollments WHERE client_id = ?1)",
+        params![id],
+    )?;
     conn.execute("DELETE FROM enrollments WHERE client_id = ?1", params![id])?;
     conn.execute("DELETE FROM client_providers WHERE client_id = ?1", params![id])?;
     conn.execute("DELETE FROM clients WHERE id = ?1", params![id])?;
 
-    // Rebuild FTS index
+    rebuild_search_index(conn)
+}
+
+/// Regenerate `clients_fts` from `clients`. The sync triggers normally keep
+/// it current; this is the repair path.
+pub fn rebuild_search_index(conn: &Connection) -> Result<(), AppError> {
     conn.execute("INSERT INTO clients_fts(clients_fts) VALUES('rebuild')", [])?;
+    Ok(())
+}
 
+/// Merge the FTS index's segments into one, for faster searches after many
+/// writes
+pub fn optimize_search_index(conn: &Connection) -> Result<(), AppError> {
+    conn.execute("INSERT INTO clients_fts(clients_fts) VALUES('optimize')", [])?;
     Ok(())
 }
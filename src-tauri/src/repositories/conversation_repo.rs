@@ -3,7 +3,8 @@ use rusqlite::{params, Connection};
 use crate::error::AppError;
 use crate::models::{
     Conversation, ConversationEntry, ConversationListItem, CreateConversationEntryInput,
-    CreateConversationInput, TimelineEntry, UpdateConversationEntryInput, UpdateConversationInput,
+    CreateConversationInput, EntryType, TimelineEntry, UpdateConversationEntryInput,
+    UpdateConversationInput,
 };
 
 // ── Conversations ────────────────────────────────────────────────────────────
@@ -118,7 +119,8 @@ pub fn get_conversation_entries(
                       call_direction, call_duration, call_outcome, call_phone_number,
                       meeting_location, meeting_type, email_to, email_from,
                       system_event_type, system_event_data,
-                      is_active, created_at, updated_at
+                      is_active, created_at, updated_at,
+                      follow_up_recurrence, follow_up_until
                FROM conversation_entries
                WHERE conversation_id = ?1 AND is_active = 1
                ORDER BY occurred_at DESC";
@@ -149,6 +151,8 @@ pub fn get_conversation_entries(
                 is_active: row.get(19)?,
                 created_at: row.get(20)?,
                 updated_at: row.get(21)?,
+                follow_up_recurrence: row.get(22)?,
+                follow_up_until: row.get(23)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -165,7 +169,8 @@ pub fn get_conversation_entry(
                       call_direction, call_duration, call_outcome, call_phone_number,
                       meeting_location, meeting_type, email_to, email_from,
                       system_event_type, system_event_data,
-                      is_active, created_at, updated_at
+                      is_active, created_at, updated_at,
+                      follow_up_recurrence, follow_up_until
                FROM conversation_entries WHERE id = ?1";
 
     conn.query_row(sql, params![id], |row| {
@@ -192,6 +197,8 @@ pub fn get_conversation_entry(
             is_active: row.get(19)?,
             created_at: row.get(20)?,
             updated_at: row.get(21)?,
+            follow_up_recurrence: row.get(22)?,
+            follow_up_until: row.get(23)?,
         })
     })
     .map_err(|e| match e {
@@ -211,9 +218,10 @@ pub fn create_conversation_entry(
                (id, conversation_id, client_id, entry_type, subject, body, occurred_at,
                 follow_up_date, follow_up_note,
                 call_direction, call_duration, call_outcome, call_phone_number,
-                meeting_location, meeting_type, email_to, email_from)
+                meeting_location, meeting_type, email_to, email_from,
+                follow_up_recurrence, follow_up_until)
                VALUES (?1, ?2, ?3, ?4, ?5, ?6, COALESCE(?7, datetime('now')),
-                        ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)";
+                        ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)";
 
     conn.execute(
         sql,
@@ -235,6 +243,8 @@ pub fn create_conversation_entry(
             input.meeting_type,
             input.email_to,
             input.email_from,
+            input.follow_up_recurrence,
+            input.follow_up_until,
         ],
     )?;
 
@@ -260,7 +270,9 @@ pub fn update_conversation_entry(
                meeting_type = COALESCE(?12, meeting_type),
                email_to = COALESCE(?13, email_to),
                email_from = COALESCE(?14, email_from),
-               is_active = COALESCE(?15, is_active)
+               is_active = COALESCE(?15, is_active),
+               follow_up_recurrence = COALESCE(?16, follow_up_recurrence),
+               follow_up_until = COALESCE(?17, follow_up_until)
                WHERE id = ?1";
 
     let rows = conn.execute(
@@ -281,6 +293,8 @@ pub fn update_conversation_entry(
             input.email_to,
             input.email_from,
             input.is_active,
+            input.follow_up_recurrence,
+            input.follow_up_until,
         ],
     )?;
 
@@ -298,7 +312,7 @@ pub fn update_conversation_entry(
 pub fn get_client_timeline(
     conn: &Connection,
     client_id: &str,
-    entry_type_filter: Option<&str>,
+    entry_type_filter: Option<EntryType>,
     limit: i64,
     offset: i64,
 ) -> Result<Vec<TimelineEntry>, AppError> {
@@ -310,7 +324,8 @@ pub fn get_client_timeline(
                         ce.follow_up_date, ce.follow_up_note,
                         ce.call_direction, ce.call_duration, ce.call_outcome, ce.call_phone_number,
                         ce.meeting_location, ce.meeting_type, ce.email_to, ce.email_from,
-                        ce.system_event_type, ce.system_event_data, ce.created_at
+                        ce.system_event_type, ce.system_event_data, ce.created_at,
+                        ce.follow_up_recurrence, ce.follow_up_until
                  FROM conversation_entries ce
                  JOIN conversations c ON c.id = ce.conversation_id
                  WHERE ce.client_id = ?1 AND ce.entry_type = ?2 AND ce.is_active = 1 AND c.is_active = 1
@@ -319,7 +334,7 @@ pub fn get_client_timeline(
                     .to_string(),
                 vec![
                     Box::new(client_id.to_string()) as Box<dyn rusqlite::types::ToSql>,
-                    Box::new(et.to_string()),
+                    Box::new(et),
                     Box::new(limit),
                     Box::new(offset),
                 ],
@@ -331,7 +346,8 @@ pub fn get_client_timeline(
                         ce.follow_up_date, ce.follow_up_note,
                         ce.call_direction, ce.call_duration, ce.call_outcome, ce.call_phone_number,
                         ce.meeting_location, ce.meeting_type, ce.email_to, ce.email_from,
-                        ce.system_event_type, ce.system_event_data, ce.created_at
+                        ce.system_event_type, ce.system_event_data, ce.created_at,
+                        ce.follow_up_recurrence, ce.follow_up_until
                  FROM conversation_entries ce
                  JOIN conversations c ON c.id = ce.conversation_id
                  WHERE ce.client_id = ?1 AND ce.is_active = 1 AND c.is_active = 1
@@ -374,6 +390,8 @@ pub fn get_client_timeline(
                 system_event_type: row.get(18)?,
                 system_event_data: row.get(19)?,
                 created_at: row.get(20)?,
+                follow_up_recurrence: row.get(21)?,
+                follow_up_until: row.get(22)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -381,41 +399,86 @@ pub fn get_client_timeline(
     Ok(items)
 }
 
+/// `occurred_at` of the client's most recent system event of one type
+pub fn get_last_system_event_at(conn: &Connection, client_id: &str, event_type: &str) -> Result<Option<String>, AppError> {
+    let last = conn.query_row(
+        "SELECT MAX(occurred_at) FROM conversation_entries
+         WHERE client_id = ?1 AND is_active = 1 AND entry_type = 'SYSTEM' AND system_event_type = ?2",
+        [client_id, event_type],
+        |row| row.get(0),
+    )?;
+    Ok(last)
+}
+
+/// `occurred_at` of the client's most recent active, non-system entry
+pub fn get_last_entry_at(conn: &Connection, client_id: &str) -> Result<Option<String>, AppError> {
+    let last = conn.query_row(
+        "SELECT MAX(occurred_at) FROM conversation_entries
+         WHERE client_id = ?1 AND is_active = 1 AND entry_type != 'SYSTEM'",
+        [client_id],
+        |row| row.get(0),
+    )?;
+    Ok(last)
+}
+
 pub fn get_pending_follow_ups(
     conn: &Connection,
     client_id: Option<&str>,
+    include_overdue: bool,
 ) -> Result<Vec<TimelineEntry>, AppError> {
+    // A past follow-up counts as dropped until the client has some later
+    // (non-system) entry logged against them
+    let overdue = if include_overdue {
+        "OR NOT EXISTS (
+                            SELECT 1 FROM conversation_entries later
+                            WHERE later.client_id = ce.client_id AND later.is_active = 1
+                                  AND later.entry_type != 'SYSTEM'
+                                  AND later.occurred_at > ce.occurred_at)"
+    } else {
+        ""
+    };
+
     let (sql, param_values): (String, Vec<Box<dyn rusqlite::types::ToSql>>) =
         if let Some(cid) = client_id {
             (
-                "SELECT ce.id, ce.conversation_id, c.title, ce.client_id, ce.entry_type,
+                format!("SELECT ce.id, ce.conversation_id, c.title, ce.client_id, ce.entry_type,
                         ce.subject, ce.body, ce.occurred_at,
                         ce.follow_up_date, ce.follow_up_note,
                         ce.call_direction, ce.call_duration, ce.call_outcome, ce.call_phone_number,
                         ce.meeting_location, ce.meeting_type, ce.email_to, ce.email_from,
-                        ce.system_event_type, ce.system_event_data, ce.created_at
+                        ce.system_event_type, ce.system_event_data, ce.created_at,
+                        ce.follow_up_recurrence, ce.follow_up_until
                  FROM conversation_entries ce
                  JOIN conversations c ON c.id = ce.conversation_id
                  WHERE ce.client_id = ?1 AND ce.follow_up_date IS NOT NULL
-                       AND ce.follow_up_date >= date('now') AND ce.is_active = 1 AND c.is_active = 1
+                       AND (ce.follow_up_date >= date('now') OR (
+                            ce.follow_up_recurrence IN ('WEEKLY', 'MONTHLY', 'QUARTERLY')
+                            AND (ce.follow_up_until IS NULL OR ce.follow_up_until >= date('now')))
+                        {overdue})
+                       AND ce.is_active = 1 AND c.is_active = 1
                  ORDER BY ce.follow_up_date ASC"
-                    .to_string(),
+                ),
                 vec![Box::new(cid.to_string()) as Box<dyn rusqlite::types::ToSql>],
             )
         } else {
             (
-                "SELECT ce.id, ce.conversation_id, c.title, ce.client_id, ce.entry_type,
+                format!("SELECT ce.id, ce.conversation_id, c.title, ce.client_id, ce.entry_type,
                         ce.subject, ce.body, ce.occurred_at,
                         ce.follow_up_date, ce.follow_up_note,
                         ce.call_direction, ce.call_duration, ce.call_outcome, ce.call_phone_number,
                         ce.meeting_location, ce.meeting_type, ce.email_to, ce.email_from,
-                        ce.system_event_type, ce.system_event_data, ce.created_at
+                        ce.system_event_type, ce.system_event_data, ce.created_at,
+                        ce.follow_up_recurrence, ce.follow_up_until
                  FROM conversation_entries ce
                  JOIN conversations c ON c.id = ce.conversation_id
                  WHERE ce.follow_up_date IS NOT NULL
-                       AND ce.follow_up_date >= date('now') AND ce.is_active = 1 AND c.is_active = 1
+                       AND (ce.follow_up_date >= date('now') OR (
+                            ce.follow_up_recurrence IN ('WEEKLY', 'MONTHLY', 'QUARTERLY')
+                            AND (ce.follow_up_until IS NULL OR ce.follow_up_until >= date('now')))
+                        {overdue})
+                       AND ce.is_active = 1 AND c.is_active = 1
                  ORDER BY ce.follow_up_date ASC"
-                    .to_string(),
+                ),
                 vec![],
             )
         };
@@ -448,6 +511,8 @@ pub fn get_pending_follow_ups(
                 system_event_type: row.get(18)?,
                 system_event_data: row.get(19)?,
                 created_at: row.get(20)?,
+                follow_up_recurrence: row.get(21)?,
+                follow_up_until: row.get(22)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -1,6 +1,6 @@
 use rusqlite::{params, Connection};
 use crate::error::AppError;
-use crate::models::{Enrollment, EnrollmentListItem, CreateEnrollmentInput, UpdateEnrollmentInput};
+use crate::models::{Enrollment, EnrollmentHistoryItem, EnrollmentListItem, CreateEnrollmentInput, UpdateEnrollmentInput, PaginatedResult};
 
 /// Get enrollments, optionally filtered by client_id
 pub fn get_enrollments(conn: &Connection, client_id: Option<&str>) -> Result<Vec<EnrollmentListItem>, AppError> {
@@ -30,29 +30,115 @@ pub fn get_enrollments(conn: &Connection, client_id: Option<&str>) -> Result<Vec
 
     let params_refs: Vec<&dyn rusqlite::types::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
     let mut stmt = conn.prepare(&sql)?;
-    let items = stmt.query_map(params_refs.as_slice(), |row| {
-        Ok(EnrollmentListItem {
-            id: row.get(0)?,
-            client_name: row.get(1)?,
-            plan_name: row.get(2)?,
-            carrier_name: row.get(3)?,
-            plan_type: row.get(4)?,
-            status: row.get(5)?,
-            effective_date: row.get(6)?,
-            termination_date: row.get(7)?,
-        })
-    })?
-    .collect::<Result<Vec<_>, _>>()?;
+    let items = stmt.query_map(params_refs.as_slice(), map_list_item)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(items)
+}
+
+/// Get every enrollment a client has had, newest first, including inactive
+/// (deleted) records and all statuses
+pub fn get_enrollment_history(conn: &Connection, client_id: &str) -> Result<Vec<EnrollmentHistoryItem>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT e.id, e.plan_name, cr.name, e.plan_type_code, e.status_code, es.name,
+                e.effective_date, e.termination_date, e.enrollment_source, e.is_active
+         FROM enrollments e
+         LEFT JOIN carriers cr ON e.carrier_id = cr.id
+         LEFT JOIN enrollment_statuses es ON e.status_code = es.code
+         WHERE e.client_id = ?1
+         ORDER BY e.effective_date DESC, e.created_at DESC",
+    )?;
+    let items = stmt
+        .query_map([client_id], |row| {
+            Ok(EnrollmentHistoryItem {
+                id: row.get(0)?,
+                plan_name: row.get(1)?,
+                carrier_name: row.get(2)?,
+                plan_type: row.get(3)?,
+                status_code: row.get(4)?,
+                status: row.get(5)?,
+                effective_date: row.get(6)?,
+                termination_date: row.get(7)?,
+                enrollment_source: row.get(8)?,
+                is_active: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(items)
+}
+
+/// Get one page of all active enrollments, with the total count
+pub fn get_enrollments_page(conn: &Connection, page: i32, per_page: i32) -> Result<PaginatedResult<EnrollmentListItem>, AppError> {
+    let offset = (page - 1) * per_page;
+
+    let total: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM enrollments WHERE is_active = 1",
+        [],
+        |row| row.get(0),
+    )?;
+
+    let mut stmt = conn.prepare(
+        "SELECT e.id, c.first_name || ' ' || c.last_name, e.plan_name, cr.name, e.plan_type_code, es.name, e.effective_date, e.termination_date
+         FROM enrollments e
+         LEFT JOIN clients c ON e.client_id = c.id
+         LEFT JOIN carriers cr ON e.carrier_id = cr.id
+         LEFT JOIN enrollment_statuses es ON e.status_code = es.code
+         WHERE e.is_active = 1
+         ORDER BY e.effective_date DESC, e.id
+         LIMIT ?1 OFFSET ?2",
+    )?;
+    let items = stmt.query_map(params![per_page as i64, offset as i64], map_list_item)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PaginatedResult {
+        items,
+        total,
+        page,
+        per_page,
+    })
+}
 
+/// Active enrollments that started before `year_start` and are still in
+/// force on it, i.e. the ones that need rolling over into the new plan year
+pub fn get_renewable_enrollments(conn: &Connection, year_start: &str) -> Result<Vec<EnrollmentListItem>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT e.id, c.first_name || ' ' || c.last_name, e.plan_name, cr.name, e.plan_type_code, es.name, e.effective_date, e.termination_date
+         FROM enrollments e
+         LEFT JOIN clients c ON e.client_id = c.id
+         LEFT JOIN carriers cr ON e.carrier_id = cr.id
+         LEFT JOIN enrollment_statuses es ON e.status_code = es.code
+         WHERE e.is_active = 1 AND c.is_active = 1
+           AND e.status_code = 'ACTIVE'
+           AND e.effective_date < ?1
+           AND (e.termination_date IS NULL OR e.termination_date = '' OR e.termination_date >= ?1)
+         ORDER BY c.last_name, c.first_name, e.effective_date",
+    )?;
+    let items = stmt.query_map(params![year_start], map_list_item)?
+        .collect::<Result<Vec<_>, _>>()?;
     Ok(items)
 }
 
+fn map_list_item(row: &rusqlite::Row) -> rusqlite::Result<EnrollmentListItem> {
+    Ok(EnrollmentListItem {
+        id: row.get(0)?,
+        client_name: row.get(1)?,
+        plan_name: row.get(2)?,
+        carrier_name: row.get(3)?,
+        plan_type: row.get(4)?,
+        status: row.get(5)?,
+        effective_date: row.get(6)?,
+        termination_date: row.get(7)?,
+    })
+}
+
 /// Get a single enrollment by ID
 pub fn get_enrollment(conn: &Connection, id: &str) -> Result<Enrollment, AppError> {
     let sql = "SELECT id, client_id, plan_id, carrier_id, plan_type_code, plan_name, contract_number,
                pbp_number, effective_date, termination_date, application_date, status_code,
                enrollment_period, disenrollment_reason, premium, confirmation_number, enrollment_source,
-               is_active, created_at, updated_at
+               is_active, created_at, updated_at,
+               commission_amount, commission_status, commission_paid_date, aor_status
                FROM enrollments WHERE id = ?1";
 
     conn.query_row(sql, params![id], |row| {
@@ -74,6 +160,10 @@ pub fn get_enrollment(conn: &Connection, id: &str) -> Result<Enrollment, AppErro
             premium: row.get(14)?,
             confirmation_number: row.get(15)?,
             enrollment_source: row.get(16)?,
+            commission_amount: row.get(20)?,
+            commission_status: row.get(21)?,
+            commission_paid_date: row.get(22)?,
+            aor_status: row.get(23)?,
             is_active: row.get(17)?,
             created_at: row.get(18)?,
             updated_at: row.get(19)?,
@@ -124,6 +214,26 @@ pub fn has_active_enrollment_in_category(conn: &Connection, client_id: &str, pla
     Ok(count > 0)
 }
 
+/// Whether `code` is a seeded enrollment status
+pub fn status_code_exists(conn: &Connection, code: &str) -> Result<bool, AppError> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM enrollment_statuses WHERE code = ?1)",
+        params![code],
+        |row| row.get(0),
+    )?;
+    Ok(exists)
+}
+
+/// Whether `code` is a seeded plan type
+pub fn plan_type_exists(conn: &Connection, code: &str) -> Result<bool, AppError> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM plan_types WHERE code = ?1)",
+        params![code],
+        |row| row.get(0),
+    )?;
+    Ok(exists)
+}
+
 fn get_plan_category(plan_type_code: &str) -> String {
     match plan_type_code {
         "MA" | "MAPD" | "DSNP" | "CSNP" | "ISNP" | "MMP" | "PACE" | "MSA" | "PFFS" | "COST" => "ADVANTAGE".to_string(),
@@ -146,14 +256,16 @@ fn get_codes_for_category(category: &str) -> Vec<&'static str> {
 pub fn create_enrollment(conn: &Connection, id: &str, input: &CreateEnrollmentInput) -> Result<(), AppError> {
     let sql = "INSERT INTO enrollments (id, client_id, plan_id, carrier_id, plan_type_code, plan_name,
                contract_number, pbp_number, effective_date, termination_date, application_date,
-               status_code, enrollment_period, disenrollment_reason, premium, confirmation_number, enrollment_source)
-               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)";
+               status_code, enrollment_period, disenrollment_reason, premium, confirmation_number, enrollment_source,
+               commission_amount, commission_status, commission_paid_date)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20)";
 
     conn.execute(sql, params![
         id, input.client_id, input.plan_id, input.carrier_id, input.plan_type_code, input.plan_name,
         input.contract_number, input.pbp_number, input.effective_date, input.termination_date,
         input.application_date, input.status_code, input.enrollment_period, input.disenrollment_reason,
-        input.premium, input.confirmation_number, input.enrollment_source
+        input.premium, input.confirmation_number, input.enrollment_source,
+        input.commission_amount, input.commission_status, input.commission_paid_date
     ])?;
 
     Ok(())
@@ -168,14 +280,18 @@ pub fn update_enrollment(conn: &Connection, id: &str, input: &UpdateEnrollmentIn
                application_date = COALESCE(?10, application_date), status_code = COALESCE(?11, status_code),
                enrollment_period = COALESCE(?12, enrollment_period), disenrollment_reason = COALESCE(?13, disenrollment_reason),
                premium = COALESCE(?14, premium), confirmation_number = COALESCE(?15, confirmation_number),
-               enrollment_source = COALESCE(?16, enrollment_source), is_active = COALESCE(?17, is_active)
+               enrollment_source = COALESCE(?16, enrollment_source), is_active = COALESCE(?17, is_active),
+               commission_amount = COALESCE(?18, commission_amount),
+               commission_status = COALESCE(?19, commission_status),
+               commission_paid_date = COALESCE(?20, commission_paid_date)
                WHERE id = ?1";
 
     let rows = conn.execute(sql, params![
         id, input.plan_id, input.carrier_id, input.plan_type_code, input.plan_name,
         input.contract_number, input.pbp_number, input.effective_date, input.termination_date,
         input.application_date, input.status_code, input.enrollment_period, input.disenrollment_reason,
-        input.premium, input.confirmation_number, input.enrollment_source, input.is_active
+        input.premium, input.confirmation_number, input.enrollment_source, input.is_active,
+        input.commission_amount, input.commission_status, input.commission_paid_date
     ])?;
 
     if rows == 0 {
@@ -184,3 +300,15 @@ pub fn update_enrollment(conn: &Connection, id: &str, input: &UpdateEnrollmentIn
 
     Ok(())
 }
+
+/// Soft-delete (`active = false`) or restore an enrollment
+pub fn set_enrollment_active(conn: &Connection, id: &str, active: bool) -> Result<(), AppError> {
+    let rows = conn.execute(
+        "UPDATE enrollments SET is_active = ?2, updated_at = datetime('now') WHERE id = ?1",
+        params![id, active],
+    )?;
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Enrollment {} not found", id)));
+    }
+    Ok(())
+}
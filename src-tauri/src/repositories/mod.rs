@@ -1,7 +1,9 @@
+pub mod audit_repo;
 pub mod carrier_repo;
 pub mod client_repo;
 pub mod commission_repo;
 pub mod conversation_repo;
 pub mod enrollment_repo;
+pub mod plan_repo;
 pub mod provider_repo;
 pub mod report_repo;
@@ -0,0 +1,113 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use crate::error::AppError;
+use crate::models::{CreatePlanInput, Plan, PlanListItem, UpdatePlanInput};
+
+const PLAN_COLUMNS: &str = "id, carrier_id, plan_type_code, plan_name, contract_number, pbp_number,
+                            segment_id, plan_year, state, county_fips, premium, is_active, created_at, updated_at";
+
+fn map_plan(row: &rusqlite::Row) -> rusqlite::Result<Plan> {
+    Ok(Plan {
+        id: row.get(0)?,
+        carrier_id: row.get(1)?,
+        plan_type_code: row.get(2)?,
+        plan_name: row.get(3)?,
+        contract_number: row.get(4)?,
+        pbp_number: row.get(5)?,
+        segment_id: row.get(6)?,
+        plan_year: row.get(7)?,
+        state: row.get(8)?,
+        county_fips: row.get(9)?,
+        premium: row.get(10)?,
+        is_active: row.get(11)?,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+    })
+}
+
+/// Active plans, optionally narrowed to one carrier and/or plan year
+pub fn get_plans(conn: &Connection, carrier_id: Option<&str>, plan_year: Option<i32>) -> Result<Vec<PlanListItem>, AppError> {
+    let sql = "SELECT p.id, cr.name, p.plan_type_code, p.plan_name, p.contract_number, p.pbp_number,
+                      p.plan_year, p.premium
+               FROM plans p
+               LEFT JOIN carriers cr ON p.carrier_id = cr.id
+               WHERE p.is_active = 1
+                 AND (?1 IS NULL OR p.carrier_id = ?1)
+                 AND (?2 IS NULL OR p.plan_year = ?2)
+               ORDER BY p.plan_year DESC, cr.name, p.plan_name";
+    let mut stmt = conn.prepare(sql)?;
+    let items = stmt.query_map(params![carrier_id, plan_year], |row| {
+        Ok(PlanListItem {
+            id: row.get(0)?,
+            carrier_name: row.get(1)?,
+            plan_type_code: row.get(2)?,
+            plan_name: row.get(3)?,
+            contract_number: row.get(4)?,
+            pbp_number: row.get(5)?,
+            plan_year: row.get(6)?,
+            premium: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+}
+
+pub fn get_plan(conn: &Connection, id: &str) -> Result<Plan, AppError> {
+    let sql = format!("SELECT {} FROM plans WHERE id = ?1", PLAN_COLUMNS);
+    conn.query_row(&sql, [id], map_plan).map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => AppError::NotFound(format!("Plan {} not found", id)),
+        _ => AppError::Database(e.to_string()),
+    })
+}
+
+/// Active plan by contract and PBP. With a plan year only that year's plan
+/// matches; without one the most recent year wins.
+pub fn find_plan_by_contract_pbp(
+    conn: &Connection,
+    contract_number: &str,
+    pbp_number: &str,
+    plan_year: Option<i32>,
+) -> Result<Option<Plan>, AppError> {
+    let sql = format!(
+        "SELECT {} FROM plans
+         WHERE contract_number = ?1 AND pbp_number = ?2 AND is_active = 1
+           AND (?3 IS NULL OR plan_year = ?3)
+         ORDER BY plan_year DESC, segment_id
+         LIMIT 1",
+        PLAN_COLUMNS
+    );
+    let plan = conn
+        .query_row(&sql, params![contract_number, pbp_number, plan_year], map_plan)
+        .optional()?;
+    Ok(plan)
+}
+
+pub fn create_plan(conn: &Connection, id: &str, input: &CreatePlanInput) -> Result<(), AppError> {
+    let sql = "INSERT INTO plans (id, carrier_id, plan_type_code, plan_name, contract_number, pbp_number,
+               segment_id, plan_year, state, county_fips, premium)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, COALESCE(?7, '000'), ?8, ?9, ?10, ?11)";
+    conn.execute(sql, params![
+        id, input.carrier_id, input.plan_type_code, input.plan_name, input.contract_number,
+        input.pbp_number, input.segment_id, input.plan_year, input.state, input.county_fips, input.premium
+    ])?;
+    Ok(())
+}
+
+pub fn update_plan(conn: &Connection, id: &str, input: &UpdatePlanInput) -> Result<(), AppError> {
+    let sql = "UPDATE plans SET carrier_id = COALESCE(?2, carrier_id), plan_type_code = COALESCE(?3, plan_type_code),
+               plan_name = COALESCE(?4, plan_name), contract_number = COALESCE(?5, contract_number),
+               pbp_number = COALESCE(?6, pbp_number), segment_id = COALESCE(?7, segment_id),
+               plan_year = COALESCE(?8, plan_year), state = COALESCE(?9, state),
+               county_fips = COALESCE(?10, county_fips), premium = COALESCE(?11, premium),
+               is_active = COALESCE(?12, is_active), updated_at = datetime('now')
+               WHERE id = ?1";
+    let rows = conn.execute(sql, params![
+        id, input.carrier_id, input.plan_type_code, input.plan_name, input.contract_number,
+        input.pbp_number, input.segment_id, input.plan_year, input.state, input.county_fips,
+        input.premium, input.is_active
+    ])?;
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Plan {} not found", id)));
+    }
+    Ok(())
+}
@@ -1,6 +1,9 @@
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 use crate::error::AppError;
-use crate::models::report::{DashboardStats, MonthlyTrend};
+use crate::models::report::{DashboardStats, EnrollmentPeriodWindow, MonthlyTrend};
+
+const BY_PLAN_TYPE_SQL: &str = "SELECT COALESCE(e.plan_type_code, 'Unknown'), COUNT(DISTINCT e.client_id) FROM enrollments e WHERE e.status_code = 'ACTIVE' AND e.is_active = 1 GROUP BY e.plan_type_code ORDER BY COUNT(DISTINCT e.client_id) DESC";
+const BY_STATE_SQL: &str = "SELECT COALESCE(cl.state, 'Unknown'), COUNT(*) FROM clients cl WHERE cl.is_active = 1 AND cl.state IS NOT NULL GROUP BY cl.state ORDER BY COUNT(*) DESC LIMIT 15";
 
 pub fn get_dashboard_stats(conn: &Connection) -> Result<DashboardStats, AppError> {
     // Total active clients
@@ -31,18 +34,91 @@ pub fn get_dashboard_stats(conn: &Connection) -> Result<DashboardStats, AppError
         |row| row.get(0),
     )?;
 
-    // By plan type
-    let by_plan_type = query_pairs(conn,
-        "SELECT COALESCE(e.plan_type_code, 'Unknown'), COUNT(DISTINCT e.client_id) FROM enrollments e WHERE e.status_code = 'ACTIVE' AND e.is_active = 1 GROUP BY e.plan_type_code ORDER BY COUNT(DISTINCT e.client_id) DESC"
+    // One-off follow-ups past due with nothing logged for the client since.
+    // get_pending_follow_ups moves recurring series on to their next
+    // occurrence instead of listing them as overdue, so they aren't counted.
+    let overdue_follow_ups: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM conversation_entries ce
+         JOIN conversations c ON c.id = ce.conversation_id
+         JOIN clients cl ON cl.id = ce.client_id
+         WHERE ce.follow_up_date IS NOT NULL AND ce.follow_up_date < date('now')
+               AND COALESCE(ce.follow_up_recurrence, 'NONE') = 'NONE'
+               AND ce.is_active = 1 AND c.is_active = 1 AND cl.is_active = 1
+               AND NOT EXISTS (
+                   SELECT 1 FROM conversation_entries later
+                   WHERE later.client_id = ce.client_id AND later.is_active = 1
+                         AND later.entry_type != 'SYSTEM'
+                         AND later.occurred_at > ce.occurred_at)",
+        [],
+        |row| row.get(0),
+    )?;
+
+    // Follow-ups due today through the next 7 days
+    let follow_ups_next_7_days: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM conversation_entries ce
+         JOIN conversations c ON c.id = ce.conversation_id
+         JOIN clients cl ON cl.id = ce.client_id
+         WHERE ce.follow_up_date >= date('now') AND ce.follow_up_date < date('now', '+7 days')
+               AND ce.is_active = 1 AND c.is_active = 1 AND cl.is_active = 1",
+        [],
+        |row| row.get(0),
+    )?;
+
+    // Per-enrollment commission: what active enrollments should bring in
+    // vs. what has actually been marked paid this month
+    let projected_commission_this_month: f64 = conn.query_row(
+        "SELECT COALESCE(SUM(commission_amount), 0) FROM enrollments
+         WHERE status_code = 'ACTIVE' AND is_active = 1
+               AND COALESCE(commission_status, 'PENDING') != 'CHARGEBACK'",
+        [],
+        |row| row.get(0),
+    )?;
+    let paid_commission_this_month: f64 = conn.query_row(
+        "SELECT COALESCE(SUM(commission_amount), 0) FROM enrollments
+         WHERE commission_status = 'PAID' AND commission_paid_date >= date('now', 'start of month')
+               AND is_active = 1",
+        [],
+        |row| row.get(0),
     )?;
 
+    // Call outcomes and talk time over a configurable window
+    let call_stats_window_days: i64 = conn
+        .query_row(
+            "SELECT CAST(value AS INTEGER) FROM app_settings WHERE key = 'call_stats_window_days'",
+            [],
+            |row| row.get::<_, Option<i64>>(0),
+        )
+        .optional()?
+        .flatten()
+        .filter(|d| *d > 0)
+        .unwrap_or(30);
+    let call_window = "entry_type = 'CALL' AND is_active = 1
+                       AND occurred_at >= datetime('now', '-' || ?1 || ' days')";
+    let mut stmt = conn.prepare(&format!(
+        "SELECT COALESCE(call_outcome, 'Unknown'), COUNT(*) FROM conversation_entries
+         WHERE {call_window}
+         GROUP BY call_outcome ORDER BY COUNT(*) DESC"
+    ))?;
+    let call_stats = stmt
+        .query_map([call_stats_window_days], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<(String, i64)>, _>>()?;
+    let total_call_minutes: i64 = conn.query_row(
+        &format!(
+            "SELECT CAST(ROUND(COALESCE(SUM(call_duration), 0) / 60.0) AS INTEGER)
+             FROM conversation_entries WHERE {call_window}"
+        ),
+        [call_stats_window_days],
+        |row| row.get(0),
+    )?;
+
+    // By plan type
+    let by_plan_type = query_pairs(conn, BY_PLAN_TYPE_SQL)?;
+
     // By carrier (name, actual active count, expected active count)
     let by_carrier = query_carrier_breakdown(conn)?;
 
     // By state
-    let by_state = query_pairs(conn,
-        "SELECT COALESCE(cl.state, 'Unknown'), COUNT(*) FROM clients cl WHERE cl.is_active = 1 AND cl.state IS NOT NULL GROUP BY cl.state ORDER BY COUNT(*) DESC LIMIT 15"
-    )?;
+    let by_state = query_pairs(conn, BY_STATE_SQL)?;
 
     // Monthly trend (last 12 months)
     let monthly_trend = get_monthly_trend(conn)?;
@@ -52,13 +128,62 @@ pub fn get_dashboard_stats(conn: &Connection) -> Result<DashboardStats, AppError
         new_this_month,
         lost_this_month,
         pending_enrollments: pending,
+        overdue_follow_ups,
+        follow_ups_next_7_days,
+        projected_commission_this_month,
+        paid_commission_this_month,
+        call_stats,
+        total_call_minutes,
+        call_stats_window_days,
         by_plan_type,
         by_carrier,
         by_state,
         monthly_trend,
+        active_enrollment_periods: Vec::new(),
     })
 }
 
+/// Enrollment periods with a fixed yearly window (IEP, SEP and the like are
+/// per-beneficiary and have no dates)
+pub fn get_fixed_enrollment_periods(
+    conn: &Connection,
+) -> Result<Vec<EnrollmentPeriodWindow>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT code, name, start_month, start_day, end_month, end_day FROM enrollment_periods
+         WHERE start_month IS NOT NULL AND start_day IS NOT NULL
+               AND end_month IS NOT NULL AND end_day IS NOT NULL
+         ORDER BY code",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(EnrollmentPeriodWindow {
+                code: row.get(0)?,
+                name: row.get(1)?,
+                start_month: row.get(2)?,
+                start_day: row.get(3)?,
+                end_month: row.get(4)?,
+                end_day: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// One of the dashboard's breakdowns, by report `group_by` value ("carrier",
+/// "plan_type" or "state"). `None` for any other grouping.
+pub fn get_breakdown(conn: &Connection, group_by: &str) -> Result<Option<Vec<(String, i64)>>, AppError> {
+    let pairs = match group_by {
+        "carrier" => query_carrier_breakdown(conn)?
+            .into_iter()
+            .map(|(name, active, _)| (name, active))
+            .collect(),
+        "plan_type" => query_pairs(conn, BY_PLAN_TYPE_SQL)?,
+        "state" => query_pairs(conn, BY_STATE_SQL)?,
+        _ => return Ok(None),
+    };
+    Ok(Some(pairs))
+}
+
 fn query_pairs(conn: &Connection, sql: &str) -> Result<Vec<(String, i64)>, AppError> {
     let mut stmt = conn.prepare(sql)?;
     let rows = stmt.query_map([], |row| {
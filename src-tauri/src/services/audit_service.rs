@@ -0,0 +1,91 @@
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::AppError;
+use crate::models::AuditEntry;
+use crate::repositories::audit_repo;
+
+pub const CLIENT: &str = "client";
+pub const ENROLLMENT: &str = "enrollment";
+pub const CONVERSATION: &str = "conversation";
+pub const CONVERSATION_ENTRY: &str = "conversation_entry";
+
+/// Bookkeeping columns that change on every write and say nothing useful
+const IGNORED_FIELDS: &[&str] = &["created_at", "updated_at"];
+
+/// Run a mutation and its audit entries as one unit: in a new transaction,
+/// or as part of the caller's when one is already open (SQLite transactions
+/// don't nest).
+pub fn in_transaction<T>(
+    conn: &Connection,
+    f: impl FnOnce(&Connection) -> Result<T, AppError>,
+) -> Result<T, AppError> {
+    if !conn.is_autocommit() {
+        return f(conn);
+    }
+    let tx = conn.unchecked_transaction()?;
+    let result = f(&tx)?;
+    tx.commit()?;
+    Ok(result)
+}
+
+pub fn get_audit_log(conn: &Connection, entity_type: &str, entity_id: Option<&str>) -> Result<Vec<AuditEntry>, AppError> {
+    audit_repo::get_audit_log(conn, entity_type, entity_id)
+}
+
+/// Record a mutation with free-form `changes`
+pub fn record(
+    conn: &Connection,
+    entity_type: &str,
+    entity_id: &str,
+    action: &str,
+    changes: Option<Value>,
+) -> Result<(), AppError> {
+    let changes = changes.map(|c| c.to_string());
+    audit_repo::insert_audit_entry(conn, entity_type, entity_id, action, changes.as_deref())
+}
+
+/// Record a new record, keeping its non-empty fields
+pub fn record_create<T: Serialize>(conn: &Connection, entity_type: &str, entity_id: &str, after: &T) -> Result<(), AppError> {
+    let fields: Map<String, Value> = to_fields(after)
+        .into_iter()
+        .filter(|(_, v)| !v.is_null())
+        .collect();
+    record(conn, entity_type, entity_id, "CREATE", Some(Value::Object(fields)))
+}
+
+/// Record an update as `{"field": {"old": .., "new": ..}}` for each field
+/// that differs. Nothing is written when nothing changed.
+pub fn record_update<T: Serialize>(
+    conn: &Connection,
+    entity_type: &str,
+    entity_id: &str,
+    before: &T,
+    after: &T,
+) -> Result<(), AppError> {
+    let before = to_fields(before);
+    let changed: Map<String, Value> = to_fields(after)
+        .into_iter()
+        .filter_map(|(k, new)| {
+            let old = before.get(&k).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| (k, serde_json::json!({ "old": old, "new": new })))
+        })
+        .collect();
+    if changed.is_empty() {
+        return Ok(());
+    }
+    record(conn, entity_type, entity_id, "UPDATE", Some(Value::Object(changed)))
+}
+
+fn to_fields<T: Serialize>(value: &T) -> Map<String, Value> {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(mut fields)) => {
+            for f in IGNORED_FIELDS {
+                fields.remove(*f);
+            }
+            fields
+        }
+        _ => Map::new(),
+    }
+}
@@ -4,6 +4,7 @@ use argon2::{Algorithm, Argon2, Params, Version};
 use rand::rngs::OsRng;
 use rand::RngCore;
 use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
 
 use crate::db::{migrations, seed};
 use crate::error::AppError;
@@ -14,6 +15,26 @@ const ARGON2_P_COST: u32 = 4;
 const KEY_LENGTH: usize = 32;
 const SALT_FILE: &str = "compass.salt";
 const DB_FILE: &str = "compass.db";
+const LOCKOUT_FILE: &str = "compass.lockout";
+const LOCKOUT_THRESHOLD: u32 = 5;
+const LOCKOUT_MAX_MINUTES: i64 = 60;
+/// Read-only connections opened next to the writer for `with_read_conn`
+const READ_POOL_SIZE: usize = 3;
+
+/// The writer connection plus its read-only pool, all keyed with the same key
+pub struct UnlockedDb {
+    pub conn: Connection,
+    pub readers: Vec<Connection>,
+}
+
+/// Failed-login bookkeeping. Kept in a plaintext sidecar file because the
+/// database can't be read until the password is right.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LoginLockout {
+    failed_login_count: u32,
+    /// Unix timestamp (seconds) before which no attempt is accepted
+    locked_until: Option<i64>,
+}
 
 /// Check if this is a first run (no salt file exists)
 pub fn is_first_run(app_data_dir: &Path) -> bool {
@@ -21,7 +42,7 @@ pub fn is_first_run(app_data_dir: &Path) -> bool {
 }
 
 /// Create a new account: generate salt, derive key, create encrypted DB
-pub fn create_database(app_data_dir: &Path, password: &str) -> Result<Connection, AppError> {
+pub fn create_database(app_data_dir: &Path, password: &str) -> Result<UnlockedDb, AppError> {
     if !is_first_run(app_data_dir) {
         return Err(AppError::Auth(
             "Database already exists. Use login instead.".to_string(),
@@ -47,11 +68,50 @@ pub fn create_database(app_data_dir: &Path, password: &str) -> Result<Connection
     seed::seed_data(&conn)?;
 
     tracing::info!("New encrypted database created successfully");
-    Ok(conn)
+    Ok(UnlockedDb {
+        conn,
+        readers: open_read_connections(app_data_dir, &key),
+    })
 }
 
 /// Unlock existing database with password
-pub fn unlock_database(app_data_dir: &Path, password: &str) -> Result<Connection, AppError> {
+pub fn unlock_database(app_data_dir: &Path, password: &str) -> Result<UnlockedDb, AppError> {
+    let (conn, key) = open_with_lockout(app_data_dir, password)?;
+
+    // Run any pending migrations (for upgrades)
+    migrations::run_migrations(&conn)?;
+
+    // Re-run seed data (INSERT OR IGNORE) so new carriers/statuses are added
+    seed::seed_data(&conn)?;
+
+    tracing::info!("Database unlocked successfully");
+    Ok(UnlockedDb {
+        conn,
+        readers: open_read_connections(app_data_dir, &key),
+    })
+}
+
+/// Check a password against the database on disk without touching the open
+/// session, by deriving its key and opening a throwaway connection with it.
+/// Wrong guesses count toward the same lockout as unlocking.
+pub fn verify_password(app_data_dir: &Path, password: &str) -> Result<(), AppError> {
+    open_with_lockout(app_data_dir, password).map(drop)
+}
+
+/// Open the database with a password, refusing while locked out and counting
+/// failed attempts toward the next lockout. Returns the connection and key.
+fn open_with_lockout(app_data_dir: &Path, password: &str) -> Result<(Connection, Vec<u8>), AppError> {
+    let mut lockout = read_lockout(app_data_dir);
+    let now = chrono::Utc::now().timestamp();
+    if let Some(until) = lockout.locked_until.filter(|until| *until > now) {
+        let minutes = (until - now + 59) / 60;
+        return Err(AppError::Auth(format!(
+            "locked, try again in {} minute{}",
+            minutes,
+            if minutes == 1 { "" } else { "s" }
+        )));
+    }
+
     // Read salt
     let salt = std::fs::read(salt_path(app_data_dir))
         .map_err(|e| AppError::Auth(format!("Failed to read salt file: {}", e)))?;
@@ -60,24 +120,36 @@ pub fn unlock_database(app_data_dir: &Path, password: &str) -> Result<Connection
     let key = derive_key(password, &salt)?;
 
     // Try to open DB - if password is wrong, open_encrypted_db returns "Invalid password"
-    let conn = open_encrypted_db(app_data_dir, &key)?;
-
-    // Run any pending migrations (for upgrades)
-    migrations::run_migrations(&conn)?;
-
-    // Re-run seed data (INSERT OR IGNORE) so new carriers/statuses are added
-    seed::seed_data(&conn)?;
+    let conn = match open_encrypted_db(app_data_dir, &key) {
+        Ok(conn) => conn,
+        Err(AppError::Auth(msg)) => {
+            lockout.failed_login_count += 1;
+            if lockout.failed_login_count >= LOCKOUT_THRESHOLD {
+                // 1, 2, 4, ... minutes for each failure past the threshold
+                let exponent = (lockout.failed_login_count - LOCKOUT_THRESHOLD).min(6);
+                let minutes = (1i64 << exponent).min(LOCKOUT_MAX_MINUTES);
+                lockout.locked_until = Some(now + minutes * 60);
+            }
+            write_lockout(app_data_dir, &lockout);
+            return Err(AppError::Auth(msg));
+        }
+        Err(e) => return Err(e),
+    };
+
+    if lockout.failed_login_count > 0 || lockout.locked_until.is_some() {
+        write_lockout(app_data_dir, &LoginLockout::default());
+    }
 
-    tracing::info!("Database unlocked successfully");
-    Ok(conn)
+    Ok((conn, key))
 }
 
-/// Change the database password
+/// Change the database password. Returns a fresh read-only pool keyed with
+/// the new key, since the existing readers still hold the old one.
 pub fn change_password(
     conn: &Connection,
     app_data_dir: &Path,
     new_password: &str,
-) -> Result<(), AppError> {
+) -> Result<Vec<Connection>, AppError> {
     // Generate new salt
     let mut new_salt = [0u8; 32];
     OsRng.fill_bytes(&mut new_salt);
@@ -95,7 +167,7 @@ pub fn change_password(
         .map_err(|e| AppError::Io(format!("Failed to write new salt file: {}", e)))?;
 
     tracing::info!("Database password changed successfully");
-    Ok(())
+    Ok(open_read_connections(app_data_dir, &new_key))
 }
 
 /// Derive a 32-byte key from password and salt using Argon2id
@@ -135,6 +207,45 @@ fn open_encrypted_db(app_data_dir: &Path, key: &[u8]) -> Result<Connection, AppE
     Ok(conn)
 }
 
+/// Open the read-only pool. Best effort: a reader that fails to open just
+/// means more reads fall back to the writer.
+fn open_read_connections(app_data_dir: &Path, key: &[u8]) -> Vec<Connection> {
+    (0..READ_POOL_SIZE)
+        .filter_map(|_| {
+            let conn = open_encrypted_db(app_data_dir, key).and_then(|conn| {
+                conn.execute_batch("PRAGMA query_only=ON;")?;
+                Ok(conn)
+            });
+            match conn {
+                Ok(conn) => Some(conn),
+                Err(e) => {
+                    tracing::warn!("Failed to open read connection: {}", e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// A missing or unreadable lockout file counts as no failures
+fn read_lockout(app_data_dir: &Path) -> LoginLockout {
+    std::fs::read(app_data_dir.join(LOCKOUT_FILE))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn write_lockout(app_data_dir: &Path, lockout: &LoginLockout) {
+    let result = serde_json::to_vec(lockout)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| {
+            std::fs::write(app_data_dir.join(LOCKOUT_FILE), bytes).map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        tracing::warn!("Failed to write login lockout file: {}", e);
+    }
+}
+
 fn salt_path(app_data_dir: &Path) -> PathBuf {
     app_data_dir.join(SALT_FILE)
 }
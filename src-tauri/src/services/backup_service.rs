@@ -0,0 +1,277 @@
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::error::AppError;
+
+const AUTO_BACKUP_PREFIX: &str = "compass_auto_";
+const DEFAULT_AUTO_BACKUP_KEEP: usize = 7;
+
+/// Export, verify, and record a backup at `destination`.
+///
+/// This is the whole of a manual backup; automatic backups go through it too
+/// so both paths leave the same trail in `last_backup_at`.
+pub fn create_backup(conn: &Connection, destination: &Path) -> Result<(), AppError> {
+    if let Err(e) = backup_to(conn, destination) {
+        let _ = std::fs::remove_file(destination);
+        return Err(AppError::Io(format!("Backup failed: {}", e)));
+    }
+    verify_backup(conn, destination)?;
+    // Only record the backup once it has been read back
+    record_backup(conn)
+}
+
+/// Back up to `auto_backup_dir` if the last backup is older than
+/// `auto_backup_interval_days`, then prune old automatic backups down to
+/// `auto_backup_keep` (default 7).
+///
+/// Returns the new file's path, or `None` when auto backups are not
+/// configured or not yet due. Only files this function wrote are rotated;
+/// manual backups saved into the same directory are left alone.
+pub fn run_auto_backup(conn: &Connection) -> Result<Option<PathBuf>, AppError> {
+    let interval_days = match get_setting(conn, "auto_backup_interval_days")?
+        .and_then(|v| v.trim().parse::<i64>().ok())
+    {
+        Some(days) if days > 0 => days,
+        _ => return Ok(None),
+    };
+    let dir = match get_setting(conn, "auto_backup_dir")? {
+        Some(dir) if !dir.trim().is_empty() => PathBuf::from(dir.trim()),
+        _ => return Ok(None),
+    };
+    let keep = get_setting(conn, "auto_backup_keep")?
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_AUTO_BACKUP_KEEP);
+
+    let due: bool = conn.query_row(
+        "SELECT NOT EXISTS (
+            SELECT 1 FROM app_settings
+            WHERE key = 'last_backup_at'
+              AND julianday('now') - julianday(value) < ?1
+        )",
+        [interval_days],
+        |row| row.get(0),
+    )?;
+    if !due {
+        return Ok(None);
+    }
+
+    std::fs::create_dir_all(&dir)?;
+    let destination = dir.join(format!(
+        "{}{}.db",
+        AUTO_BACKUP_PREFIX,
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    create_backup(conn, &destination)?;
+    rotate_auto_backups(&dir, keep)?;
+
+    tracing::info!("Automatic backup written to {}", destination.display());
+    Ok(Some(destination))
+}
+
+/// Delete all but the newest `keep` automatic backups in `dir`. The
+/// timestamped names sort chronologically, so name order is age order.
+fn rotate_auto_backups(dir: &Path, keep: usize) -> Result<(), AppError> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with(AUTO_BACKUP_PREFIX) && n.ends_with(".db"))
+                .unwrap_or(false)
+        })
+        .collect();
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(keep);
+    for old in &backups[..excess] {
+        if let Err(e) = std::fs::remove_file(old) {
+            tracing::warn!("Could not remove old backup {}: {}", old.display(), e);
+        }
+    }
+    Ok(())
+}
+
+fn get_setting(conn: &Connection, key: &str) -> Result<Option<String>, AppError> {
+    Ok(conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            [key],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()?
+        .flatten())
+}
+
+/// Write a consistent, encrypted copy of the open database to `destination`.
+///
+/// Uses SQLCipher's `sqlcipher_export` into an attached database rather than
+/// copying the file, so the snapshot is taken inside a read transaction and
+/// includes anything still sitting in the WAL. An attached database with no
+/// KEY clause inherits the main database's key, so the backup opens with the
+/// same password.
+pub fn backup_to(conn: &Connection, destination: &Path) -> Result<(), AppError> {
+    // sqlcipher_export needs an empty target
+    if destination.exists() {
+        std::fs::remove_file(destination)?;
+    }
+    let dest_str = destination.to_string_lossy().to_string();
+
+    conn.execute("ATTACH DATABASE ?1 AS backup", [&dest_str])?;
+    let exported = (|| -> Result<(), AppError> {
+        conn.query_row("SELECT sqlcipher_export('backup')", [], |_| Ok(()))?;
+        // sqlcipher_export copies schema and data but not the schema version
+        // that drives migrations
+        let user_version: i64 = conn.query_row("PRAGMA main.user_version", [], |row| row.get(0))?;
+        conn.execute_batch(&format!("PRAGMA backup.user_version = {};", user_version))?;
+        Ok(())
+    })();
+    let detached = conn.execute_batch("DETACH DATABASE backup;");
+
+    exported?;
+    detached?;
+    Ok(())
+}
+
+/// Confirm a finished backup decrypts with the current key and can be read.
+///
+/// On failure the file is removed so a truncated or unreadable backup is
+/// never left looking like a good one.
+pub fn verify_backup(conn: &Connection, destination: &Path) -> Result<(), AppError> {
+    let dest_str = destination.to_string_lossy().to_string();
+    let checked = (|| -> Result<(), AppError> {
+        conn.execute("ATTACH DATABASE ?1 AS verify", [&dest_str])?;
+        let counted = conn.query_row("SELECT count(*) FROM verify.clients", [], |row| {
+            row.get::<_, i64>(0)
+        });
+        conn.execute_batch("DETACH DATABASE verify;")?;
+        counted?;
+        Ok(())
+    })();
+
+    if let Err(e) = checked {
+        let _ = std::fs::remove_file(destination);
+        return Err(AppError::Io(format!(
+            "Backup at {} could not be read back and was removed: {}",
+            destination.display(),
+            e
+        )));
+    }
+    Ok(())
+}
+
+/// Replaces every field that identifies a client (or the agent) in the
+/// `anonymized` copy with a deterministic fake derived from the rowid, so
+/// row counts, keys and enrollment structure are untouched. DOBs keep their
+/// year so age-based reports still behave; zips keep their first three
+/// digits. Raw portal snapshots, the sync review queue and saved portal
+/// credentials are dropped, as are the before/after values in the audit log.
+const ANONYMIZE_SQL: &str = "
+    UPDATE anonymized.clients SET
+        first_name = 'Client',
+        last_name = printf('Test%05d', rowid),
+        middle_name = CASE WHEN middle_name IS NULL THEN NULL ELSE 'M' END,
+        dob = CASE WHEN dob IS NULL THEN NULL ELSE substr(dob, 1, 4) || printf('-%02d-15', rowid % 12 + 1) END,
+        phone = CASE WHEN phone IS NULL THEN NULL ELSE printf('555%07d', rowid % 10000000) END,
+        phone2 = CASE WHEN phone2 IS NULL THEN NULL ELSE printf('555%07d', (rowid + 5000000) % 10000000) END,
+        email = CASE WHEN email IS NULL THEN NULL ELSE printf('client%d@example.com', rowid) END,
+        address_line1 = CASE WHEN address_line1 IS NULL THEN NULL ELSE printf('%d Main St', rowid) END,
+        address_line2 = CASE WHEN address_line2 IS NULL THEN NULL ELSE 'Apt 1' END,
+        city = CASE WHEN city IS NULL THEN NULL ELSE 'Anytown' END,
+        zip = CASE WHEN zip IS NULL THEN NULL ELSE substr(zip, 1, 3) || '00' END,
+        mbi = CASE WHEN mbi IS NULL THEN NULL ELSE
+            printf('%dA%sA%sAA%s', 1 + (rowid / 1000000) % 9,
+                   substr(printf('%06d', rowid % 1000000), 1, 2),
+                   substr(printf('%06d', rowid % 1000000), 3, 2),
+                   substr(printf('%06d', rowid % 1000000), 5, 2)) END,
+        medicaid_id = CASE WHEN medicaid_id IS NULL THEN NULL ELSE printf('MCD%08d', rowid) END,
+        member_record_locator = CASE WHEN member_record_locator IS NULL THEN NULL ELSE printf('MRL%08d', rowid) END,
+        notes = CASE WHEN notes IS NULL THEN NULL ELSE '[redacted]' END;
+
+    UPDATE anonymized.enrollments SET
+        confirmation_number = CASE WHEN confirmation_number IS NULL THEN NULL ELSE printf('CONF%06d', rowid) END;
+
+    UPDATE anonymized.conversations SET title = printf('Conversation %d', rowid);
+
+    UPDATE anonymized.conversation_entries SET
+        subject = CASE WHEN subject IS NULL THEN NULL ELSE printf('Subject %d', rowid) END,
+        body = CASE WHEN body IS NULL THEN NULL ELSE '[redacted]' END,
+        follow_up_note = CASE WHEN follow_up_note IS NULL THEN NULL ELSE '[redacted]' END,
+        call_phone_number = CASE WHEN call_phone_number IS NULL THEN NULL ELSE printf('555%07d', rowid % 10000000) END,
+        meeting_location = CASE WHEN meeting_location IS NULL THEN NULL ELSE '[redacted]' END,
+        email_to = CASE WHEN email_to IS NULL THEN NULL ELSE 'client@example.com' END,
+        email_from = CASE WHEN email_from IS NULL THEN NULL ELSE 'agent@example.com' END,
+        system_event_data = CASE WHEN system_event_data IS NULL THEN NULL ELSE '{}' END;
+
+    UPDATE anonymized.client_providers SET
+        first_name = 'Provider',
+        last_name = printf('Test%05d', rowid),
+        npi = CASE WHEN npi IS NULL THEN NULL ELSE printf('1%09d', rowid) END,
+        phone = CASE WHEN phone IS NULL THEN NULL ELSE printf('555%07d', rowid % 10000000) END;
+
+    UPDATE anonymized.commission_entries SET
+        member_name = CASE WHEN member_name IS NULL THEN NULL ELSE printf('Member %d', rowid) END,
+        member_id = CASE WHEN member_id IS NULL THEN NULL ELSE printf('M%08d', rowid) END,
+        raw_data = NULL;
+
+    UPDATE anonymized.agent_profile SET
+        first_name = 'Agent', last_name = 'Test', email = 'agent@example.com',
+        phone = '5550000000', npn = '0000000';
+
+    UPDATE anonymized.import_logs SET
+        filename = printf('import_%d', rowid),
+        error_details = NULL;
+
+    UPDATE anonymized.audit_log SET changes = NULL;
+
+    DELETE FROM anonymized.carrier_sync_snapshots;
+    DELETE FROM anonymized.sync_review_items;
+    DELETE FROM anonymized.app_settings WHERE key LIKE 'portal_creds_%';
+    INSERT INTO anonymized.clients_fts(clients_fts) VALUES ('rebuild');
+";
+
+/// Write a plaintext, de-identified copy of the database to `destination`
+/// for maintainers to reproduce bugs on realistically shaped data.
+///
+/// The live database is exported into an unkeyed in-memory database, the
+/// copy is scrubbed there, and only then written out with `VACUUM INTO`, so
+/// real PHI never reaches the destination file. (`VACUUM INTO` straight from
+/// the main database would carry over its key and leave the copy unreadable
+/// without the agent's password.) `updated_at` triggers fire on the copy, so
+/// those timestamps show the export time.
+pub fn export_anonymized_copy(conn: &Connection, destination: &Path) -> Result<(), AppError> {
+    // VACUUM INTO needs an empty target
+    if destination.exists() {
+        std::fs::remove_file(destination)?;
+    }
+    let dest_str = destination.to_string_lossy().to_string();
+
+    conn.execute_batch("ATTACH DATABASE ':memory:' AS anonymized KEY '';")?;
+    let exported = (|| -> Result<(), AppError> {
+        conn.query_row("SELECT sqlcipher_export('anonymized')", [], |_| Ok(()))?;
+        conn.execute_batch(ANONYMIZE_SQL)?;
+        let user_version: i64 = conn.query_row("PRAGMA main.user_version", [], |row| row.get(0))?;
+        conn.execute_batch(&format!("PRAGMA anonymized.user_version = {};", user_version))?;
+        conn.execute("VACUUM anonymized INTO ?1", [&dest_str])?;
+        Ok(())
+    })();
+    let detached = conn.execute_batch("DETACH DATABASE anonymized;");
+
+    if let Err(e) = exported {
+        let _ = std::fs::remove_file(destination);
+        return Err(AppError::Io(format!("Anonymized export failed: {}", e)));
+    }
+    detached?;
+    tracing::info!("Anonymized copy written to {}", destination.display());
+    Ok(())
+}
+
+/// Record that a backup just completed
+pub fn record_backup(conn: &Connection) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO app_settings (key, value) VALUES ('last_backup_at', datetime('now')) ON CONFLICT(key) DO UPDATE SET value = datetime('now'), updated_at = datetime('now')",
+        [],
+    )?;
+    Ok(())
+}
@@ -1,13 +1,17 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use uuid::Uuid;
 
+use crate::carrier_sync::FetchParams;
 use crate::error::AppError;
 use crate::models::{
     ConfirmDisenrollmentResult, CreateClientInput, CreateEnrollmentInput, ImportPortalResult,
-    PortalMember, SyncDisenrollment, SyncLogEntry, SyncMatch, SyncResult,
+    PortalFetchSummary, PortalMember, SyncAorLoss, SyncDisenrollment, SyncLogEntry, SyncMatch, SyncPossibleMatch, SyncResult, SyncReviewAction, SyncReviewItem,
+    SyncSnapshot, SyncTermination,
 };
 use crate::models::CreateProviderInput;
-use crate::services::{client_service, conversation_service, enrollment_service, matching, provider_service};
+use crate::repositories::enrollment_repo;
+use crate::services::{audit_service, client_service, conversation_service, enrollment_service, matching, plan_service, provider_service};
+use crate::services::plan_service::ImportedPlan;
 
 /// Internal struct for matching local enrollments against portal data.
 struct LocalEnrollment {
@@ -20,6 +24,66 @@ struct LocalEnrollment {
     plan_name: Option<String>,
 }
 
+/// Page size and page limit overrides for a carrier's fetch script. Missing,
+/// zero or unparseable settings leave the script's defaults in place.
+pub fn fetch_params(conn: &Connection, carrier_id: &str) -> Result<FetchParams, AppError> {
+    let setting = |name: &str| -> Result<Option<u32>, AppError> {
+        let value: Option<String> = conn
+            .query_row(
+                "SELECT value FROM app_settings WHERE key = ?1",
+                [format!("{}_{}", name, carrier_id)],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value.and_then(|v| v.trim().parse::<u32>().ok()).filter(|&n| n > 0))
+    };
+    Ok(FetchParams {
+        page_size: setting("carrier_sync_page_size")?,
+        max_pages: setting("carrier_sync_max_pages")?,
+    })
+}
+
+/// Members included in `PortalFetchSummary::sample`
+const PORTAL_SAMPLE_SIZE: usize = 10;
+
+/// Decode the member list the portal script posted.
+pub fn decode_portal_members(members_json: &str) -> Result<Vec<PortalMember>, AppError> {
+    serde_json::from_str(members_json)
+        .map_err(|e| AppError::CarrierSync(format!("Failed to parse member data: {}", e)))
+}
+
+/// First step of a sync: parse the fetched members and report how many came
+/// back, without comparing anything yet.
+pub fn parse_portal_members(
+    conn: &Connection,
+    carrier_id: &str,
+    carrier_name: &str,
+    members_json: &str,
+) -> Result<PortalFetchSummary, AppError> {
+    let members = decode_portal_members(members_json)?;
+    Ok(PortalFetchSummary {
+        carrier_name: carrier_name.to_string(),
+        member_count: members.len(),
+        local_count: get_local_enrollments(conn, carrier_id)?.len(),
+        sample: members.into_iter().take(PORTAL_SAMPLE_SIZE).collect(),
+    })
+}
+
+/// `run_sync` inside a transaction that is rolled back: the result shows
+/// what the sync would flag, but nothing is written.
+pub fn preview_sync(
+    conn: &Connection,
+    carrier_id: &str,
+    carrier_name: &str,
+    portal_members: &[PortalMember],
+    empty_confirmed: bool,
+) -> Result<SyncResult, AppError> {
+    let tx = conn.unchecked_transaction()?;
+    let result = run_sync(&tx, carrier_id, carrier_name, portal_members, empty_confirmed);
+    tx.rollback()?;
+    result
+}
+
 /// Compare portal members against local enrollments for a given carrier,
 /// auto-update disenrolled records, and return a summary.
 pub fn run_sync(
@@ -27,19 +91,50 @@ pub fn run_sync(
     carrier_id: &str,
     carrier_name: &str,
     portal_members: &[PortalMember],
+    empty_confirmed: bool,
 ) -> Result<SyncResult, AppError> {
     // 1. Fetch local active enrollments for this carrier
     let local = get_local_enrollments(conn, carrier_id)?;
     let local_count = local.len();
     let portal_count = portal_members.len();
 
+    // An empty portal list the script couldn't vouch for is most likely a
+    // scrape that found nothing to parse. Comparing it would flag the whole
+    // book for disenrollment, so bail out instead.
+    if portal_count == 0 && local_count > 0 && !empty_confirmed {
+        return Err(AppError::CarrierSync(format!(
+            "{} returned no members, but the portal did not confirm an empty book of business. \
+             The page may not have loaded fully; no enrollments were flagged. Reload the portal and sync again.",
+            carrier_name
+        )));
+    }
+
     // 2. Match portal members to local enrollments
     let mut matched_enrollment_ids: Vec<String> = Vec::new();
     let mut matched_members: Vec<SyncMatch> = Vec::new();
     let mut new_in_portal: Vec<PortalMember> = Vec::new();
+    let mut termed_matches: Vec<(&LocalEnrollment, &'static str, String)> = Vec::new();
+    let mut aor_lost: Vec<SyncAorLoss> = Vec::new();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
 
     for pm in portal_members {
         if let Some((local_match, tier)) = find_match(&local, pm) {
+            if let Some(loss) = record_aor_status(conn, &local_match.enrollment_id, pm)? {
+                aor_lost.push(SyncAorLoss {
+                    client_name: format!("{} {}", local_match.client_first_name, local_match.client_last_name),
+                    client_id: local_match.client_id.clone(),
+                    enrollment_id: local_match.enrollment_id.clone(),
+                    carrier_name: Some(carrier_name.to_string()),
+                    plan_name: local_match.plan_name.clone(),
+                    aor_status: loss,
+                });
+            }
+            // A termed portal record still accounts for the local enrollment,
+            // but is applied after the loop in case an active record also matches
+            if let Some((code, date)) = portal_termination(pm, &today) {
+                termed_matches.push((local_match, code, date));
+                continue;
+            }
             matched_enrollment_ids.push(local_match.enrollment_id.clone());
             matched_members.push(SyncMatch {
                 client_name: format!("{} {}", local_match.client_first_name, local_match.client_last_name),
@@ -67,11 +162,78 @@ pub fn run_sync(
         }
     }
 
-    // 3. Local enrollments NOT matched in portal → candidates for disenrollment
+    // 2b. Last resort: pair leftover portal members with leftover local
+    //     enrollments by name similarity, for the agent to confirm
+    //     (pairs the agent already decided on in the review queue are
+    //     applied instead of asked again)
+    let mut possible_matches: Vec<SyncPossibleMatch> = Vec::new();
+    if let Some(threshold) = fuzzy_threshold(conn)? {
+        let claimed: Vec<&str> = termed_matches
+            .iter()
+            .map(|(le, _, _)| le.enrollment_id.as_str())
+            .chain(matched_enrollment_ids.iter().map(String::as_str))
+            .collect();
+        let (possible, unmatched) = find_possible_matches(&local, &claimed, new_in_portal, threshold);
+        new_in_portal = unmatched;
+        for pm in possible {
+            match prior_pair_decision(conn, carrier_id, &pm)?.as_deref() {
+                Some("MATCHED") => {
+                    if let Some(loss) = record_aor_status(conn, &pm.enrollment_id, &pm.portal_member)? {
+                        aor_lost.push(SyncAorLoss {
+                            client_name: pm.client_name.clone(),
+                            client_id: pm.client_id.clone(),
+                            enrollment_id: pm.enrollment_id.clone(),
+                            carrier_name: Some(carrier_name.to_string()),
+                            plan_name: pm.plan_name.clone(),
+                            aor_status: loss,
+                        });
+                    }
+                    matched_enrollment_ids.push(pm.enrollment_id.clone());
+                    matched_members.push(SyncMatch {
+                        client_name: pm.client_name,
+                        client_id: pm.client_id,
+                        portal_member: pm.portal_member,
+                        match_tier: "confirmed_similar".to_string(),
+                    });
+                }
+                Some("IGNORED") => new_in_portal.push(pm.portal_member),
+                _ => possible_matches.push(pm),
+            }
+        }
+    }
+
+    // 3. Matched enrollments the portal shows as termed (and no active record
+    //    vouches for) → apply the carrier's termination now
+    let mut terminated_in_portal: Vec<SyncTermination> = Vec::new();
+    for (le, code, date) in termed_matches {
+        if matched_enrollment_ids.contains(&le.enrollment_id) {
+            continue;
+        }
+        mark_disenrolled(
+            conn,
+            &le.enrollment_id,
+            code,
+            Some(&date),
+            "Carrier portal sync - terminated in portal",
+            "Terminated in carrier portal",
+        )?;
+        matched_enrollment_ids.push(le.enrollment_id.clone());
+        terminated_in_portal.push(SyncTermination {
+            client_name: format!("{} {}", le.client_first_name, le.client_last_name),
+            client_id: le.client_id.clone(),
+            enrollment_id: le.enrollment_id.clone(),
+            plan_name: le.plan_name.clone(),
+            status_code: code.to_string(),
+            termination_date: date,
+        });
+    }
+
+    // 4. Local enrollments NOT matched in portal → candidates for disenrollment
     //    (reported to the user for confirmation, NOT auto-disenrolled)
     let mut disenrolled: Vec<SyncDisenrollment> = Vec::new();
     for le in &local {
-        if !matched_enrollment_ids.contains(&le.enrollment_id) {
+        let is_possible_match = possible_matches.iter().any(|p| p.enrollment_id == le.enrollment_id);
+        if !matched_enrollment_ids.contains(&le.enrollment_id) && !is_possible_match {
             disenrolled.push(SyncDisenrollment {
                 client_name: format!("{} {}", le.client_first_name, le.client_last_name),
                 client_id: le.client_id.clone(),
@@ -83,8 +245,18 @@ pub fn run_sync(
 
     let matched = matched_members.len();
 
-    // 4. Log the sync (disenrolled=0 because disenrollment is now user-confirmed)
-    log_sync(conn, carrier_id, portal_count, matched, 0, new_in_portal.len())?;
+    // 5. Log the sync (only portal-side terminations count as disenrolled;
+    //    missing members are user-confirmed)
+    let log_id = log_sync(
+        conn,
+        carrier_id,
+        portal_count,
+        matched,
+        terminated_in_portal.len(),
+        new_in_portal.len(),
+    )?;
+    save_snapshot(conn, &log_id, carrier_id, portal_members)?;
+    queue_for_review(conn, &log_id, carrier_id, &new_in_portal, &mut possible_matches)?;
 
     Ok(SyncResult {
         carrier_name: carrier_name.to_string(),
@@ -93,10 +265,260 @@ pub fn run_sync(
         matched,
         matched_members,
         disenrolled,
+        terminated_in_portal,
         new_in_portal,
+        possible_matches,
+        aor_lost,
     })
 }
 
+/// App setting for name-similarity matching in carrier sync; unset or 0
+/// leaves it off
+const FUZZY_THRESHOLD_SETTING: &str = "carrier_sync_fuzzy_threshold";
+
+/// The configured similarity threshold, if name-similarity matching is on.
+/// Values above 1 are read as percentages ("92" is 0.92).
+fn fuzzy_threshold(conn: &Connection) -> Result<Option<f64>, AppError> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            params![FUZZY_THRESHOLD_SETTING],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let threshold = value
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .map(|t| if t > 1.0 { t / 100.0 } else { t })
+        .filter(|t| *t > 0.0 && *t <= 1.0);
+    Ok(threshold)
+}
+
+fn full_name_key(first: &str, last: &str) -> String {
+    format!("{} {}", matching::normalize_first_name(first), last.trim().to_ascii_lowercase())
+}
+
+/// Pair each portal member with the most similar local enrollment not
+/// already matched, best scores first so each enrollment is offered once.
+/// Returns the pairings and the portal members left without one.
+fn find_possible_matches(
+    locals: &[LocalEnrollment],
+    claimed: &[&str],
+    portal_members: Vec<PortalMember>,
+    threshold: f64,
+) -> (Vec<SyncPossibleMatch>, Vec<PortalMember>) {
+    let open: Vec<&LocalEnrollment> = locals
+        .iter()
+        .filter(|le| !claimed.contains(&le.enrollment_id.as_str()))
+        .collect();
+
+    let mut scored: Vec<(usize, usize, f64)> = Vec::new();
+    for (pi, pm) in portal_members.iter().enumerate() {
+        let portal_key = full_name_key(&pm.first_name, &pm.last_name);
+        for (li, le) in open.iter().enumerate() {
+            let score = matching::jaro_winkler(&portal_key, &full_name_key(&le.client_first_name, &le.client_last_name));
+            if score >= threshold {
+                scored.push((pi, li, score));
+            }
+        }
+    }
+    scored.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut portal_taken = vec![false; portal_members.len()];
+    let mut local_taken = vec![false; open.len()];
+    let mut pairs = Vec::new();
+    for (pi, li, score) in scored {
+        if portal_taken[pi] || local_taken[li] {
+            continue;
+        }
+        portal_taken[pi] = true;
+        local_taken[li] = true;
+        pairs.push((pi, li, score));
+    }
+
+    let mut possible = Vec::new();
+    for (pi, li, score) in pairs {
+        let le = open[li];
+        possible.push(SyncPossibleMatch {
+            client_name: format!("{} {}", le.client_first_name, le.client_last_name),
+            client_id: le.client_id.clone(),
+            enrollment_id: le.enrollment_id.clone(),
+            plan_name: le.plan_name.clone(),
+            portal_member: portal_members[pi].clone(),
+            score,
+            review_item_id: None,
+        });
+    }
+    let unmatched = portal_members
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| !portal_taken[*i])
+        .map(|(_, pm)| pm)
+        .collect();
+    (possible, unmatched)
+}
+
+/// Map a carrier's free-text member status to an `enrollment_statuses` code.
+/// Blank means active (Medical Mutual only reports exceptions); anything
+/// unrecognised is treated as pending rather than guessed at.
+pub fn normalize_portal_status(raw: &str) -> &'static str {
+    let s = raw.trim().to_lowercase().replace(['_', '-'], " ");
+    let has = |needle: &str| s.contains(needle);
+
+    if s.is_empty() {
+        return "ACTIVE";
+    }
+    if has("deceased") || has("death") {
+        "DISENROLLED_DECEASED"
+    } else if has("plan term") {
+        "DISENROLLED_PLAN_TERM"
+    } else if has("other coverage") {
+        "DISENROLLED_OTHER_COV"
+    } else if has("voluntary") && !has("involuntary") {
+        "DISENROLLED_VOLUNTARY"
+    } else if has("reinstat") {
+        "REINSTATED"
+    } else if has("reject") || has("denied") {
+        "REJECTED"
+    } else if has("cancel") || has("withdrawn") || s == "not enrolled" {
+        "CANCELLED"
+    } else if s == "t"
+        || has("term")
+        || has("inactive")
+        || has("disenroll")
+        || has("lapse")
+        || has("involuntary")
+    {
+        "DISENROLLED_INVOLUNTARY"
+    } else if s == "a" || has("active") || s == "enrolled" || has("in force") || s == "inforce" {
+        "ACTIVE"
+    } else {
+        "PENDING"
+    }
+}
+
+/// If the portal shows a member as termed — a terminal status, or an end date
+/// already passed — return the status code and termination date to apply.
+fn portal_termination(member: &PortalMember, today: &str) -> Option<(&'static str, String)> {
+    let code = member_status_code(member);
+    let end_date = member
+        .end_date
+        .as_deref()
+        .filter(|d| d.len() >= 10 && chrono::NaiveDate::parse_from_str(&d[..10], "%Y-%m-%d").is_ok())
+        .map(|d| d[..10].to_string());
+
+    if is_terminal_status(code) {
+        Some((code, end_date.unwrap_or_else(|| today.to_string())))
+    } else {
+        end_date
+            .filter(|d| d.as_str() < today)
+            .map(|d| ("DISENROLLED_INVOLUNTARY", d))
+    }
+}
+
+/// Whether a status code means the enrollment has ended.
+fn is_terminal_status(code: &str) -> bool {
+    !matches!(code, "ACTIVE" | "PENDING" | "REINSTATED")
+}
+
+/// Combined status for a portal member: a termination in either `status` or
+/// `policy_status` wins, then an explicit active in either.
+fn member_status_code(member: &PortalMember) -> &'static str {
+    let status = normalize_portal_status(member.status.as_deref().unwrap_or(""));
+    let policy = member
+        .policy_status
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .map(normalize_portal_status);
+
+    if is_terminal_status(status) {
+        status
+    } else if let Some(code) = policy.filter(|c| is_terminal_status(c)) {
+        code
+    } else if status == "ACTIVE" || policy == Some("ACTIVE") {
+        "ACTIVE"
+    } else {
+        status
+    }
+}
+
+/// Whether an agent-of-record status means the agent is no longer the
+/// agent of record.
+pub fn is_not_aor(aor_status: &str) -> bool {
+    !aor_status.trim().is_empty() && is_terminal_status(normalize_portal_status(aor_status))
+}
+
+/// Whether the portal still shows the member enrolled while their
+/// `aor_status` has ended: the client kept the plan but moved to another
+/// agent of record.
+fn is_lost_aor(member: &PortalMember) -> bool {
+    member.aor_status.as_deref().is_some_and(is_not_aor) && !is_terminal_status(member_status_code(member))
+}
+
+/// Store the member's `aor_status` on the matched enrollment, auditing any
+/// change. A member without one leaves the stored
+/// value alone. Returns the new status when the agent just lost the AOR.
+fn record_aor_status(
+    conn: &Connection,
+    enrollment_id: &str,
+    member: &PortalMember,
+) -> Result<Option<String>, AppError> {
+    let Some(status) = member.aor_status.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    let previous: Option<String> = conn.query_row(
+        "SELECT aor_status FROM enrollments WHERE id = ?1",
+        params![enrollment_id],
+        |row| row.get(0),
+    )?;
+    if previous.as_deref() == Some(status) {
+        return Ok(None);
+    }
+    conn.execute(
+        "UPDATE enrollments SET aor_status = ?1, updated_at = datetime('now') WHERE id = ?2",
+        params![status, enrollment_id],
+    )?;
+    audit_service::record(
+        conn,
+        audit_service::ENROLLMENT,
+        enrollment_id,
+        "UPDATE",
+        Some(serde_json::json!({ "aor_status": { "old": previous, "new": status } })),
+    )?;
+
+    let newly_lost = is_lost_aor(member) && !previous.as_deref().is_some_and(is_not_aor);
+    Ok(newly_lost.then(|| status.to_string()))
+}
+
+/// Active and pending enrollments, across all carriers, whose last synced
+/// `aor_status` says the agent is no longer the agent of record.
+pub fn get_lost_aor_enrollments(conn: &Connection) -> Result<Vec<SyncAorLoss>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT c.first_name || ' ' || c.last_name, e.client_id, e.id, cr.name, e.plan_name, e.aor_status
+         FROM enrollments e
+         JOIN clients c ON e.client_id = c.id
+         LEFT JOIN carriers cr ON e.carrier_id = cr.id
+         WHERE e.is_active = 1
+           AND c.is_active = 1
+           AND e.status_code IN ('ACTIVE', 'PENDING')
+           AND COALESCE(e.aor_status, '') <> ''
+         ORDER BY cr.name, c.last_name, c.first_name",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(SyncAorLoss {
+                client_name: row.get(0)?,
+                client_id: row.get(1)?,
+                enrollment_id: row.get(2)?,
+                carrier_name: row.get(3)?,
+                plan_name: row.get(4)?,
+                aor_status: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    // Status text varies by carrier, so the check runs here rather than in SQL
+    Ok(rows.into_iter().filter(|r| is_not_aor(&r.aor_status)).collect())
+}
+
 /// Fetch all active enrollments for a given carrier, joined with client info.
 fn get_local_enrollments(conn: &Connection, carrier_id: &str) -> Result<Vec<LocalEnrollment>, AppError> {
     let sql = "SELECT e.id, e.client_id, c.first_name, c.last_name, c.mbi, c.dob, e.plan_name
@@ -181,20 +603,68 @@ fn find_match<'a>(locals: &'a [LocalEnrollment], portal: &PortalMember) -> Optio
     None
 }
 
-/// Mark an enrollment as disenrolled (involuntary).
+/// Mark an enrollment as disenrolled (involuntary) and note it on the
+/// client's timeline.
 fn disenroll_enrollment(conn: &Connection, enrollment_id: &str) -> Result<(), AppError> {
+    mark_disenrolled(
+        conn,
+        enrollment_id,
+        "DISENROLLED_INVOLUNTARY",
+        None,
+        "Carrier portal sync - not found in portal",
+        "Not found in carrier portal",
+    )
+}
+
+/// Set an enrollment's terminal status and termination date (today when
+/// not given), and log a CARRIER_SYNC_DISENROLLED event on the client.
+fn mark_disenrolled(
+    conn: &Connection,
+    enrollment_id: &str,
+    status_code: &str,
+    termination_date: Option<&str>,
+    db_reason: &str,
+    event_reason: &str,
+) -> Result<(), AppError> {
     let sql = "UPDATE enrollments
-               SET status_code = 'DISENROLLED_INVOLUNTARY',
-                   disenrollment_reason = 'Carrier portal sync - not found in portal',
-                   termination_date = date('now'),
+               SET status_code = ?2,
+                   disenrollment_reason = ?3,
+                   termination_date = COALESCE(?4, date('now')),
                    updated_at = datetime('now')
                WHERE id = ?1";
 
-    conn.execute(sql, params![enrollment_id])?;
+    let before = enrollment_repo::get_enrollment(conn, enrollment_id)?;
+    conn.execute(sql, params![enrollment_id, status_code, db_reason, termination_date])?;
+
+    let enrollment = enrollment_repo::get_enrollment(conn, enrollment_id)?;
+    audit_service::record_update(conn, audit_service::ENROLLMENT, enrollment_id, &before, &enrollment)?;
+    let carrier_name: Option<String> = conn
+        .query_row(
+            "SELECT name FROM carriers WHERE id = ?1",
+            params![enrollment.carrier_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    // A paid commission on a disenrolled member is a likely chargeback
+    let event_data = serde_json::json!({
+        "enrollment_id": enrollment_id,
+        "plan_name": enrollment.plan_name,
+        "carrier": carrier_name,
+        "reason": event_reason,
+        "commission_status": enrollment.commission_status,
+    })
+    .to_string();
+    let _ = conversation_service::create_system_event(
+        conn,
+        &enrollment.client_id,
+        "CARRIER_SYNC_DISENROLLED",
+        Some(&event_data),
+    );
+
     Ok(())
 }
 
-/// Insert a sync log entry.
+/// Insert a sync log entry, returning its ID.
 fn log_sync(
     conn: &Connection,
     carrier_id: &str,
@@ -202,7 +672,7 @@ fn log_sync(
     matched: usize,
     disenrolled: usize,
     new_found: usize,
-) -> Result<(), AppError> {
+) -> Result<String, AppError> {
     let id = Uuid::new_v4().to_string();
     let sql = "INSERT INTO carrier_sync_logs (id, carrier_id, portal_count, matched, disenrolled, new_found)
                VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
@@ -211,9 +681,75 @@ fn log_sync(
         sql,
         params![id, carrier_id, portal_count as i64, matched as i64, disenrolled as i64, new_found as i64],
     )?;
+    Ok(id)
+}
+
+/// Record a sync attempt that failed before producing a result (bad member
+/// data, an empty-book abort, a portal error or a timeout).
+pub fn log_sync_failure(conn: &Connection, carrier_id: &str, error_message: &str) -> Result<(), AppError> {
+    let id = Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO carrier_sync_logs (id, carrier_id, status, error_message) VALUES (?1, ?2, 'FAILED', ?3)",
+        params![id, carrier_id, error_message],
+    )?;
+    Ok(())
+}
+
+/// Number of raw member snapshots kept per carrier.
+const SNAPSHOTS_PER_CARRIER: i64 = 10;
+
+/// Store the raw portal member list for a sync and prune older snapshots
+/// for the same carrier.
+fn save_snapshot(
+    conn: &Connection,
+    sync_log_id: &str,
+    carrier_id: &str,
+    members: &[PortalMember],
+) -> Result<(), AppError> {
+    let members_json = serde_json::to_string(members)
+        .map_err(|e| AppError::CarrierSync(format!("Failed to serialize snapshot: {}", e)))?;
+
+    conn.execute(
+        "INSERT INTO carrier_sync_snapshots (sync_log_id, carrier_id, members_json) VALUES (?1, ?2, ?3)",
+        params![sync_log_id, carrier_id, members_json],
+    )?;
+
+    conn.execute(
+        "DELETE FROM carrier_sync_snapshots
+         WHERE carrier_id = ?1
+           AND sync_log_id NOT IN (
+               SELECT sync_log_id FROM carrier_sync_snapshots
+               WHERE carrier_id = ?1
+               ORDER BY created_at DESC, rowid DESC
+               LIMIT ?2
+           )",
+        params![carrier_id, SNAPSHOTS_PER_CARRIER],
+    )?;
     Ok(())
 }
 
+/// Get the raw portal member list stored for a sync log entry.
+pub fn get_sync_snapshot(conn: &Connection, sync_log_id: &str) -> Result<SyncSnapshot, AppError> {
+    let (carrier_id, created_at, members_json): (String, String, String) = conn
+        .query_row(
+            "SELECT carrier_id, created_at, members_json FROM carrier_sync_snapshots WHERE sync_log_id = ?1",
+            params![sync_log_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("No snapshot for sync {}", sync_log_id)))?;
+
+    let members = serde_json::from_str(&members_json)
+        .map_err(|e| AppError::CarrierSync(format!("Failed to parse snapshot: {}", e)))?;
+
+    Ok(SyncSnapshot {
+        sync_log_id: sync_log_id.to_string(),
+        carrier_id,
+        created_at,
+        members,
+    })
+}
+
 /// Find an existing client by MBI or by (first_name, last_name, DOB).
 /// Searches both active and inactive clients so that a re-appearing member
 /// reuses the existing record instead of creating a duplicate.
@@ -232,6 +768,20 @@ fn find_existing_client(
     .map(|m| m.client_id)
 }
 
+/// Whether a client already has an active or pending enrollment with a carrier.
+fn has_open_enrollment(conn: &Connection, client_id: &str, carrier_id: &str) -> Result<bool, AppError> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS (
+             SELECT 1 FROM enrollments
+             WHERE client_id = ?1 AND carrier_id = ?2 AND is_active = 1
+               AND status_code IN ('ACTIVE', 'PENDING')
+         )",
+        params![client_id, carrier_id],
+        |row| row.get(0),
+    )?;
+    Ok(exists)
+}
+
 /// Import portal members as new clients with enrollments linked to the carrier.
 pub fn import_portal_members(
     conn: &Connection,
@@ -245,6 +795,14 @@ pub fn import_portal_members(
     for member in members {
         // Check for an existing client (active or inactive) before creating a new one
         let client_id = if let Some(existing_id) = find_existing_client(conn, member) {
+            // Re-importing the same member must not stack a second enrollment
+            if has_open_enrollment(conn, &existing_id, carrier_id)? {
+                errors.push(format!(
+                    "{} {}: already has an active enrollment with this carrier — skipped",
+                    member.first_name, member.last_name
+                ));
+                continue;
+            }
             // Reactivate if the matched client is inactive
             let _ = conn.execute(
                 "UPDATE clients SET is_active = 1, updated_at = datetime('now') WHERE id = ?1 AND is_active = 0",
@@ -258,7 +816,7 @@ pub fn import_portal_members(
                 middle_name: member.middle_name.clone(),
                 dob: member.dob.clone(),
                 gender: member.gender.clone(),
-                phone: member.phone.clone(),
+                phone: member.phone.as_deref().and_then(matching::normalize_phone),
                 phone2: None,
                 email: member.email.clone(),
                 address_line1: member.address_line1.clone(),
@@ -305,54 +863,65 @@ pub fn import_portal_members(
             Some(&event_data),
         );
 
-        let status_code = {
-            let s = member.status.as_deref().unwrap_or("").to_lowercase();
-            let ps = member.policy_status.as_deref().unwrap_or("").to_lowercase();
-            // Explicitly inactive / canceled
-            if ps.contains("inactive") || s.contains("cancel") || s.contains("inactive") || s == "not_enrolled" || s == "terminated" {
-                "CANCELLED"
-            // Explicitly active
-            } else if ps.contains("active") || s.contains("active") || s == "enrolled" {
-                "ACTIVE"
-            // Blank status = active (e.g. Medical Mutual)
-            } else if s.trim().is_empty() {
-                "ACTIVE"
-            } else {
-                "PENDING"
-            }
-        };
+        let status_code = member_status_code(member);
 
-        // If the member is canceled/inactive on the portal, deactivate the client
-        if status_code == "CANCELLED" {
+        // If the member is canceled/termed on the portal, deactivate the client
+        if is_terminal_status(status_code) {
             let _ = conn.execute(
                 "UPDATE clients SET is_active = 0, updated_at = datetime('now') WHERE id = ?1",
                 params![client_id],
             );
         }
 
+        // Portals hand back assorted date formats; store ISO or nothing
+        let effective_date = member.effective_date.as_deref().and_then(matching::normalize_date);
+        let plan_id = plan_service::resolve_imported_plan(conn, &ImportedPlan {
+            carrier_id: Some(carrier_id),
+            plan_type_code: None,
+            plan_name: member.plan_name.as_deref(),
+            contract_number: member.contract_number.as_deref(),
+            pbp_number: member.pbp_number.as_deref(),
+            effective_date: effective_date.as_deref(),
+        })
+        .unwrap_or_else(|e| {
+            tracing::warn!("Plan lookup failed for {} {}: {}", member.first_name, member.last_name, e);
+            None
+        });
+
         let enrollment_input = CreateEnrollmentInput {
             client_id: client_id.clone(),
-            plan_id: None,
+            plan_id,
             carrier_id: Some(carrier_id.to_string()),
             plan_type_code: None,
             plan_name: member.plan_name.clone(),
-            contract_number: None,
-            pbp_number: None,
-            effective_date: member.effective_date.clone(),
+            contract_number: member.contract_number.clone(),
+            pbp_number: member.pbp_number.clone(),
+            effective_date,
             termination_date: None,
-            application_date: member.application_date.clone(),
+            application_date: member.application_date.as_deref().and_then(matching::normalize_date),
             status_code: Some(status_code.to_string()),
             enrollment_period: None,
             disenrollment_reason: None,
             premium: None,
             confirmation_number: None,
             enrollment_source: Some("carrier_sync".to_string()),
+            commission_amount: None,
+            commission_status: None,
+            commission_paid_date: None,
         };
 
         match enrollment_service::create_enrollment(conn, &enrollment_input) {
-            Ok(_) => {
+            Ok(enrollment) => {
+                if let Err(e) = record_aor_status(conn, &enrollment.id, member) {
+                    tracing::warn!("Failed to store AOR status for {} {}: {}", member.first_name, member.last_name, e);
+                }
                 imported += 1;
                 imported_names.push(format!("{} {}", member.first_name, member.last_name));
+                conn.execute(
+                    "UPDATE sync_review_items SET status = 'CREATED', resolved_at = datetime('now')
+                     WHERE carrier_id = ?1 AND member_key = ?2 AND status = 'PENDING'",
+                    params![carrier_id, member_key(member)],
+                )?;
             }
             Err(e) => {
                 errors.push(format!(
@@ -403,7 +972,8 @@ pub fn confirm_disenrollments(
 pub fn get_sync_logs(conn: &Connection, carrier_id: Option<&str>) -> Result<Vec<SyncLogEntry>, AppError> {
     let (sql, param_values): (String, Vec<Box<dyn rusqlite::types::ToSql>>) = if let Some(cid) = carrier_id {
         (
-            "SELECT sl.id, sl.carrier_id, cr.name, sl.synced_at, sl.portal_count, sl.matched, sl.disenrolled, sl.new_found, sl.status
+            "SELECT sl.id, sl.carrier_id, cr.name, sl.synced_at, sl.portal_count, sl.matched, sl.disenrolled, sl.new_found, sl.status,
+                    EXISTS (SELECT 1 FROM carrier_sync_snapshots ss WHERE ss.sync_log_id = sl.id), sl.error_message
              FROM carrier_sync_logs sl
              LEFT JOIN carriers cr ON sl.carrier_id = cr.id
              WHERE sl.carrier_id = ?1
@@ -413,7 +983,8 @@ pub fn get_sync_logs(conn: &Connection, carrier_id: Option<&str>) -> Result<Vec<
         )
     } else {
         (
-            "SELECT sl.id, sl.carrier_id, cr.name, sl.synced_at, sl.portal_count, sl.matched, sl.disenrolled, sl.new_found, sl.status
+            "SELECT sl.id, sl.carrier_id, cr.name, sl.synced_at, sl.portal_count, sl.matched, sl.disenrolled, sl.new_found, sl.status,
+                    EXISTS (SELECT 1 FROM carrier_sync_snapshots ss WHERE ss.sync_log_id = sl.id), sl.error_message
              FROM carrier_sync_logs sl
              LEFT JOIN carriers cr ON sl.carrier_id = cr.id
              ORDER BY sl.synced_at DESC
@@ -436,9 +1007,316 @@ pub fn get_sync_logs(conn: &Connection, carrier_id: Option<&str>) -> Result<Vec<
                 disenrolled: row.get(6)?,
                 new_found: row.get(7)?,
                 status: row.get(8)?,
+                has_snapshot: row.get(9)?,
+                error_message: row.get(10)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
 
     Ok(items)
 }
+
+// ── Review queue ────────────────────────────────────────────────────────────
+
+/// Identifies a portal member across syncs: normalized first name, last
+/// name and DOB
+fn member_key(member: &PortalMember) -> String {
+    format!(
+        "{}|{}|{}",
+        matching::normalize_first_name(&member.first_name),
+        member.last_name.trim().to_ascii_lowercase(),
+        member.dob.as_deref().and_then(matching::normalize_date).unwrap_or_default()
+    )
+}
+
+/// How the agent last resolved this exact portal member / enrollment pairing
+/// (MATCHED or IGNORED), if they have
+fn prior_pair_decision(
+    conn: &Connection,
+    carrier_id: &str,
+    pair: &SyncPossibleMatch,
+) -> Result<Option<String>, AppError> {
+    let status = conn
+        .query_row(
+            "SELECT status FROM sync_review_items
+             WHERE carrier_id = ?1 AND kind = 'POSSIBLE_MATCH' AND member_key = ?2
+               AND enrollment_id = ?3 AND status IN ('MATCHED', 'IGNORED')
+             ORDER BY resolved_at DESC LIMIT 1",
+            params![carrier_id, member_key(&pair.portal_member), pair.enrollment_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(status)
+}
+
+/// Replace the carrier's pending review items with this sync's. Portal
+/// members the agent chose to ignore are not queued again.
+fn queue_for_review(
+    conn: &Connection,
+    sync_log_id: &str,
+    carrier_id: &str,
+    new_in_portal: &[PortalMember],
+    possible_matches: &mut [SyncPossibleMatch],
+) -> Result<(), AppError> {
+    conn.execute(
+        "DELETE FROM sync_review_items WHERE carrier_id = ?1 AND status = 'PENDING'",
+        params![carrier_id],
+    )?;
+
+    let insert = |member: &PortalMember, pair: Option<&SyncPossibleMatch>| -> Result<String, AppError> {
+        let id = Uuid::new_v4().to_string();
+        let member_json = serde_json::to_string(member)
+            .map_err(|e| AppError::CarrierSync(format!("Failed to serialize member: {}", e)))?;
+        conn.execute(
+            "INSERT INTO sync_review_items
+             (id, sync_log_id, carrier_id, kind, member_key, member_json, client_id, enrollment_id, score)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                id,
+                sync_log_id,
+                carrier_id,
+                if pair.is_some() { "POSSIBLE_MATCH" } else { "NEW_IN_PORTAL" },
+                member_key(member),
+                member_json,
+                pair.map(|p| &p.client_id),
+                pair.map(|p| &p.enrollment_id),
+                pair.map(|p| p.score),
+            ],
+        )?;
+        Ok(id)
+    };
+
+    for member in new_in_portal {
+        let ignored: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sync_review_items
+                            WHERE carrier_id = ?1 AND kind = 'NEW_IN_PORTAL'
+                              AND member_key = ?2 AND status = 'IGNORED')",
+            params![carrier_id, member_key(member)],
+            |row| row.get(0),
+        )?;
+        if !ignored {
+            insert(member, None)?;
+        }
+    }
+    for pair in possible_matches.iter_mut() {
+        pair.review_item_id = Some(insert(&pair.portal_member, Some(pair))?);
+    }
+    Ok(())
+}
+
+const REVIEW_ITEM_SELECT: &str = "
+    SELECT r.id, r.carrier_id, cr.name, r.sync_log_id, r.kind, r.member_json, r.client_id,
+           c.first_name || ' ' || c.last_name, r.enrollment_id, r.score, r.status,
+           r.created_at, r.resolved_at
+    FROM sync_review_items r
+    LEFT JOIN carriers cr ON r.carrier_id = cr.id
+    LEFT JOIN clients c ON r.client_id = c.id";
+
+fn map_review_item(row: &rusqlite::Row) -> rusqlite::Result<SyncReviewItem> {
+    let member_json: String = row.get(5)?;
+    let portal_member = serde_json::from_str(&member_json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    Ok(SyncReviewItem {
+        id: row.get(0)?,
+        carrier_id: row.get(1)?,
+        carrier_name: row.get(2)?,
+        sync_log_id: row.get(3)?,
+        kind: row.get(4)?,
+        portal_member,
+        client_id: row.get(6)?,
+        client_name: row.get(7)?,
+        enrollment_id: row.get(8)?,
+        score: row.get(9)?,
+        status: row.get(10)?,
+        created_at: row.get(11)?,
+        resolved_at: row.get(12)?,
+    })
+}
+
+/// Pending review items, optionally for one carrier, possible matches first
+pub fn get_sync_review_items(conn: &Connection, carrier_id: Option<&str>) -> Result<Vec<SyncReviewItem>, AppError> {
+    let sql = format!(
+        "{} WHERE r.status = 'PENDING' AND (?1 IS NULL OR r.carrier_id = ?1)
+         ORDER BY cr.name, r.kind DESC, r.created_at",
+        REVIEW_ITEM_SELECT
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let items = stmt
+        .query_map(params![carrier_id], map_review_item)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+}
+
+fn get_sync_review_item(conn: &Connection, id: &str) -> Result<SyncReviewItem, AppError> {
+    let sql = format!("{} WHERE r.id = ?1", REVIEW_ITEM_SELECT);
+    conn.query_row(&sql, params![id], map_review_item)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Review item {} not found", id)))
+}
+
+/// Resolve a pending review item:
+/// - `confirm_match`: the possible match is the same person; later syncs
+///   count the pair as matched
+/// - `create_new`: import the portal member as a new client and enrollment
+/// - `ignore`: drop it; the member (or pair) isn't queued again
+pub fn resolve_sync_review_item(conn: &Connection, id: &str, action: SyncReviewAction) -> Result<SyncReviewItem, AppError> {
+    let item = get_sync_review_item(conn, id)?;
+    if item.status != "PENDING" {
+        return Err(AppError::Validation(format!(
+            "Review item was already resolved ({})",
+            item.status
+        )));
+    }
+
+    let status = match action {
+        SyncReviewAction::ConfirmMatch => {
+            if item.kind != "POSSIBLE_MATCH" {
+                return Err(AppError::Validation(
+                    "Only possible matches can be confirmed".to_string(),
+                ));
+            }
+            "MATCHED"
+        }
+        SyncReviewAction::CreateNew => {
+            let result = import_portal_members(conn, &item.carrier_id, std::slice::from_ref(&item.portal_member))?;
+            if result.imported == 0 {
+                return Err(AppError::Validation(result.errors.join("; ")));
+            }
+            "CREATED"
+        }
+        SyncReviewAction::Ignore => "IGNORED",
+    };
+
+    conn.execute(
+        "UPDATE sync_review_items SET status = ?2, resolved_at = datetime('now') WHERE id = ?1",
+        params![id, status],
+    )?;
+    get_sync_review_item(conn, id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(enrollment_id: &str, first: &str, last: &str) -> LocalEnrollment {
+        LocalEnrollment {
+            enrollment_id: enrollment_id.to_string(),
+            client_id: format!("client-{}", enrollment_id),
+            client_first_name: first.to_string(),
+            client_last_name: last.to_string(),
+            client_mbi: None,
+            client_dob: None,
+            plan_name: None,
+        }
+    }
+
+    fn member(first: &str, last: &str) -> PortalMember {
+        PortalMember {
+            first_name: first.to_string(),
+            last_name: last.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_possible_matches_pairs_similar_names() {
+        let locals = [local("e1", "John", "Smith"), local("e2", "Mary", "Jones")];
+        let (possible, unmatched) =
+            find_possible_matches(&locals, &[], vec![member("Jon", "Smith"), member("Robert", "Brown")], 0.85);
+
+        assert_eq!(possible.len(), 1);
+        assert_eq!(possible[0].enrollment_id, "e1");
+        assert_eq!(possible[0].client_id, "client-e1");
+        assert_eq!(possible[0].client_name, "John Smith");
+        assert_eq!(possible[0].portal_member.first_name, "Jon");
+        assert!(possible[0].score >= 0.85 && possible[0].score < 1.0);
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].first_name, "Robert");
+    }
+
+    #[test]
+    fn test_find_possible_matches_ignores_case_and_middle_initial() {
+        let locals = [local("e1", "MARY A", "JONES ")];
+        let (possible, unmatched) = find_possible_matches(&locals, &[], vec![member("mary", "jones")], 0.85);
+        assert_eq!(possible.len(), 1);
+        assert_eq!(possible[0].score, 1.0);
+        assert!(unmatched.is_empty());
+    }
+
+    #[test]
+    fn test_find_possible_matches_skips_claimed_enrollments() {
+        let locals = [local("e1", "John", "Smith")];
+        let (possible, unmatched) = find_possible_matches(&locals, &["e1"], vec![member("Jon", "Smith")], 0.85);
+        assert!(possible.is_empty());
+        assert_eq!(unmatched.len(), 1);
+    }
+
+    #[test]
+    fn test_find_possible_matches_respects_threshold() {
+        let locals = [local("e1", "John", "Smith")];
+        let (possible, unmatched) = find_possible_matches(&locals, &[], vec![member("Jon", "Smith")], 0.99);
+        assert!(possible.is_empty());
+        assert_eq!(unmatched.len(), 1);
+    }
+
+    #[test]
+    fn test_find_possible_matches_offers_each_enrollment_once() {
+        // Both portal members resemble the one enrollment; the closer one gets it
+        let locals = [local("e1", "John", "Smith")];
+        let (possible, unmatched) = find_possible_matches(
+            &locals,
+            &[],
+            vec![member("Jonathan", "Smith"), member("Jon", "Smith")],
+            0.8,
+        );
+        assert_eq!(possible.len(), 1);
+        assert_eq!(possible[0].portal_member.first_name, "Jon");
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].first_name, "Jonathan");
+    }
+
+    #[test]
+    fn test_normalize_portal_status_active() {
+        assert_eq!(normalize_portal_status(""), "ACTIVE");
+        assert_eq!(normalize_portal_status("   "), "ACTIVE");
+        assert_eq!(normalize_portal_status("Active"), "ACTIVE");
+        assert_eq!(normalize_portal_status("A"), "ACTIVE");
+        assert_eq!(normalize_portal_status("Enrolled"), "ACTIVE");
+        assert_eq!(normalize_portal_status("In Force"), "ACTIVE");
+        assert_eq!(normalize_portal_status("INFORCE"), "ACTIVE");
+    }
+
+    #[test]
+    fn test_normalize_portal_status_disenrolled() {
+        assert_eq!(normalize_portal_status("Deceased"), "DISENROLLED_DECEASED");
+        assert_eq!(normalize_portal_status("Termed - Death"), "DISENROLLED_DECEASED");
+        assert_eq!(normalize_portal_status("Plan Terminated"), "DISENROLLED_PLAN_TERM");
+        assert_eq!(normalize_portal_status("Other Coverage"), "DISENROLLED_OTHER_COV");
+        assert_eq!(normalize_portal_status("Voluntary Disenrollment"), "DISENROLLED_VOLUNTARY");
+        // "involuntary" contains "voluntary" but must not map to it
+        assert_eq!(normalize_portal_status("Involuntary"), "DISENROLLED_INVOLUNTARY");
+        assert_eq!(normalize_portal_status("T"), "DISENROLLED_INVOLUNTARY");
+        assert_eq!(normalize_portal_status("Terminated"), "DISENROLLED_INVOLUNTARY");
+        assert_eq!(normalize_portal_status("Lapsed"), "DISENROLLED_INVOLUNTARY");
+        assert_eq!(normalize_portal_status("DISENROLLED"), "DISENROLLED_INVOLUNTARY");
+        // "inactive" contains "active" but is a termination
+        assert_eq!(normalize_portal_status("Inactive"), "DISENROLLED_INVOLUNTARY");
+    }
+
+    #[test]
+    fn test_normalize_portal_status_other() {
+        assert_eq!(normalize_portal_status("Reinstated"), "REINSTATED");
+        assert_eq!(normalize_portal_status("Rejected"), "REJECTED");
+        assert_eq!(normalize_portal_status("Application Denied"), "REJECTED");
+        assert_eq!(normalize_portal_status("Cancelled"), "CANCELLED");
+        assert_eq!(normalize_portal_status("WITHDRAWN"), "CANCELLED");
+        assert_eq!(normalize_portal_status("Not Enrolled"), "CANCELLED");
+        // Separators are normalized before matching
+        assert_eq!(normalize_portal_status("plan_term"), "DISENROLLED_PLAN_TERM");
+        assert_eq!(normalize_portal_status("in-force"), "ACTIVE");
+        // Anything unrecognised waits as pending
+        assert_eq!(normalize_portal_status("Pending"), "PENDING");
+        assert_eq!(normalize_portal_status("Under Review"), "PENDING");
+    }
+}
@@ -1,62 +1,268 @@
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 use uuid::Uuid;
 use crate::error::AppError;
-use crate::models::{Client, ClientFilters, ClientListItem, CreateClientInput, UpdateClientInput, PaginatedResult};
+use crate::models::{client, AgingInClient, Client, ClientFilters, ClientListItem, CreateClientInput, UpdateClientInput, PaginatedResult};
 use crate::repositories::client_repo;
+use crate::services::{audit_service, conversation_service};
 use crate::services::matching::{self, DuplicateCandidate, DuplicateGroup};
 
-/// Validate MBI format: 11 characters, specific pattern
-fn validate_mbi(mbi: &str) -> Result<(), AppError> {
-    if mbi.is_empty() {
-        return Ok(());
+/// Check the filter values the queries can't: the enrollment status keyword,
+/// the created-date range (both bounds YYYY-MM-DD and in order) and the
+/// no-contact date
+pub fn validate_filters(filters: &ClientFilters) -> Result<(), AppError> {
+    if let Some(ref status) = filters.enrollment_status {
+        if status != "NONE" {
+            return Err(AppError::Validation(format!(
+                "Unknown enrollment_status filter: {}",
+                status
+            )));
+        }
     }
-    // MBI format: [1-9][AC-HJKMNP-RT][0-9AC-HJKMNP-RT][0-9]-[AC-HJKMNP-RT][AC-HJKMNP-RT0-9][0-9]-[AC-HJKMNP-RT][AC-HJKMNP-RT0-9][0-9][0-9]
-    // Simplified: 11 alphanumeric characters (no S, L, O, I, B, Z)
-    if mbi.len() != 11 {
-        return Err(AppError::Validation(format!("MBI must be 11 characters, got {}", mbi.len())));
-    }
-    let valid = mbi.chars().all(|c| c.is_ascii_alphanumeric());
-    if !valid {
-        return Err(AppError::Validation("MBI must contain only letters and numbers".to_string()));
+
+    let parse = |label: &str, value: &Option<String>| -> Result<Option<chrono::NaiveDate>, AppError> {
+        value
+            .as_deref()
+            .map(|v| {
+                chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d").map_err(|_| {
+                    AppError::Validation(format!("{} must be a YYYY-MM-DD date, got {}", label, v))
+                })
+            })
+            .transpose()
+    };
+    let after = parse("created_after", &filters.created_after)?;
+    let before = parse("created_before", &filters.created_before)?;
+    parse("no_contact_since", &filters.no_contact_since)?;
+    if let (Some(after), Some(before)) = (after, before) {
+        if after > before {
+            return Err(AppError::Validation(
+                "created_after must be on or before created_before".to_string(),
+            ));
+        }
     }
     Ok(())
 }
 
+/// Page size cap used when the `max_page_size` setting is missing or invalid
+const DEFAULT_MAX_PAGE_SIZE: i32 = 100;
+/// The setting can raise the cap, but never past this
+const MAX_PAGE_SIZE_CEILING: i32 = 1000;
+
+/// Largest `per_page` the paged lists serve, from the `max_page_size` setting
+pub fn max_page_size(conn: &Connection) -> Result<i32, AppError> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'max_page_size'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(value
+        .and_then(|v| v.trim().parse::<i32>().ok())
+        .filter(|n| *n >= 1)
+        .map_or(DEFAULT_MAX_PAGE_SIZE, |n| n.min(MAX_PAGE_SIZE_CEILING)))
+}
+
+/// The result's `per_page` is the clamped size, so callers can tell when
+/// they asked for more than `max_page_size`
 pub fn get_clients(conn: &Connection, filters: &ClientFilters, page: i32, per_page: i32) -> Result<PaginatedResult<ClientListItem>, AppError> {
+    validate_filters(filters)?;
     let page = if page < 1 { 1 } else { page };
-    let per_page = per_page.clamp(1, 100);
+    let per_page = per_page.clamp(1, max_page_size(conn)?);
     client_repo::get_clients(conn, filters, page, per_page)
 }
 
+/// Just the `total` of `get_clients`, for match counts while filters change
+pub fn count_clients(conn: &Connection, filters: &ClientFilters) -> Result<i64, AppError> {
+    validate_filters(filters)?;
+    client_repo::count_clients(conn, filters)
+}
+
+/// Rebuild and then optimize the client search index
+pub fn optimize_search_index(conn: &Connection) -> Result<(), AppError> {
+    client_repo::rebuild_search_index(conn)?;
+    client_repo::optimize_search_index(conn)
+}
+
 pub fn get_client(conn: &Connection, id: &str) -> Result<Client, AppError> {
     client_repo::get_client(conn, id)
 }
 
+/// Reject an MBI another active client already has; empty MBIs are allowed
+fn ensure_mbi_unique(conn: &Connection, mbi: &str, exclude_id: Option<&str>) -> Result<(), AppError> {
+    if !mbi.is_empty() && client_repo::mbi_exists(conn, mbi, exclude_id)? {
+        return Err(AppError::Validation("A client with this MBI already exists".to_string()));
+    }
+    Ok(())
+}
+
 pub fn create_client(conn: &Connection, input: &CreateClientInput) -> Result<Client, AppError> {
-    // Validate MBI if provided
+    // Validate MBI if provided, storing it in normalized form
+    let mut input = input.clone();
     if let Some(ref mbi) = input.mbi {
-        validate_mbi(mbi)?;
+        let mbi = matching::validate_mbi(mbi)?;
+        ensure_mbi_unique(conn, &mbi, None)?;
+        input.mbi = Some(mbi);
     }
 
     let id = Uuid::new_v4().to_string();
-    client_repo::create_client(conn, &id, input)?;
-    client_repo::get_client(conn, &id)
+    audit_service::in_transaction(conn, |tx| {
+        client_repo::create_client(tx, &id, &input)?;
+        let client = client_repo::get_client(tx, &id)?;
+        audit_service::record_create(tx, audit_service::CLIENT, &id, &client)?;
+        Ok(client)
+    })
 }
 
 pub fn update_client(conn: &Connection, id: &str, input: &UpdateClientInput) -> Result<Client, AppError> {
+    let mut input = input.clone();
     if let Some(ref mbi) = input.mbi {
-        validate_mbi(mbi)?;
+        let mbi = matching::validate_mbi(mbi)?;
+        ensure_mbi_unique(conn, &mbi, Some(id))?;
+        input.mbi = Some(mbi);
     }
-    client_repo::update_client(conn, id, input)?;
-    client_repo::get_client(conn, id)
+    audit_service::in_transaction(conn, |tx| {
+        let before = client_repo::get_client(tx, id)?;
+        client_repo::update_client(tx, id, &input)?;
+        let after = audited_client(tx, id, &before)?;
+        log_address_change(tx, &before, &after);
+        Ok(after)
+    })
+}
+
+/// A changed address (not one filled in for the first time) usually means the
+/// client moved, which opens an SEP; see `sep_service`.
+fn log_address_change(conn: &Connection, before: &Client, after: &Client) {
+    fn address(c: &Client) -> [Option<&str>; 5] {
+        [
+            c.address_line1.as_deref(),
+            c.city.as_deref(),
+            c.state.as_deref(),
+            c.zip.as_deref(),
+            c.county.as_deref(),
+        ]
+        .map(|v| v.map(str::trim).filter(|v| !v.is_empty()))
+    }
+    let (old, new) = (address(before), address(after));
+    if old == new || old.iter().all(Option::is_none) {
+        return;
+    }
+    let event_data = serde_json::json!({
+        "from": { "address_line1": old[0], "city": old[1], "state": old[2], "zip": old[3], "county": old[4] },
+        "to": { "address_line1": new[0], "city": new[1], "state": new[2], "zip": new[3], "county": new[4] },
+    })
+    .to_string();
+    let _ = conversation_service::create_system_event(conn, &after.id, "ADDRESS_CHANGED", Some(&event_data));
+}
+
+/// Re-read a client after a change and audit the difference
+fn audited_client(conn: &Connection, id: &str, before: &Client) -> Result<Client, AppError> {
+    let after = client_repo::get_client(conn, id)?;
+    audit_service::record_update(conn, audit_service::CLIENT, id, before, &after)?;
+    Ok(after)
+}
+
+/// Tags live in a comma-separated column, so a tag can't contain the delimiter
+fn validate_tag(tag: &str) -> Result<&str, AppError> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(AppError::Validation("Tag cannot be empty".to_string()));
+    }
+    if tag.contains(client::TAG_DELIMITER) {
+        return Err(AppError::Validation(format!(
+            "Tag cannot contain '{}'",
+            client::TAG_DELIMITER
+        )));
+    }
+    Ok(tag)
+}
+
+pub fn add_client_tag(conn: &Connection, id: &str, tag: &str) -> Result<Client, AppError> {
+    let tag = validate_tag(tag)?;
+    audit_service::in_transaction(conn, |tx| {
+        let before = client_repo::get_client(tx, id)?;
+        client_repo::add_tag(tx, id, tag)?;
+        audited_client(tx, id, &before)
+    })
+}
+
+pub fn remove_client_tag(conn: &Connection, id: &str, tag: &str) -> Result<Client, AppError> {
+    audit_service::in_transaction(conn, |tx| {
+        let before = client_repo::get_client(tx, id)?;
+        client_repo::remove_tag(tx, id, tag.trim())?;
+        audited_client(tx, id, &before)
+    })
+}
+
+/// Add (or remove) one tag across many clients in a single transaction.
+/// IDs that no longer exist are skipped. Returns how many clients changed.
+pub fn bulk_tag_clients(conn: &Connection, client_ids: &[String], tag: &str, add: bool) -> Result<usize, AppError> {
+    let tag = if add { validate_tag(tag)? } else { tag.trim() };
+    let tx = conn.unchecked_transaction()?;
+    let mut modified = 0;
+    for id in client_ids {
+        let changed = if add {
+            client_repo::add_tag(&tx, id, tag)
+        } else {
+            client_repo::remove_tag(&tx, id, tag)
+        };
+        match changed {
+            Ok(true) => {
+                let key = if add { "added" } else { "removed" };
+                audit_service::record(&tx, audit_service::CLIENT, id, "UPDATE", Some(serde_json::json!({ "tags": { key: tag } })))?;
+                modified += 1;
+            }
+            Ok(false) | Err(AppError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    tx.commit()?;
+    Ok(modified)
 }
 
 pub fn delete_client(conn: &Connection, id: &str) -> Result<(), AppError> {
-    client_repo::delete_client(conn, id)
+    audit_service::in_transaction(conn, |tx| {
+        client_repo::delete_client(tx, id)?;
+        audit_service::record(tx, audit_service::CLIENT, id, "DELETE", None)
+    })
 }
 
 pub fn hard_delete_client(conn: &Connection, id: &str) -> Result<(), AppError> {
-    client_repo::hard_delete_client(conn, id)
+    audit_service::in_transaction(conn, |tx| {
+        client_repo::hard_delete_client(tx, id)?;
+        audit_service::record(tx, audit_service::CLIENT, id, "DELETE", Some(serde_json::json!({ "permanent": true })))
+    })
+}
+
+/// Permanently remove clients that were soft-deleted more than
+/// `older_than_days` ago, in one transaction. Returns the count purged.
+pub fn purge_inactive_clients(conn: &Connection, older_than_days: i64) -> Result<usize, AppError> {
+    if older_than_days < 0 {
+        return Err(AppError::Validation("older_than_days cannot be negative".to_string()));
+    }
+    let tx = conn.unchecked_transaction()?;
+    let purged = client_repo::purge_inactive_clients(&tx, older_than_days)?;
+    if purged > 0 {
+        tx.execute("INSERT INTO clients_fts(clients_fts) VALUES('rebuild')", [])?;
+        // The purged rows are gone, so the entry records the sweep itself
+        audit_service::record(
+            &tx,
+            audit_service::CLIENT,
+            "*",
+            "PURGE",
+            Some(serde_json::json!({ "count": purged, "older_than_days": older_than_days })),
+        )?;
+    }
+    tx.commit()?;
+    Ok(purged)
+}
+
+/// Active clients whose 65th birthday falls within the next `months_ahead`
+/// months, including prospects with no enrollments yet.
+pub fn get_aging_in_clients(conn: &Connection, months_ahead: i64) -> Result<Vec<AgingInClient>, AppError> {
+    if months_ahead < 0 {
+        return Err(AppError::Validation("months_ahead cannot be negative".to_string()));
+    }
+    client_repo::get_aging_in_clients(conn, months_ahead)
 }
 
 /// Check for potential duplicate clients before creating a new one.
@@ -75,35 +281,133 @@ pub fn find_duplicate_clients(conn: &Connection) -> Result<Vec<DuplicateGroup>,
     matching::find_duplicate_clients(conn)
 }
 
-/// Merge `source_id` into `keeper_id`: move all enrollments and conversations
-/// from source to keeper, then hard-delete the source client.
+/// Whether a Medicaid dual status code means the client is dual-eligible.
+/// Blank codes and explicit non-dual values (CMS code `00`, "None", "No")
+/// are not dual; any other level (QMB, SLMB, FBDE, 01-10, ...) is.
+pub fn is_dual_status(code: &str) -> bool {
+    let code = code.trim().to_lowercase();
+    !matches!(
+        code.as_str(),
+        "" | "0" | "00" | "none" | "no" | "n" | "n/a" | "na" | "non-dual" | "not dual"
+    )
+}
+
+/// Re-derive `is_dual_eligible` from `dual_status_code` for every client that
+/// has a status code recorded. Clients without a code keep their flag, since it
+/// may have come from another source (e.g. a D-SNP eligibility column).
+/// Each change is audited. Returns the number of clients whose flag changed.
+pub fn recompute_dual_eligibility(conn: &Connection) -> Result<usize, AppError> {
+    let tx = conn.unchecked_transaction()?;
+    let rows: Vec<(String, String, bool)> = {
+        let mut stmt = tx.prepare(
+            "SELECT id, dual_status_code, COALESCE(is_dual_eligible, 0) FROM clients
+             WHERE dual_status_code IS NOT NULL AND TRIM(dual_status_code) != ''",
+        )?;
+        let mapped = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        mapped.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut changed = 0usize;
+    for (id, code, current) in rows {
+        let derived = is_dual_status(&code);
+        if derived != current {
+            tx.execute(
+                "UPDATE clients SET is_dual_eligible = ?1, updated_at = datetime('now') WHERE id = ?2",
+                rusqlite::params![derived, id],
+            )?;
+            audit_service::record(
+                &tx,
+                audit_service::CLIENT,
+                &id,
+                "UPDATE",
+                Some(serde_json::json!({ "is_dual_eligible": { "old": current, "new": derived } })),
+            )?;
+            changed += 1;
+        }
+    }
+    tx.commit()?;
+    Ok(changed)
+}
+
+/// Merge `source_id` into `keeper_id`: move enrollments, conversations,
+/// providers, and commission entries from source to keeper, fill any
+/// keeper fields that are blank from the source, then soft-delete the source
+/// and record a CLIENT_MERGED event on the keeper. Runs in one transaction so
+/// a failure leaves both records untouched.
 pub fn merge_clients(conn: &Connection, keeper_id: &str, source_id: &str) -> Result<Client, AppError> {
     if keeper_id == source_id {
         return Err(AppError::Validation("Cannot merge a client into itself".to_string()));
     }
+    let tx = conn.unchecked_transaction()?;
+
     // Verify both exist
-    client_repo::get_client(conn, keeper_id)?;
-    client_repo::get_client(conn, source_id)?;
+    let keeper = client_repo::get_client(&tx, keeper_id)?;
+    let source = client_repo::get_client(&tx, source_id)?;
 
-    // Move enrollments
-    conn.execute(
-        "UPDATE enrollments SET client_id = ?1, updated_at = datetime('now') WHERE client_id = ?2",
-        rusqlite::params![keeper_id, source_id],
-    )?;
-    // Move conversations
-    conn.execute(
-        "UPDATE conversations SET client_id = ?1, updated_at = datetime('now') WHERE client_id = ?2",
+    // Fill blank keeper fields from the source
+    let blank = |v: &Option<String>| v.as_deref().is_none_or(|s| s.trim().is_empty());
+    let mut filled: Vec<&str> = Vec::new();
+    macro_rules! fill_blank {
+        ($($field:ident),* $(,)?) => {
+            $(
+                if blank(&keeper.$field) && !blank(&source.$field) {
+                    filled.push(stringify!($field));
+                }
+            )*
+        };
+    }
+    fill_blank!(
+        middle_name, dob, gender, phone, phone2, email, address_line1, address_line2,
+        city, state, zip, county, mbi, part_a_date, part_b_date, orec, dual_status_code,
+        lis_level, medicaid_id, lead_source, member_record_locator, tags, notes,
+    );
+    for col in &filled {
+        tx.execute(
+            &format!(
+                "UPDATE clients SET {col} = (SELECT {col} FROM clients WHERE id = ?2) WHERE id = ?1",
+                col = col
+            ),
+            rusqlite::params![keeper_id, source_id],
+        )?;
+    }
+    if source.is_dual_eligible == Some(true) && keeper.is_dual_eligible != Some(true) {
+        tx.execute(
+            "UPDATE clients SET is_dual_eligible = 1 WHERE id = ?1",
+            rusqlite::params![keeper_id],
+        )?;
+        filled.push("is_dual_eligible");
+    }
+
+    // Move related records
+    for table in ["enrollments", "conversations", "conversation_entries", "client_providers"] {
+        tx.execute(
+            &format!("UPDATE {} SET client_id = ?1 WHERE client_id = ?2", table),
+            rusqlite::params![keeper_id, source_id],
+        )?;
+    }
+    // A keeper entry for the same carrier/month wins; conflicting source rows stay put
+    tx.execute(
+        "UPDATE OR IGNORE commission_entries SET client_id = ?1 WHERE client_id = ?2",
         rusqlite::params![keeper_id, source_id],
     )?;
-    // Move conversation entries
-    conn.execute(
-        "UPDATE conversation_entries SET client_id = ?1, updated_at = datetime('now') WHERE client_id = ?2",
-        rusqlite::params![keeper_id, source_id],
+    tx.execute(
+        "UPDATE clients SET updated_at = datetime('now') WHERE id = ?1",
+        rusqlite::params![keeper_id],
     )?;
-    // Hard-delete source client
-    conn.execute("DELETE FROM clients WHERE id = ?1", rusqlite::params![source_id])?;
-    // Rebuild FTS
-    conn.execute("INSERT INTO clients_fts(clients_fts) VALUES('rebuild')", [])?;
 
+    // Soft-delete the source
+    client_repo::delete_client(&tx, source_id)?;
+    audit_service::record(&tx, audit_service::CLIENT, source_id, "DELETE", Some(serde_json::json!({ "merged_into": keeper_id })))?;
+    let merged = client_repo::get_client(&tx, keeper_id)?;
+    audit_service::record_update(&tx, audit_service::CLIENT, keeper_id, &keeper, &merged)?;
+
+    let event_data = serde_json::json!({
+        "merged_client_id": source_id,
+        "merged_client_name": format!("{} {}", source.first_name, source.last_name),
+        "fields_filled": filled,
+    });
+    conversation_service::create_system_event(&tx, keeper_id, "CLIENT_MERGED", Some(&event_data.to_string()))?;
+
+    tx.commit()?;
     client_repo::get_client(conn, keeper_id)
 }
@@ -1,12 +1,15 @@
+use chrono::{Days, Months, NaiveDate};
 use rusqlite::Connection;
 use uuid::Uuid;
 
 use crate::error::AppError;
 use crate::models::{
-    Conversation, ConversationEntry, ConversationListItem, CreateConversationEntryInput,
-    CreateConversationInput, TimelineEntry, UpdateConversationEntryInput, UpdateConversationInput,
+    CallOutcome, ClientNextAction, Conversation, ConversationEntry, ConversationListItem, ConversationStatus,
+    CreateConversationEntryInput, CreateConversationInput, EntryType, FollowUpRecurrence, TimelineEntry,
+    UpdateConversationEntryInput, UpdateConversationInput,
 };
 use crate::repositories::conversation_repo;
+use crate::services::audit_service;
 
 pub fn get_conversations(
     conn: &Connection,
@@ -30,8 +33,12 @@ pub fn create_conversation(
     }
 
     let id = Uuid::new_v4().to_string();
-    conversation_repo::create_conversation(conn, &id, input)?;
-    conversation_repo::get_conversation(conn, &id)
+    audit_service::in_transaction(conn, |tx| {
+        conversation_repo::create_conversation(tx, &id, input)?;
+        let conversation = conversation_repo::get_conversation(tx, &id)?;
+        audit_service::record_create(tx, audit_service::CONVERSATION, &id, &conversation)?;
+        Ok(conversation)
+    })
 }
 
 pub fn update_conversation(
@@ -39,18 +46,41 @@ pub fn update_conversation(
     id: &str,
     input: &UpdateConversationInput,
 ) -> Result<Conversation, AppError> {
-    if let Some(ref status) = input.status {
-        let valid = ["OPEN", "CLOSED", "ARCHIVED"];
-        if !valid.contains(&status.as_str()) {
-            return Err(AppError::Validation(format!(
-                "Invalid conversation status: {}",
-                status
-            )));
+    audit_service::in_transaction(conn, |tx| {
+        let before = conversation_repo::get_conversation(tx, id)?;
+        conversation_repo::update_conversation(tx, id, input)?;
+        let after = conversation_repo::get_conversation(tx, id)?;
+        audit_service::record_update(tx, audit_service::CONVERSATION, id, &before, &after)?;
+        Ok(after)
+    })
+}
+
+/// Move every conversation in `ids` to `status` in one transaction, e.g. to
+/// archive a disenrolled client's closed threads. Each change goes through
+/// `update_conversation` so it is audited; conversations already in that
+/// status are skipped. Returns how many changed.
+pub fn bulk_update_conversation_status(
+    conn: &Connection,
+    ids: &[String],
+    status: ConversationStatus,
+) -> Result<usize, AppError> {
+    let input = UpdateConversationInput {
+        title: None,
+        status: Some(status),
+        is_pinned: None,
+        is_active: None,
+    };
+    let tx = conn.unchecked_transaction()?;
+    let mut changed = 0;
+    for id in ids {
+        if conversation_repo::get_conversation(&tx, id)?.status == status {
+            continue;
         }
+        update_conversation(&tx, id, &input)?;
+        changed += 1;
     }
-
-    conversation_repo::update_conversation(conn, id, input)?;
-    conversation_repo::get_conversation(conn, id)
+    tx.commit()?;
+    Ok(changed)
 }
 
 pub fn get_conversation_entries(
@@ -64,31 +94,48 @@ pub fn create_conversation_entry(
     conn: &Connection,
     input: &CreateConversationEntryInput,
 ) -> Result<ConversationEntry, AppError> {
-    let valid_types = ["CALL", "EMAIL", "MEETING", "SMS", "NOTE", "SYSTEM"];
-    if !valid_types.contains(&input.entry_type.as_str()) {
-        return Err(AppError::Validation(format!(
-            "Invalid entry type: {}",
-            input.entry_type
-        )));
+    // CALL and SMS entries require a direction (stored in call_direction)
+    if input.call_direction.is_none() {
+        match input.entry_type {
+            EntryType::Call => {
+                return Err(AppError::Validation(
+                    "Call entries require a call direction (INBOUND or OUTBOUND)".to_string(),
+                ))
+            }
+            EntryType::Sms => {
+                return Err(AppError::Validation(
+                    "SMS entries require a direction (INBOUND or OUTBOUND)".to_string(),
+                ))
+            }
+            _ => {}
+        }
     }
 
-    // CALL entries require call_direction
-    if input.entry_type == "CALL" && input.call_direction.is_none() {
+    let recurring = input
+        .follow_up_recurrence
+        .is_some_and(|r| r != FollowUpRecurrence::None);
+    if recurring && input.follow_up_date.is_none() {
         return Err(AppError::Validation(
-            "Call entries require a call direction (INBOUND or OUTBOUND)".to_string(),
+            "Recurring follow-ups need a first follow-up date".to_string(),
         ));
     }
 
     // SYSTEM entries should not be created directly through this path
-    if input.entry_type == "SYSTEM" {
+    if input.entry_type == EntryType::System {
         return Err(AppError::Validation(
             "System entries cannot be created directly; use system event integration".to_string(),
         ));
     }
 
+    validate_call_fields(input.entry_type, input.call_outcome, input.call_duration)?;
+
     let id = Uuid::new_v4().to_string();
-    conversation_repo::create_conversation_entry(conn, &id, input)?;
-    conversation_repo::get_conversation_entry(conn, &id)
+    audit_service::in_transaction(conn, |tx| {
+        conversation_repo::create_conversation_entry(tx, &id, input)?;
+        let entry = conversation_repo::get_conversation_entry(tx, &id)?;
+        audit_service::record_create(tx, audit_service::CONVERSATION_ENTRY, &id, &entry)?;
+        Ok(entry)
+    })
 }
 
 pub fn update_conversation_entry(
@@ -96,14 +143,47 @@ pub fn update_conversation_entry(
     id: &str,
     input: &UpdateConversationEntryInput,
 ) -> Result<ConversationEntry, AppError> {
-    conversation_repo::update_conversation_entry(conn, id, input)?;
-    conversation_repo::get_conversation_entry(conn, id)
+    let existing = conversation_repo::get_conversation_entry(conn, id)?;
+    if input.call_direction.is_some()
+        && !matches!(existing.entry_type, EntryType::Call | EntryType::Sms)
+    {
+        return Err(AppError::Validation(
+            "Only call and SMS entries have a direction".to_string(),
+        ));
+    }
+    validate_call_fields(existing.entry_type, input.call_outcome, input.call_duration)?;
+
+    audit_service::in_transaction(conn, |tx| {
+        conversation_repo::update_conversation_entry(tx, id, input)?;
+        let after = conversation_repo::get_conversation_entry(tx, id)?;
+        audit_service::record_update(tx, audit_service::CONVERSATION_ENTRY, id, &existing, &after)?;
+        Ok(after)
+    })
+}
+
+/// Direction and outcome values are checked when the input is deserialized
+/// (`CallDirection`, `CallOutcome`); this checks the call-only fields aren't
+/// set on other kinds of entry and the duration isn't negative.
+fn validate_call_fields(
+    entry_type: EntryType,
+    call_outcome: Option<CallOutcome>,
+    call_duration: Option<i64>,
+) -> Result<(), AppError> {
+    if entry_type != EntryType::Call && (call_outcome.is_some() || call_duration.is_some()) {
+        return Err(AppError::Validation(
+            "Call outcome and duration only apply to call entries".to_string(),
+        ));
+    }
+    if call_duration.is_some_and(|d| d < 0) {
+        return Err(AppError::Validation("Call duration cannot be negative".to_string()));
+    }
+    Ok(())
 }
 
 pub fn get_client_timeline(
     conn: &Connection,
     client_id: &str,
-    entry_type_filter: Option<&str>,
+    entry_type_filter: Option<EntryType>,
     limit: Option<i64>,
     offset: Option<i64>,
 ) -> Result<Vec<TimelineEntry>, AppError> {
@@ -112,11 +192,104 @@ pub fn get_client_timeline(
     conversation_repo::get_client_timeline(conn, client_id, entry_type_filter, limit, offset)
 }
 
+/// The client's soonest upcoming follow-up (recurring series at their next
+/// occurrence) with the date of their last entry, or `None` when nothing is
+/// scheduled.
+pub fn get_client_next_action(
+    conn: &Connection,
+    client_id: &str,
+) -> Result<Option<ClientNextAction>, AppError> {
+    let Some(next) = get_pending_follow_ups(conn, Some(client_id), false)?.into_iter().next() else {
+        return Ok(None);
+    };
+    let Some(follow_up_date) = next.follow_up_date else {
+        return Ok(None);
+    };
+    Ok(Some(ClientNextAction {
+        entry_id: next.id,
+        conversation_id: next.conversation_id,
+        entry_type: next.entry_type,
+        subject: next.subject,
+        follow_up_date,
+        follow_up_note: next.follow_up_note,
+        last_entry_at: conversation_repo::get_last_entry_at(conn, client_id)?,
+    }))
+}
+
+/// Upcoming follow-ups, soonest first. Recurring entries are reported at
+/// their next occurrence on or after today, and dropped once the series has
+/// run past `follow_up_until`.
+///
+/// With `include_overdue`, past one-off follow-ups with no later entry for
+/// the client are included too; they sort ahead of upcoming ones, oldest
+/// first.
 pub fn get_pending_follow_ups(
     conn: &Connection,
     client_id: Option<&str>,
+    include_overdue: bool,
 ) -> Result<Vec<TimelineEntry>, AppError> {
-    conversation_repo::get_pending_follow_ups(conn, client_id)
+    // date('now') in the repo query is UTC, so match it here
+    let today = chrono::Utc::now().date_naive();
+    let mut items = conversation_repo::get_pending_follow_ups(conn, client_id, include_overdue)?;
+
+    items.retain_mut(|entry| {
+        let recurrence = match entry.follow_up_recurrence {
+            Some(r) if r != FollowUpRecurrence::None => r,
+            _ => return true,
+        };
+        let (Some(start), until) = (
+            entry.follow_up_date.as_deref().and_then(parse_date),
+            entry.follow_up_until.as_deref().and_then(parse_date),
+        ) else {
+            return true;
+        };
+        match next_occurrence(start, recurrence, until, today) {
+            Some(next) => {
+                entry.follow_up_date = Some(next.format("%Y-%m-%d").to_string());
+                true
+            }
+            None => false,
+        }
+    });
+    items.sort_by(|a, b| a.follow_up_date.cmp(&b.follow_up_date));
+
+    Ok(items)
+}
+
+/// Dates are stored as `YYYY-MM-DD`, timestamps as `YYYY-MM-DD HH:MM:SS`;
+/// either parses to its date
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// First occurrence of a series starting at `start` that falls on or after
+/// `today`. Monthly steps are counted from `start` rather than chained, so a
+/// series on the 31st doesn't drift to the 28th after February.
+fn next_occurrence(
+    start: NaiveDate,
+    recurrence: FollowUpRecurrence,
+    until: Option<NaiveDate>,
+    today: NaiveDate,
+) -> Option<NaiveDate> {
+    let nth = |n: u32| match recurrence {
+        FollowUpRecurrence::None => Some(start),
+        FollowUpRecurrence::Weekly => start.checked_add_days(Days::new(7 * u64::from(n))),
+        FollowUpRecurrence::Monthly => start.checked_add_months(Months::new(n)),
+        FollowUpRecurrence::Quarterly => start.checked_add_months(Months::new(3 * n)),
+    };
+
+    let mut n = 0;
+    let next = loop {
+        let date = nth(n)?;
+        if date >= today || recurrence == FollowUpRecurrence::None {
+            break date;
+        }
+        n += 1;
+    };
+    match until {
+        Some(until) if next > until => None,
+        _ => Some(next),
+    }
 }
 
 /// Create a system event entry. Finds or auto-creates a "System Activity" conversation.
@@ -142,3 +315,57 @@ pub fn create_system_event(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn next(start: &str, recurrence: FollowUpRecurrence, until: Option<&str>, today: &str) -> Option<NaiveDate> {
+        next_occurrence(d(start), recurrence, until.map(d), d(today))
+    }
+
+    #[test]
+    fn test_next_occurrence_one_off() {
+        // A one-off follow-up stays on its date, even once it's overdue
+        assert_eq!(next("2026-01-10", FollowUpRecurrence::None, None, "2026-03-01"), Some(d("2026-01-10")));
+        assert_eq!(next("2026-01-10", FollowUpRecurrence::None, Some("2026-01-05"), "2026-01-01"), None);
+    }
+
+    #[test]
+    fn test_next_occurrence_weekly() {
+        assert_eq!(next("2026-01-01", FollowUpRecurrence::Weekly, None, "2026-01-20"), Some(d("2026-01-22")));
+        // An occurrence falling on today is still due today
+        assert_eq!(next("2026-01-01", FollowUpRecurrence::Weekly, None, "2026-01-15"), Some(d("2026-01-15")));
+        // A series that hasn't started yet begins at its start date
+        assert_eq!(next("2026-02-01", FollowUpRecurrence::Weekly, None, "2026-01-15"), Some(d("2026-02-01")));
+    }
+
+    #[test]
+    fn test_next_occurrence_month_end() {
+        assert_eq!(next("2026-01-31", FollowUpRecurrence::Monthly, None, "2026-02-15"), Some(d("2026-02-28")));
+        // Counted from the start, so March is back on the 31st
+        assert_eq!(next("2026-01-31", FollowUpRecurrence::Monthly, None, "2026-03-01"), Some(d("2026-03-31")));
+        assert_eq!(next("2024-01-31", FollowUpRecurrence::Monthly, None, "2024-02-01"), Some(d("2024-02-29")));
+    }
+
+    #[test]
+    fn test_next_occurrence_quarterly() {
+        assert_eq!(next("2025-11-30", FollowUpRecurrence::Quarterly, None, "2026-01-01"), Some(d("2026-02-28")));
+        assert_eq!(next("2025-11-30", FollowUpRecurrence::Quarterly, None, "2026-03-01"), Some(d("2026-05-30")));
+    }
+
+    #[test]
+    fn test_next_occurrence_until() {
+        // The series ends once the next occurrence would pass `until`
+        assert_eq!(next("2026-01-15", FollowUpRecurrence::Monthly, Some("2026-03-01"), "2026-03-10"), None);
+        // `until` itself is still included
+        assert_eq!(
+            next("2026-01-15", FollowUpRecurrence::Monthly, Some("2026-03-15"), "2026-03-10"),
+            Some(d("2026-03-15"))
+        );
+    }
+}
@@ -1,8 +1,47 @@
+use chrono::{Datelike, NaiveDate};
 use rusqlite::Connection;
 use crate::error::AppError;
-use crate::models::report::DashboardStats;
+use crate::models::report::{ActiveEnrollmentPeriod, DashboardStats, EnrollmentPeriodWindow};
 use crate::repositories::report_repo;
 
 pub fn get_dashboard_stats(conn: &Connection) -> Result<DashboardStats, AppError> {
-    report_repo::get_dashboard_stats(conn)
+    let mut stats = report_repo::get_dashboard_stats(conn)?;
+    let today = chrono::Local::now().date_naive();
+    stats.active_enrollment_periods =
+        active_enrollment_periods(&report_repo::get_fixed_enrollment_periods(conn)?, today);
+    Ok(stats)
+}
+
+/// Match today against each period's month/day window. A window whose end
+/// falls before its start in the calendar (e.g. Dec 1 – Feb 15) runs into
+/// the following year, so both this year's and last year's occurrence are
+/// checked.
+fn active_enrollment_periods(
+    periods: &[EnrollmentPeriodWindow],
+    today: NaiveDate,
+) -> Vec<ActiveEnrollmentPeriod> {
+    let mut active = Vec::new();
+    for p in periods {
+        let wraps = (p.end_month, p.end_day) < (p.start_month, p.start_day);
+        for year in [today.year() - 1, today.year()] {
+            let end_year = if wraps { year + 1 } else { year };
+            let (Some(start), Some(end)) = (
+                NaiveDate::from_ymd_opt(year, p.start_month, p.start_day),
+                NaiveDate::from_ymd_opt(end_year, p.end_month, p.end_day),
+            ) else {
+                continue;
+            };
+            if start <= today && today <= end {
+                active.push(ActiveEnrollmentPeriod {
+                    code: p.code.clone(),
+                    name: p.name.clone(),
+                    end_date: end.format("%Y-%m-%d").to_string(),
+                    days_remaining: (end - today).num_days(),
+                });
+                break;
+            }
+        }
+    }
+    active.sort_by_key(|p| p.days_remaining);
+    active
 }
@@ -1,15 +1,156 @@
 use rusqlite::Connection;
 use uuid::Uuid;
 use crate::error::AppError;
-use crate::models::{Enrollment, EnrollmentListItem, CreateEnrollmentInput, UpdateEnrollmentInput};
-use crate::repositories::enrollment_repo;
-use crate::services::conversation_service;
+use crate::models::{Enrollment, EnrollmentHistoryItem, Plan, EnrollmentListItem, CreateEnrollmentInput, UpdateEnrollmentInput, PaginatedResult};
+use crate::repositories::{enrollment_repo, plan_repo};
+use crate::services::{audit_service, client_service, conversation_service};
 
 pub fn get_enrollments(conn: &Connection, client_id: Option<&str>) -> Result<Vec<EnrollmentListItem>, AppError> {
     enrollment_repo::get_enrollments(conn, client_id)
 }
 
+pub fn get_enrollment_history(conn: &Connection, client_id: &str) -> Result<Vec<EnrollmentHistoryItem>, AppError> {
+    enrollment_repo::get_enrollment_history(conn, client_id)
+}
+
+pub fn get_enrollments_page(conn: &Connection, page: i32, per_page: i32) -> Result<PaginatedResult<EnrollmentListItem>, AppError> {
+    let page = if page < 1 { 1 } else { page };
+    let per_page = per_page.clamp(1, client_service::max_page_size(conn)?);
+    enrollment_repo::get_enrollments_page(conn, page, per_page)
+}
+
+const COMMISSION_STATUSES: &[&str] = &["PENDING", "PAID", "CHARGEBACK"];
+
+fn validate_commission_status(status: Option<&str>) -> Result<(), AppError> {
+    match status {
+        Some(s) if !COMMISSION_STATUSES.contains(&s) => Err(AppError::Validation(format!(
+            "Commission status must be one of {}, got {}",
+            COMMISSION_STATUSES.join(", "),
+            s
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Reject status and plan type codes that aren't in the seeded lookup tables
+fn validate_codes(conn: &Connection, status_code: Option<&str>, plan_type_code: Option<&str>) -> Result<(), AppError> {
+    if let Some(code) = status_code {
+        if !enrollment_repo::status_code_exists(conn, code)? {
+            return Err(AppError::Validation(format!("Unknown enrollment status: {}", code)));
+        }
+    }
+    if let Some(code) = plan_type_code {
+        if !enrollment_repo::plan_type_exists(conn, code)? {
+            return Err(AppError::Validation(format!("Unknown plan type: {}", code)));
+        }
+    }
+    Ok(())
+}
+
+/// Dates must be YYYY-MM-DD, and termination can't precede effective.
+/// All problems are reported together.
+fn validate_dates(
+    effective_date: Option<&str>,
+    termination_date: Option<&str>,
+    application_date: Option<&str>,
+) -> Result<(), AppError> {
+    let mut problems = Vec::new();
+    let mut parse = |label: &str, value: Option<&str>| {
+        value.filter(|v| !v.is_empty()).and_then(|v| {
+            let parsed = chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d").ok();
+            if parsed.is_none() {
+                problems.push(format!("{} must be a YYYY-MM-DD date, got {}", label, v));
+            }
+            parsed
+        })
+    };
+    let effective = parse("effective_date", effective_date);
+    let termination = parse("termination_date", termination_date);
+    parse("application_date", application_date);
+
+    if let (Some(effective), Some(termination)) = (effective, termination) {
+        if termination < effective {
+            problems.push(format!(
+                "termination_date {} is before effective_date {}",
+                termination, effective
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(problems.join("; ")))
+    }
+}
+
+/// Denormalized plan columns carried by both enrollment inputs
+struct PlanFields<'a> {
+    carrier_id: &'a mut Option<String>,
+    plan_type_code: &'a mut Option<String>,
+    plan_name: &'a mut Option<String>,
+    contract_number: &'a mut Option<String>,
+    pbp_number: &'a mut Option<String>,
+    premium: &'a mut Option<f64>,
+}
+
+/// Fill the fields the caller left empty from the linked catalog plan, so the
+/// denormalized columns agree with the plan
+fn fill_from_linked_plan(conn: &Connection, plan_id: Option<&str>, fields: PlanFields) -> Result<(), AppError> {
+    let Some(plan) = linked_plan(conn, plan_id)? else {
+        return Ok(());
+    };
+    fields.carrier_id.get_or_insert(plan.carrier_id);
+    fields.plan_type_code.get_or_insert(plan.plan_type_code);
+    fields.plan_name.get_or_insert(plan.plan_name);
+    if fields.contract_number.is_none() {
+        *fields.contract_number = plan.contract_number;
+    }
+    if fields.pbp_number.is_none() {
+        *fields.pbp_number = plan.pbp_number;
+    }
+    if fields.premium.is_none() {
+        *fields.premium = plan.premium;
+    }
+    Ok(())
+}
+
+/// Catalog plan an enrollment links to
+fn linked_plan(conn: &Connection, plan_id: Option<&str>) -> Result<Option<Plan>, AppError> {
+    let Some(plan_id) = plan_id else {
+        return Ok(None);
+    };
+    match plan_repo::get_plan(conn, plan_id) {
+        Ok(plan) => Ok(Some(plan)),
+        Err(AppError::NotFound(_)) => Err(AppError::Validation(format!("Unknown plan: {}", plan_id))),
+        Err(e) => Err(e),
+    }
+}
+
 pub fn create_enrollment(conn: &Connection, input: &CreateEnrollmentInput) -> Result<Enrollment, AppError> {
+    let mut input = input.clone();
+    fill_from_linked_plan(
+        conn,
+        input.plan_id.as_deref(),
+        PlanFields {
+            carrier_id: &mut input.carrier_id,
+            plan_type_code: &mut input.plan_type_code,
+            plan_name: &mut input.plan_name,
+            contract_number: &mut input.contract_number,
+            pbp_number: &mut input.pbp_number,
+            premium: &mut input.premium,
+        },
+    )?;
+    let input = &input;
+
+    validate_commission_status(input.commission_status.as_deref())?;
+    validate_codes(conn, input.status_code.as_deref(), input.plan_type_code.as_deref())?;
+    validate_dates(
+        input.effective_date.as_deref(),
+        input.termination_date.as_deref(),
+        input.application_date.as_deref(),
+    )?;
+
     // Business rule: only one active/pending enrollment per plan category per client
     if let Some(ref plan_type_code) = input.plan_type_code {
         if enrollment_repo::has_active_enrollment_in_category(conn, &input.client_id, plan_type_code, None)? {
@@ -20,45 +161,241 @@ pub fn create_enrollment(conn: &Connection, input: &CreateEnrollmentInput) -> Re
     }
 
     let id = Uuid::new_v4().to_string();
-    enrollment_repo::create_enrollment(conn, &id, input)?;
+    audit_service::in_transaction(conn, |tx| {
+        enrollment_repo::create_enrollment(tx, &id, input)?;
 
-    let enrollment = enrollment_repo::get_enrollment(conn, &id)?;
+        let enrollment = enrollment_repo::get_enrollment(tx, &id)?;
+        audit_service::record_create(tx, audit_service::ENROLLMENT, &id, &enrollment)?;
 
-    // Generate system event for enrollment creation
-    let event_data = serde_json::json!({
-        "enrollment_id": enrollment.id,
-        "plan_name": enrollment.plan_name,
-        "status": enrollment.status_code,
+        // Generate system event for enrollment creation
+        let event_data = serde_json::json!({
+            "enrollment_id": enrollment.id,
+            "plan_name": enrollment.plan_name,
+            "status": enrollment.status_code,
+        })
+        .to_string();
+        let _ = conversation_service::create_system_event(
+            tx,
+            &input.client_id,
+            "ENROLLMENT_CREATED",
+            Some(&event_data),
+        );
+
+        Ok(enrollment)
     })
-    .to_string();
-    let _ = conversation_service::create_system_event(
+}
+
+pub fn update_enrollment(conn: &Connection, id: &str, input: &UpdateEnrollmentInput) -> Result<Enrollment, AppError> {
+    let mut input = input.clone();
+    fill_from_linked_plan(
         conn,
-        &input.client_id,
-        "ENROLLMENT_CREATED",
-        Some(&event_data),
-    );
+        input.plan_id.as_deref(),
+        PlanFields {
+            carrier_id: &mut input.carrier_id,
+            plan_type_code: &mut input.plan_type_code,
+            plan_name: &mut input.plan_name,
+            contract_number: &mut input.contract_number,
+            pbp_number: &mut input.pbp_number,
+            premium: &mut input.premium,
+        },
+    )?;
+    let input = &input;
 
-    Ok(enrollment)
+    validate_commission_status(input.commission_status.as_deref())?;
+    validate_codes(conn, input.status_code.as_deref(), input.plan_type_code.as_deref())?;
+
+    // Fields not in the input keep their stored values (COALESCE in the repo)
+    let existing = enrollment_repo::get_enrollment(conn, id)?;
+    let status = input.status_code.clone().or(existing.status_code.clone());
+    let plan_type_code = input.plan_type_code.clone().or(existing.plan_type_code.clone());
+    let is_active = input.is_active.or(existing.is_active).unwrap_or(true);
+
+    // Only the dates being changed must parse; ordering uses the merged values
+    validate_dates(
+        input.effective_date.as_deref(),
+        input.termination_date.as_deref(),
+        input.application_date.as_deref(),
+    )?;
+    let stored_valid = |d: Option<String>| {
+        d.filter(|v| chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d").is_ok())
+    };
+    validate_dates(
+        input.effective_date.clone().or(stored_valid(existing.effective_date.clone())).as_deref(),
+        input.termination_date.clone().or(stored_valid(existing.termination_date.clone())).as_deref(),
+        None,
+    )?;
+
+    // A chargeback only makes sense once the member has left the plan
+    if input.commission_status.as_deref() == Some("CHARGEBACK")
+        && !status.as_deref().is_some_and(|s| s.starts_with("DISENROLLED"))
+    {
+        return Err(AppError::Validation(
+            "Only disenrolled enrollments can be flagged as a commission chargeback".to_string(),
+        ));
+    }
+
+    // Business rule: only one active/pending enrollment per plan category per client
+    let is_open = matches!(status.as_deref(), Some("ACTIVE") | Some("PENDING"));
+    if let (true, true, Some(ref plan_type_code)) = (is_active, is_open, &plan_type_code) {
+        if enrollment_repo::has_active_enrollment_in_category(conn, &existing.client_id, plan_type_code, Some(id))? {
+            return Err(AppError::Validation(
+                "Client already has an active or pending enrollment in this plan category".to_string()
+            ));
+        }
+    }
+
+    audit_service::in_transaction(conn, |tx| {
+        enrollment_repo::update_enrollment(tx, id, input)?;
+
+        let enrollment = enrollment_repo::get_enrollment(tx, id)?;
+        audit_service::record_update(tx, audit_service::ENROLLMENT, id, &existing, &enrollment)?;
+
+        // Generate system event for enrollment update
+        let event_data = serde_json::json!({
+            "enrollment_id": enrollment.id,
+            "plan_name": enrollment.plan_name,
+            "status": enrollment.status_code,
+        })
+        .to_string();
+        let _ = conversation_service::create_system_event(
+            tx,
+            &enrollment.client_id,
+            "ENROLLMENT_UPDATED",
+            Some(&event_data),
+        );
+
+        Ok(enrollment)
+    })
 }
 
-pub fn update_enrollment(conn: &Connection, id: &str, input: &UpdateEnrollmentInput) -> Result<Enrollment, AppError> {
-    enrollment_repo::update_enrollment(conn, id, input)?;
+/// Enrollments still in force going into `plan_year` that haven't been
+/// rolled over yet
+pub fn detect_renewable_enrollments(conn: &Connection, plan_year: i32) -> Result<Vec<EnrollmentListItem>, AppError> {
+    if !(2000..=2100).contains(&plan_year) {
+        return Err(AppError::Validation(format!("Invalid plan year: {}", plan_year)));
+    }
+    enrollment_repo::get_renewable_enrollments(conn, &format!("{}-01-01", plan_year))
+}
 
-    let enrollment = enrollment_repo::get_enrollment(conn, id)?;
+/// Roll an enrollment over: the old one is closed out as RENEWED the day
+/// before `new_effective_date`, and a copy starting on that date takes its
+/// place. The old record is closed first so the category rule still holds.
+pub fn renew_enrollment(conn: &Connection, id: &str, new_effective_date: &str) -> Result<Enrollment, AppError> {
+    validate_dates(Some(new_effective_date), None, None)?;
+    let new_effective = chrono::NaiveDate::parse_from_str(new_effective_date, "%Y-%m-%d")
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let old = enrollment_repo::get_enrollment(conn, id)?;
+    if old.is_active == Some(false) || old.status_code.as_deref() != Some("ACTIVE") {
+        return Err(AppError::Validation("Only active enrollments can be renewed".to_string()));
+    }
+    if old.effective_date.as_deref().is_some_and(|d| d >= new_effective_date) {
+        return Err(AppError::Validation(
+            "The renewal must start after the current enrollment's effective date".to_string(),
+        ));
+    }
+    let termination_date = new_effective
+        .pred_opt()
+        .map(|d| d.format("%Y-%m-%d").to_string());
+
+    let tx = conn.unchecked_transaction()?;
+
+    enrollment_repo::update_enrollment(&tx, id, &UpdateEnrollmentInput {
+        termination_date: termination_date.clone(),
+        status_code: Some("RENEWED".to_string()),
+        ..Default::default()
+    })?;
+    let closed = enrollment_repo::get_enrollment(&tx, id)?;
+    audit_service::record_update(&tx, audit_service::ENROLLMENT, id, &old, &closed)?;
+
+    let new_id = Uuid::new_v4().to_string();
+    enrollment_repo::create_enrollment(&tx, &new_id, &CreateEnrollmentInput {
+        client_id: old.client_id.clone(),
+        plan_id: old.plan_id.clone(),
+        carrier_id: old.carrier_id.clone(),
+        plan_type_code: old.plan_type_code.clone(),
+        plan_name: old.plan_name.clone(),
+        contract_number: old.contract_number.clone(),
+        pbp_number: old.pbp_number.clone(),
+        effective_date: Some(new_effective_date.to_string()),
+        termination_date: None,
+        application_date: None,
+        status_code: Some("ACTIVE".to_string()),
+        enrollment_period: old.enrollment_period.clone(),
+        disenrollment_reason: None,
+        premium: old.premium,
+        confirmation_number: None,
+        enrollment_source: Some("renewal".to_string()),
+        commission_amount: None,
+        commission_status: None,
+        commission_paid_date: None,
+    })?;
+    let renewed = enrollment_repo::get_enrollment(&tx, &new_id)?;
+    audit_service::record_create(&tx, audit_service::ENROLLMENT, &new_id, &renewed)?;
 
-    // Generate system event for enrollment update
     let event_data = serde_json::json!({
-        "enrollment_id": enrollment.id,
-        "plan_name": enrollment.plan_name,
-        "status": enrollment.status_code,
+        "enrollment_id": new_id,
+        "previous_enrollment_id": old.id,
+        "plan_name": old.plan_name,
+        "previous_termination_date": termination_date,
+        "effective_date": new_effective_date,
     })
     .to_string();
     let _ = conversation_service::create_system_event(
-        conn,
-        &enrollment.client_id,
-        "ENROLLMENT_UPDATED",
+        &tx,
+        &old.client_id,
+        "RENEWAL",
         Some(&event_data),
     );
 
-    Ok(enrollment)
+    tx.commit()?;
+    Ok(renewed)
+}
+
+/// Soft-delete an enrollment; it drops out of lists and the category rule
+pub fn delete_enrollment(conn: &Connection, id: &str) -> Result<(), AppError> {
+    audit_service::in_transaction(conn, |tx| {
+        enrollment_repo::set_enrollment_active(tx, id, false)?;
+        audit_service::record(tx, audit_service::ENROLLMENT, id, "DELETE", None)?;
+        log_enrollment_event(tx, id, "ENROLLMENT_DELETED");
+        Ok(())
+    })
+}
+
+/// Undo a soft-delete. Refused if the client has since gained another
+/// active or pending enrollment in the same plan category.
+pub fn restore_enrollment(conn: &Connection, id: &str) -> Result<Enrollment, AppError> {
+    let enrollment = enrollment_repo::get_enrollment(conn, id)?;
+    let is_open = matches!(enrollment.status_code.as_deref(), Some("ACTIVE") | Some("PENDING"));
+    if let (true, Some(ref plan_type_code)) = (is_open, &enrollment.plan_type_code) {
+        if enrollment_repo::has_active_enrollment_in_category(conn, &enrollment.client_id, plan_type_code, Some(id))? {
+            return Err(AppError::Validation(
+                "Client already has an active or pending enrollment in this plan category".to_string()
+            ));
+        }
+    }
+
+    audit_service::in_transaction(conn, |tx| {
+        enrollment_repo::set_enrollment_active(tx, id, true)?;
+        audit_service::record(tx, audit_service::ENROLLMENT, id, "RESTORE", None)?;
+        log_enrollment_event(tx, id, "ENROLLMENT_RESTORED");
+        enrollment_repo::get_enrollment(tx, id)
+    })
+}
+
+fn log_enrollment_event(conn: &Connection, id: &str, event_type: &str) {
+    if let Ok(enrollment) = enrollment_repo::get_enrollment(conn, id) {
+        let event_data = serde_json::json!({
+            "enrollment_id": enrollment.id,
+            "plan_name": enrollment.plan_name,
+            "status": enrollment.status_code,
+        })
+        .to_string();
+        let _ = conversation_service::create_system_event(
+            conn,
+            &enrollment.client_id,
+            event_type,
+            Some(&event_data),
+        );
+    }
 }
@@ -170,6 +170,7 @@ pub fn import_call_log_from_db(
             None => {
                 unmatched += 1;
                 unmatched_details.push(ImportRowDetail {
+                    row_number: None,
                     label: lead_label,
                     detail: format!(
                         "MBI: {}",
@@ -203,6 +204,7 @@ pub fn import_call_log_from_db(
         if already_exists {
             skipped += 1;
             skipped_details.push(ImportRowDetail {
+                row_number: None,
                 label: lead_label,
                 detail: "Duplicate entry".to_string(),
             });
@@ -213,7 +215,7 @@ pub fn import_call_log_from_db(
         let entry_id = Uuid::new_v4().to_string();
 
         // For calls: set call_direction, call_outcome, call_phone_number
-        // For texts: leave call fields NULL
+        // For texts: only the direction; the dialer only logs texts it sent
         let (call_direction, call_outcome, call_phone_number): (
             Option<&str>,
             Option<&str>,
@@ -225,7 +227,7 @@ pub fn import_call_log_from_db(
                 src.lead_phone.as_deref(),
             )
         } else {
-            (None, None, None)
+            (Some("OUTBOUND"), None, None)
         };
 
         app_conn.execute(
@@ -262,6 +264,7 @@ pub fn import_call_log_from_db(
 
         imported += 1;
         imported_details.push(ImportRowDetail {
+            row_number: None,
             label: lead_label,
             detail: format!("{} - {}", entry_type, subject),
         });
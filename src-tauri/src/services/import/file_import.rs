@@ -1,16 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use calamine::Reader;
-use rusqlite::Connection;
+use rusqlite::{Connection, Transaction};
 
 use crate::error::AppError;
-use crate::services::conversation_service;
+use crate::models::CreateEnrollmentInput;
+use crate::repositories::client_repo;
+use crate::services::{audit_service, client_service, conversation_service, enrollment_service, plan_service};
+use crate::services::plan_service::ImportedPlan;
 use crate::services::matching;
 use super::shared::{ImportClientData, insert_client};
 
 /// Fields that should be normalized before storage/comparison.
 fn normalize_field_value(field: &str, val: String) -> String {
     match field {
-        "dob" => matching::normalize_date(&val).unwrap_or(val),
+        "dob" | "effective_date" | "termination_date" => {
+            matching::normalize_date(&val).unwrap_or(val)
+        }
         "mbi" => matching::normalize_mbi(&val).unwrap_or(val),
         "phone" | "phone2" => matching::normalize_phone(&val).unwrap_or(val),
         _ => val,
@@ -29,7 +34,11 @@ pub struct ParsedFile {
 #[derive(serde::Serialize)]
 pub struct ValidationResult {
     pub valid_rows: Vec<Vec<String>>,
+    /// 1-indexed source row number of each entry in `valid_rows`
+    pub valid_row_numbers: Vec<usize>,
     pub error_rows: Vec<ErrorRow>,
+    /// Rows that will still import but have data-quality warnings
+    pub warning_rows: Vec<ErrorRow>,
     pub total: usize,
 }
 
@@ -38,10 +47,15 @@ pub struct ErrorRow {
     pub row_number: usize,
     pub data: Vec<String>,
     pub errors: Vec<String>,
+    /// Non-fatal issues (bad ZIP, unknown state, unparseable phone)
+    pub warnings: Vec<String>,
 }
 
 #[derive(serde::Serialize, Clone)]
 pub struct ImportRowDetail {
+    /// 1-indexed row number in the source file, when the import is row-based
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_number: Option<usize>,
     pub label: String,
     pub detail: String,
 }
@@ -53,6 +67,7 @@ pub struct ImportResult {
     pub skipped: usize,
     pub errors: usize,
     pub total: usize,
+    pub enrollments_created: usize,
     pub inserted_details: Vec<ImportRowDetail>,
     pub updated_details: Vec<ImportRowDetail>,
     pub skipped_details: Vec<ImportRowDetail>,
@@ -139,8 +154,77 @@ fn parse_xlsx(file_path: &str) -> Result<ParsedFile, AppError> {
     })
 }
 
-/// Auto-map source column headers to target fields using fuzzy matching
-pub fn auto_map_columns(headers: &[String]) -> HashMap<String, String> {
+/// An auto-mapped column: the target field and how confident the guess is.
+/// Exact alias matches score 1.0; fuzzy matches score below that so the UI
+/// can ask for review.
+#[derive(serde::Serialize, Clone)]
+pub struct ColumnGuess {
+    pub target: String,
+    pub confidence: f64,
+}
+
+/// Minimum score for a fuzzy header match to be used at all
+const FUZZY_MAP_THRESHOLD: f64 = 0.7;
+/// Fuzzy matches never reach the confidence of an exact alias match
+const FUZZY_MAP_MAX_CONFIDENCE: f64 = 0.95;
+
+/// Split a normalized header into alphanumeric tokens
+fn header_tokens(s: &str) -> Vec<&str> {
+    s.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Score how closely a normalized header resembles an alias, 0.0-1.0.
+/// Takes the better of whole-string edit similarity (typos like "birthdte")
+/// and token overlap (extra words like "Member DOB", abbreviations like
+/// "Primary Ph #" where "ph" is a prefix of "phone").
+fn alias_similarity(header: &str, alias: &str) -> f64 {
+    let mut best = 0.0;
+
+    let (h_len, a_len) = (header.chars().count(), alias.chars().count());
+    if h_len.min(a_len) >= 4 {
+        let dist = matching::levenshtein(header, alias);
+        best = 1.0 - dist as f64 / h_len.max(a_len) as f64;
+    }
+
+    let h_tokens = header_tokens(header);
+    let a_tokens = header_tokens(alias);
+    if h_tokens.is_empty() || a_tokens.is_empty() {
+        return best;
+    }
+    let mut matched_alias = 0usize;
+    let mut penalty = 1.0;
+    for at in &a_tokens {
+        if h_tokens.contains(at) {
+            matched_alias += 1;
+        } else if h_tokens
+            .iter()
+            .any(|ht| ht.len() >= 2 && at.starts_with(ht))
+        {
+            matched_alias += 1;
+            penalty *= 0.9;
+        }
+    }
+    let matched_header = h_tokens
+        .iter()
+        .filter(|ht| a_tokens.iter().any(|at| at == *ht || (ht.len() >= 2 && at.starts_with(*ht))))
+        .count();
+    let alias_cov = matched_alias as f64 / a_tokens.len() as f64;
+    let header_cov = matched_header as f64 / h_tokens.len() as f64;
+    let token_score = (alias_cov * 0.7 + header_cov * 0.3) * penalty;
+
+    if token_score > best {
+        token_score
+    } else {
+        best
+    }
+}
+
+/// Auto-map source column headers to target fields. Exact alias matches are
+/// taken first; remaining headers fall back to a fuzzy match against the
+/// alias lists, each target being claimed by at most one fuzzy guess.
+pub fn auto_map_columns(headers: &[String]) -> HashMap<String, ColumnGuess> {
     let aliases: HashMap<&str, Vec<&str>> = HashMap::from([
         (
             "first_name",
@@ -388,6 +472,7 @@ pub fn auto_map_columns(headers: &[String]) -> HashMap<String, String> {
     ]);
 
     let mut mapping = HashMap::new();
+    let mut unmapped: Vec<(&String, String)> = Vec::new();
 
     for header in headers {
         let mut normalized = header.trim().to_lowercase().replace(['_', '-'], " ");
@@ -396,44 +481,144 @@ pub fn auto_map_columns(headers: &[String]) -> HashMap<String, String> {
             normalized = normalized[..pos].trim().to_string();
         }
 
+        let exact = aliases
+            .iter()
+            .find(|(_, alias_list)| alias_list.iter().any(|a| *a == normalized));
+        match exact {
+            Some((target, _)) => {
+                mapping.insert(
+                    header.clone(),
+                    ColumnGuess { target: target.to_string(), confidence: 1.0 },
+                );
+            }
+            None => unmapped.push((header, normalized)),
+        }
+    }
+
+    // Fuzzy fallback: score every (header, target) pair, then hand out the
+    // best-scoring pairs first so two headers can't both guess the same field.
+    let mut candidates: Vec<(f64, &String, &str)> = Vec::new();
+    for (header, normalized) in &unmapped {
         for (target, alias_list) in &aliases {
-            if alias_list.iter().any(|a| *a == normalized) {
-                mapping.insert(header.clone(), target.to_string());
-                break;
+            let score = alias_list
+                .iter()
+                .map(|a| alias_similarity(normalized, a))
+                .fold(0.0, f64::max);
+            if score >= FUZZY_MAP_THRESHOLD {
+                candidates.push((score, header, target));
             }
         }
     }
+    candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    for (score, header, target) in candidates {
+        if mapping.contains_key(header.as_str()) || mapping.values().any(|g: &ColumnGuess| g.target == target) {
+            continue;
+        }
+        mapping.insert(
+            header.clone(),
+            ColumnGuess {
+                target: target.to_string(),
+                confidence: (score * FUZZY_MAP_MAX_CONFIDENCE * 100.0).round() / 100.0,
+            },
+        );
+    }
 
     mapping
 }
 
-/// Validate import rows based on column mapping
-pub fn validate_rows(
-    all_rows: &[Vec<String>],
+/// Load the known 2-letter state codes used to validate imported addresses
+pub fn load_state_codes(conn: &Connection) -> Result<HashSet<String>, AppError> {
+    let mut stmt = conn.prepare("SELECT code FROM states")?;
+    let codes = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<HashSet<_>, _>>()?;
+    Ok(codes)
+}
+
+/// Check ZIP, state, and phone formats. These never block a row; they are
+/// reported so the agent can clean up the data afterwards.
+fn row_warnings(
+    row: &[String],
     headers: &[String],
     mapping: &HashMap<String, String>,
-) -> ValidationResult {
-    let mut valid_rows = Vec::new();
-    let mut error_rows = Vec::new();
+    known_states: &HashSet<String>,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let get = |field: &str| {
+        find_mapped_index(headers, mapping, field)
+            .and_then(|idx| row.get(idx))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    };
+
+    if let Some(zip) = get("zip") {
+        let digits: String = zip.chars().filter(|c| c.is_ascii_digit()).collect();
+        let well_formed = zip.chars().all(|c| c.is_ascii_digit() || c == '-' || c == ' ');
+        if !well_formed || (digits.len() != 5 && digits.len() != 9) {
+            warnings.push(format!("ZIP should be 5 or 9 digits: '{}'", zip));
+        }
+    }
+
+    if let Some(state) = get("state") {
+        if !known_states.is_empty() && !known_states.contains(&state.to_uppercase()) {
+            warnings.push(format!("Unknown state code: '{}'", state));
+        }
+    }
+
+    if let Some(phone) = get("phone") {
+        if matching::normalize_phone(phone).is_none() {
+            warnings.push(format!("Phone is not a valid 10-digit number: '{}'", phone));
+        }
+    }
 
-    // Find index for key columns
-    let first_name_idx = find_mapped_index(headers, mapping, "first_name");
-    let last_name_idx = find_mapped_index(headers, mapping, "last_name");
-    let mbi_idx = find_mapped_index(headers, mapping, "mbi");
-    let dob_idx = find_mapped_index(headers, mapping, "dob");
+    warnings
+}
 
+/// Per-row validation state shared by the preview (`validate_rows`) and the
+/// streaming import path, so both apply the same rules and duplicate checks.
+struct RowValidator<'a> {
+    headers: &'a [String],
+    mapping: &'a HashMap<String, String>,
+    known_states: &'a HashSet<String>,
+    first_name_idx: Option<usize>,
+    last_name_idx: Option<usize>,
+    mbi_idx: Option<usize>,
+    dob_idx: Option<usize>,
     // Track seen rows for within-file duplicate detection.
     // Key: (lowercase first_name, lowercase last_name, lowercase mbi_or_empty, dob_or_empty)
-    let mut seen: HashMap<(String, String, String, String), usize> = HashMap::new();
+    seen: HashMap<(String, String, String, String), usize>,
+}
 
-    for (i, row) in all_rows.iter().enumerate() {
+impl<'a> RowValidator<'a> {
+    fn new(
+        headers: &'a [String],
+        mapping: &'a HashMap<String, String>,
+        known_states: &'a HashSet<String>,
+    ) -> Self {
+        Self {
+            headers,
+            mapping,
+            known_states,
+            first_name_idx: find_mapped_index(headers, mapping, "first_name"),
+            last_name_idx: find_mapped_index(headers, mapping, "last_name"),
+            mbi_idx: find_mapped_index(headers, mapping, "mbi"),
+            dob_idx: find_mapped_index(headers, mapping, "dob"),
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Check one row (1-indexed `row_number`) and return its (errors, warnings)
+    fn check(&mut self, row_number: usize, row: &[String]) -> (Vec<String>, Vec<String>) {
         let mut errors = Vec::new();
 
-        let first = first_name_idx
+        let first = self
+            .first_name_idx
             .and_then(|idx| row.get(idx))
             .map(|v| v.trim().to_string())
             .unwrap_or_default();
-        let last = last_name_idx
+        let last = self
+            .last_name_idx
             .and_then(|idx| row.get(idx))
             .map(|v| v.trim().to_string())
             .unwrap_or_default();
@@ -447,19 +632,19 @@ pub fn validate_rows(
         }
 
         // Validate MBI format if present
-        let mbi_val = mbi_idx
+        let mbi_val = self
+            .mbi_idx
             .and_then(|idx| row.get(idx))
             .map(|v| v.trim().to_string())
             .unwrap_or_default();
-        if !mbi_val.is_empty()
-            && (mbi_val.len() != 11 || !mbi_val.chars().all(|c| c.is_ascii_alphanumeric()))
-        {
-            errors.push(format!("Invalid MBI format: '{}'", mbi_val));
+        if let Err(AppError::Validation(msg)) = matching::validate_mbi(&mbi_val) {
+            errors.push(format!("Invalid MBI '{}': {}", mbi_val, msg));
         }
 
         // Within-file duplicate detection
         if errors.is_empty() {
-            let dob_val = dob_idx
+            let dob_val = self
+                .dob_idx
                 .and_then(|idx| row.get(idx))
                 .map(|v| v.trim().to_string())
                 .unwrap_or_default();
@@ -469,20 +654,51 @@ pub fn validate_rows(
                 mbi_val.to_lowercase(),
                 dob_val.to_lowercase(),
             );
-            if let Some(&prev_row) = seen.get(&key) {
+            if let Some(&prev_row) = self.seen.get(&key) {
                 errors.push(format!("Duplicate of row {}", prev_row));
             } else {
-                seen.insert(key, i + 1);
+                self.seen.insert(key, row_number);
             }
         }
 
+        let warnings = row_warnings(row, self.headers, self.mapping, self.known_states);
+        (errors, warnings)
+    }
+}
+
+/// Validate import rows based on column mapping
+pub fn validate_rows(
+    all_rows: &[Vec<String>],
+    headers: &[String],
+    mapping: &HashMap<String, String>,
+    known_states: &HashSet<String>,
+) -> ValidationResult {
+    let mut valid_rows = Vec::new();
+    let mut valid_row_numbers = Vec::new();
+    let mut error_rows = Vec::new();
+    let mut warning_rows = Vec::new();
+    let mut validator = RowValidator::new(headers, mapping, known_states);
+
+    for (i, row) in all_rows.iter().enumerate() {
+        let (errors, warnings) = validator.check(i + 1, row);
+
         if errors.is_empty() {
             valid_rows.push(row.clone());
+            valid_row_numbers.push(i + 1);
+            if !warnings.is_empty() {
+                warning_rows.push(ErrorRow {
+                    row_number: i + 1,
+                    data: row.clone(),
+                    errors,
+                    warnings,
+                });
+            }
         } else {
             error_rows.push(ErrorRow {
                 row_number: i + 1, // 1-indexed
                 data: row.clone(),
                 errors,
+                warnings,
             });
         }
     }
@@ -490,7 +706,9 @@ pub fn validate_rows(
     let total = valid_rows.len() + error_rows.len();
     ValidationResult {
         valid_rows,
+        valid_row_numbers,
         error_rows,
+        warning_rows,
         total,
     }
 }
@@ -503,6 +721,7 @@ pub struct ImportPreview {
     pub updates: Vec<PreviewUpdate>,
     pub skipped: Vec<PreviewSkipped>,
     pub errors: Vec<ErrorRow>,
+    pub warnings: Vec<ErrorRow>,
 }
 
 #[derive(serde::Serialize)]
@@ -524,6 +743,9 @@ pub struct FieldDiff {
     pub field: String,
     pub old_value: String,
     pub new_value: String,
+    /// The client already has a value here, so the import would replace it
+    /// rather than fill a blank
+    pub replaces_existing: bool,
 }
 
 #[derive(serde::Serialize)]
@@ -533,12 +755,56 @@ pub struct PreviewSkipped {
     pub reason: String,
 }
 
-const UPDATABLE_FIELDS: &[&str] = &[
+/// Client columns an import may change on an existing client. Both the
+/// preview and the import itself go through `client_field_diffs`, so the
+/// preview shows exactly what an update would write.
+pub(super) const UPDATABLE_FIELDS: &[&str] = &[
     "phone", "email", "address_line1", "address_line2",
     "city", "state", "zip", "county",
     "dual_status_code", "lis_level", "medicaid_id", "notes",
 ];
 
+/// Field-level changes a row would make to an existing client. Blank import
+/// values are never written, and values that already match are left out.
+/// With `only_fill_empty`, fields the client already has a value for are left
+/// out too, so the import only enriches.
+fn client_field_diffs(
+    conn: &Connection,
+    client_id: &str,
+    get_val: &dyn Fn(&str) -> Option<String>,
+    only_fill_empty: bool,
+) -> Result<Vec<FieldDiff>, AppError> {
+    let columns: Vec<String> = UPDATABLE_FIELDS
+        .iter()
+        .map(|f| format!("COALESCE({}, '')", f))
+        .collect();
+    let current: Vec<String> = conn.query_row(
+        &format!("SELECT {} FROM clients WHERE id = ?1", columns.join(", ")),
+        rusqlite::params![client_id],
+        |row| (0..UPDATABLE_FIELDS.len()).map(|i| row.get(i)).collect(),
+    )?;
+
+    let mut diffs = Vec::new();
+    for (&field, current_val) in UPDATABLE_FIELDS.iter().zip(current) {
+        let Some(import_val) = get_val(field) else {
+            continue;
+        };
+        let replaces_existing = !current_val.trim().is_empty();
+        if replaces_existing && only_fill_empty {
+            continue;
+        }
+        if import_val.trim() != current_val.trim() {
+            diffs.push(FieldDiff {
+                field: field.to_string(),
+                replaces_existing,
+                old_value: current_val,
+                new_value: import_val,
+            });
+        }
+    }
+    Ok(diffs)
+}
+
 /// Build a preview of what the import will do (dry-run with DB lookup)
 pub fn preview_import(
     conn: &Connection,
@@ -546,6 +812,7 @@ pub fn preview_import(
     headers: &[String],
     mapping: &HashMap<String, String>,
     constant_values: &HashMap<String, String>,
+    only_fill_empty: bool,
 ) -> Result<ImportPreview, AppError> {
     let mut inserts = Vec::new();
     let mut updates = Vec::new();
@@ -579,29 +846,7 @@ pub fn preview_import(
         let existing_id = find_existing_client(conn, &first_name, &last_name, &mbi, &get_val);
 
         if let Some(client_id) = existing_id {
-            // Compare each updatable field
-            let mut diffs = Vec::new();
-            for &field in UPDATABLE_FIELDS {
-                let import_val = match get_val(field) {
-                    Some(v) => v,
-                    None => continue, // no value in import → skip (don't overwrite with blank)
-                };
-                let current_val: String = conn
-                    .query_row(
-                        &format!("SELECT COALESCE({}, '') FROM clients WHERE id = ?1", field),
-                        rusqlite::params![client_id],
-                        |row| row.get(0),
-                    )
-                    .unwrap_or_default();
-
-                if import_val.trim() != current_val.trim() {
-                    diffs.push(FieldDiff {
-                        field: field.to_string(),
-                        old_value: current_val,
-                        new_value: import_val,
-                    });
-                }
-            }
+            let diffs = client_field_diffs(conn, &client_id, &get_val, only_fill_empty)?;
 
             if diffs.is_empty() {
                 skipped.push(PreviewSkipped {
@@ -629,62 +874,159 @@ pub fn preview_import(
         inserts,
         updates,
         skipped,
-        errors: Vec::new(), // errors come from validation, merged by the command layer
+        errors: Vec::new(), // errors and warnings come from validation, merged by the command layer
+        warnings: Vec::new(),
     })
 }
 
-/// Execute the actual import - insert/update clients
-pub fn execute_import(
-    conn: &Connection,
-    rows: &[Vec<String>],
+/// Imports writing at least this many clients rebuild `clients_fts` at the end
+const FTS_REBUILD_MIN_ROWS: usize = 500;
+
+/// Execute the actual import - insert/update clients, and optionally create
+/// enrollments from each row's plan/carrier columns (`import_enrollments`).
+///
+/// Rows are pulled one at a time from `rows` (see `stream_rows`) and validated
+/// with the same rules as `validate_rows`, so memory stays bounded regardless
+/// of file size. Invalid rows and per-row write failures are counted as errors,
+/// and a failed row's partial writes are rolled back to its savepoint. The
+/// whole file is written in the caller's transaction `tx`, which the caller
+/// commits together with the import log row; an error (including a file that
+/// can't be read to the end) leaves it to be rolled back. `approved_inserts`
+/// indexes refer to valid rows in file order, matching the preview's
+/// `row_index`. `only_fill_empty` limits updates to fields the existing client
+/// has no value for.
+///
+/// Every client and enrollment written is tagged with `import_batch_id` (the
+/// import log id), and each client gets an IMPORT audit entry holding the
+/// values it replaced, so `undo_import` can reverse the file.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_import<I>(
+    tx: &mut Transaction<'_>,
+    rows: I,
     headers: &[String],
     mapping: &HashMap<String, String>,
     constant_values: &HashMap<String, String>,
+    known_states: &HashSet<String>,
     approved_updates: Option<&HashMap<String, Vec<String>>>,
     approved_inserts: Option<&Vec<usize>>,
-) -> Result<ImportResult, AppError> {
+    import_enrollments: bool,
+    only_fill_empty: bool,
+    import_batch_id: &str,
+    source_file: Option<&str>,
+) -> Result<ImportResult, AppError>
+where
+    I: IntoIterator<Item = Result<Vec<String>, AppError>>,
+{
     let mut inserted = 0usize;
     let mut updated = 0usize;
     let mut skipped = 0usize;
     let mut errors = 0usize;
+    let mut total = 0usize;
+    let mut enrollments_created = 0usize;
     let mut inserted_details = Vec::new();
     let mut updated_details = Vec::new();
     let mut skipped_details = Vec::new();
     let mut error_details = Vec::new();
 
-    for (i, row) in rows.iter().enumerate() {
-        match import_single_row(conn, row, i, headers, mapping, constant_values, approved_updates, approved_inserts) {
-            Ok(action) => match action {
-                ImportAction::Inserted { name } => {
-                    inserted += 1;
-                    inserted_details.push(ImportRowDetail { label: name, detail: String::new() });
-                }
-                ImportAction::Updated { name, fields } => {
-                    updated += 1;
-                    updated_details.push(ImportRowDetail { label: name, detail: fields.join(", ") });
+    let mut validator = RowValidator::new(headers, mapping, known_states);
+    let mut valid_index = 0usize;
+
+    for (i, row) in rows.into_iter().enumerate() {
+        let row = row?;
+        let row_number = i + 1;
+        total += 1;
+
+        let (row_errors, _) = validator.check(row_number, &row);
+        if !row_errors.is_empty() {
+            errors += 1;
+            error_details.push(ImportRowDetail {
+                row_number: Some(row_number),
+                label: "Validation failed".to_string(),
+                detail: row_errors.join("; "),
+            });
+            continue;
+        }
+        let row_index = valid_index;
+        valid_index += 1;
+
+        // Each row gets a savepoint so a failure part way through its writes
+        // is undone without losing the rows before it
+        let row_result = {
+            let sp = tx.savepoint()?;
+            let result = import_single_row(&sp, &row, row_index, headers, mapping, constant_values, approved_updates, approved_inserts, only_fill_empty, import_batch_id, source_file);
+            if result.is_ok() {
+                sp.commit()?;
+            }
+            result
+        };
+        match row_result {
+            Ok((action, client_id)) => {
+                // Enrollment mode: attach the row's plan/carrier data to the client
+                // in its own savepoint, so a failed enrollment keeps the client
+                if let (true, Some(client_id)) = (import_enrollments, client_id) {
+                    let enrollment_result = {
+                        let sp = tx.savepoint()?;
+                        let result = import_row_enrollment(&sp, &row, headers, mapping, constant_values, &client_id, import_batch_id);
+                        if result.is_ok() {
+                            sp.commit()?;
+                        }
+                        result
+                    };
+                    match enrollment_result {
+                        Ok(true) => enrollments_created += 1,
+                        Ok(false) => {}
+                        Err(e) => {
+                            tracing::warn!("Import enrollment error: {}", e);
+                            errors += 1;
+                            error_details.push(ImportRowDetail {
+                                row_number: Some(row_number),
+                                label: "Enrollment".to_string(),
+                                detail: e.to_string(),
+                            });
+                        }
+                    }
                 }
-                ImportAction::Skipped { name } => {
-                    skipped += 1;
-                    skipped_details.push(ImportRowDetail { label: name, detail: "No new data".to_string() });
+                match action {
+                    ImportAction::Inserted { name } => {
+                        inserted += 1;
+                        inserted_details.push(ImportRowDetail { row_number: Some(row_number), label: name, detail: String::new() });
+                    }
+                    ImportAction::Updated { name, fields } => {
+                        updated += 1;
+                        updated_details.push(ImportRowDetail { row_number: Some(row_number), label: name, detail: fields.join(", ") });
+                    }
+                    ImportAction::Skipped { name } => {
+                        skipped += 1;
+                        skipped_details.push(ImportRowDetail { row_number: Some(row_number), label: name, detail: "No new data".to_string() });
+                    }
                 }
-            },
+            }
             Err(e) => {
                 tracing::warn!("Import row error: {}", e);
                 errors += 1;
                 error_details.push(ImportRowDetail {
-                    label: format!("Row {}", i + 1),
+                    row_number: Some(row_number),
+                    label: "Import failed".to_string(),
                     detail: e.to_string(),
                 });
             }
         }
     }
+    // The FTS triggers already index every row written above; a large file
+    // gets a rebuild anyway so search is never left stale
+    if inserted + updated >= FTS_REBUILD_MIN_ROWS {
+        client_repo::rebuild_search_index(tx)?;
+    }
 
     Ok(ImportResult {
         inserted,
         updated,
         skipped,
         errors,
-        total: inserted + updated + skipped + errors,
+        // A row whose client imported but whose enrollment failed counts
+        // in both, so the total is the row count rather than their sum
+        total,
+        enrollments_created,
         inserted_details,
         updated_details,
         skipped_details,
@@ -698,6 +1040,7 @@ enum ImportAction {
     Skipped { name: String },
 }
 
+#[allow(clippy::too_many_arguments)]
 fn import_single_row(
     conn: &Connection,
     row: &[String],
@@ -707,7 +1050,10 @@ fn import_single_row(
     constant_values: &HashMap<String, String>,
     approved_updates: Option<&HashMap<String, Vec<String>>>,
     approved_inserts: Option<&Vec<usize>>,
-) -> Result<ImportAction, AppError> {
+    only_fill_empty: bool,
+    import_batch_id: &str,
+    source_file: Option<&str>,
+) -> Result<(ImportAction, Option<String>), AppError> {
     let get_raw = |target: &str| -> Option<String> {
         // Try column mapping first
         if let Some(idx) = find_mapped_index(headers, mapping, target) {
@@ -734,63 +1080,52 @@ fn import_single_row(
     let client_name = format!("{} {}", first_name, last_name);
 
     if let Some(client_id) = existing_id {
-        // If approved_updates is provided, check if this client was approved
+        // If approved_updates is provided, check if this client was approved.
+        // An unapproved client is still the row's match, so its enrollment
+        // is imported against it.
         if let Some(approved) = approved_updates {
             match approved.get(&client_id) {
-                None => return Ok(ImportAction::Skipped { name: client_name }),
+                None => return Ok((ImportAction::Skipped { name: client_name }, Some(client_id))),
                 Some(approved_fields) if approved_fields.is_empty() => {
-                    return Ok(ImportAction::Skipped { name: client_name });
+                    return Ok((ImportAction::Skipped { name: client_name }, Some(client_id)));
                 }
                 _ => {}
             }
         }
-        // Update existing client
+        // Update existing client with the same diff the preview showed
         let mut sets = Vec::new();
         let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
         let mut idx = 1;
         let mut updated_fields = Vec::new();
+        let mut changes = serde_json::Map::new();
 
         // Get the approved field list for this client (if approval filtering is active)
         let approved_field_list: Option<&Vec<String>> =
             approved_updates.and_then(|a| a.get(&client_id));
 
-        macro_rules! set_if {
-            ($field:expr, $col:expr) => {
-                if let Some(ref list) = approved_field_list {
-                    if !list.iter().any(|f| f == $col) {
-                        // Field not approved — skip
-                    } else if let Some(val) = get_val($field) {
-                        sets.push(format!("{} = ?{}", $col, idx));
-                        params.push(Box::new(val));
-                        idx += 1;
-                        updated_fields.push($col.to_string());
-                    }
-                } else if let Some(val) = get_val($field) {
-                    sets.push(format!("{} = ?{}", $col, idx));
-                    params.push(Box::new(val));
-                    idx += 1;
-                    updated_fields.push($col.to_string());
-                }
+        for diff in client_field_diffs(conn, &client_id, &get_val, only_fill_empty)? {
+            if approved_field_list.is_some_and(|list| !list.contains(&diff.field)) {
+                continue;
+            }
+            let old = if diff.old_value.is_empty() {
+                serde_json::Value::Null
+            } else {
+                serde_json::Value::String(diff.old_value)
             };
+            changes.insert(diff.field.clone(), serde_json::json!({ "old": old, "new": diff.new_value }));
+            sets.push(format!("{} = ?{}", diff.field, idx));
+            params.push(Box::new(diff.new_value));
+            idx += 1;
+            updated_fields.push(diff.field);
         }
 
-        set_if!("phone", "phone");
-        set_if!("email", "email");
-        set_if!("address_line1", "address_line1");
-        set_if!("address_line2", "address_line2");
-        set_if!("city", "city");
-        set_if!("state", "state");
-        set_if!("zip", "zip");
-        set_if!("county", "county");
-        set_if!("dual_status_code", "dual_status_code");
-        set_if!("lis_level", "lis_level");
-        set_if!("medicaid_id", "medicaid_id");
-        set_if!("notes", "notes");
-
         if sets.is_empty() {
-            return Ok(ImportAction::Skipped { name: client_name });
+            return Ok((ImportAction::Skipped { name: client_name }, Some(client_id)));
         }
 
+        sets.push(format!("import_batch_id = ?{}", idx));
+        params.push(Box::new(import_batch_id.to_string()));
+        idx += 1;
         let sql = format!(
             "UPDATE clients SET {} WHERE id = ?{}",
             sets.join(", "),
@@ -799,25 +1134,33 @@ fn import_single_row(
         params.push(Box::new(client_id.clone()));
         let refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
         conn.execute(&sql, refs.as_slice())?;
+        audit_service::record(
+            conn,
+            audit_service::CLIENT,
+            &client_id,
+            "IMPORT",
+            Some(serde_json::json!({ "import_batch_id": import_batch_id, "fields": changes })),
+        )?;
 
         let event_data = serde_json::json!({
             "source": "file_import",
+            "file": source_file,
             "fields": updated_fields,
         })
         .to_string();
         let _ = conversation_service::create_system_event(
             conn,
             &client_id,
-            "CLIENT_UPDATED",
+            "CLIENT_UPDATED_VIA_IMPORT",
             Some(&event_data),
         );
 
-        Ok(ImportAction::Updated { name: client_name, fields: updated_fields })
+        Ok((ImportAction::Updated { name: client_name, fields: updated_fields }, Some(client_id)))
     } else {
         // If approved_inserts is provided, check if this row was approved
         if let Some(approved) = approved_inserts {
             if !approved.contains(&row_index) {
-                return Ok(ImportAction::Skipped { name: client_name });
+                return Ok((ImportAction::Skipped { name: client_name }, None));
             }
         }
         // Insert new client via shared helper
@@ -839,7 +1182,7 @@ fn import_single_row(
             mbi,
             part_a_date: get_val("part_a_date"),
             part_b_date: get_val("part_b_date"),
-            is_dual_eligible: None,
+            is_dual_eligible: get_val("dual_status_code").map(|c| client_service::is_dual_status(&c)),
             dual_status_code: get_val("dual_status_code"),
             lis_level: get_val("lis_level"),
             medicaid_id: get_val("medicaid_id"),
@@ -847,12 +1190,131 @@ fn import_single_row(
             tags: get_val("tags"),
             notes: get_val("notes"),
         };
-        insert_client(conn, &client_data, Some("file_import"))?;
+        let client_id = insert_client(conn, &client_data, Some("file_import"), source_file)?;
+        conn.execute(
+            "UPDATE clients SET import_batch_id = ?1 WHERE id = ?2",
+            rusqlite::params![import_batch_id, client_id],
+        )?;
+        audit_service::record(
+            conn,
+            audit_service::CLIENT,
+            &client_id,
+            "IMPORT",
+            Some(serde_json::json!({ "import_batch_id": import_batch_id, "created": true })),
+        )?;
 
-        Ok(ImportAction::Inserted { name: client_name })
+        Ok((ImportAction::Inserted { name: client_name }, Some(client_id)))
     }
 }
 
+/// Create an enrollment for `client_id` from the row's plan/carrier columns.
+/// Returns `Ok(false)` when the row carries no plan or carrier data, or when
+/// the same enrollment is already on file (re-importing a roster is a no-op).
+fn import_row_enrollment(
+    conn: &Connection,
+    row: &[String],
+    headers: &[String],
+    mapping: &HashMap<String, String>,
+    constant_values: &HashMap<String, String>,
+    client_id: &str,
+    import_batch_id: &str,
+) -> Result<bool, AppError> {
+    let get_val = |target: &str| -> Option<String> {
+        if let Some(idx) = find_mapped_index(headers, mapping, target) {
+            let val = row.get(idx)?.trim().to_string();
+            if !val.is_empty() {
+                return Some(normalize_field_value(target, val));
+            }
+        }
+        constant_values
+            .get(target)
+            .filter(|v| !v.is_empty())
+            .map(|v| normalize_field_value(target, v.clone()))
+    };
+
+    let plan_name = get_val("plan_name");
+    let carrier_name = get_val("carrier_name");
+    if plan_name.is_none() && carrier_name.is_none() {
+        return Ok(false);
+    }
+
+    let carrier_id = carrier_name
+        .as_deref()
+        .and_then(|name| matching::find_carrier_id(conn, name));
+    if carrier_id.is_none() {
+        if let Some(ref name) = carrier_name {
+            tracing::warn!("Import: no carrier matched '{}'", name);
+        }
+    }
+    let effective_date = get_val("effective_date");
+
+    // Idempotency: skip if client + carrier + plan + effective date already exists
+    let already_exists: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM enrollments
+             WHERE client_id = ?1 AND carrier_id IS ?2 AND plan_name IS ?3
+               AND effective_date IS ?4 AND is_active = 1",
+            rusqlite::params![client_id, carrier_id, plan_name, effective_date],
+            |row| row.get::<_, i64>(0),
+        )
+        .unwrap_or(0)
+        > 0;
+    if already_exists {
+        return Ok(false);
+    }
+
+    // A termination date already in the past means the roster row is historical
+    let termination_date = get_val("termination_date");
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let status_code = match termination_date.as_deref() {
+        Some(term) if term <= today.as_str() => "DISENROLLED_VOLUNTARY",
+        _ => "ACTIVE",
+    };
+
+    let premium = get_val("premium").and_then(|p| p.replace(['$', ','], "").trim().parse::<f64>().ok());
+
+    let plan_type_code = get_val("plan_type_code");
+    let contract_number = get_val("contract_number");
+    let pbp_number = get_val("pbp_number");
+    let plan_id = plan_service::resolve_imported_plan(conn, &ImportedPlan {
+        carrier_id: carrier_id.as_deref(),
+        plan_type_code: plan_type_code.as_deref(),
+        plan_name: plan_name.as_deref(),
+        contract_number: contract_number.as_deref(),
+        pbp_number: pbp_number.as_deref(),
+        effective_date: effective_date.as_deref(),
+    })?;
+
+    let input = CreateEnrollmentInput {
+        client_id: client_id.to_string(),
+        plan_id,
+        carrier_id,
+        plan_type_code,
+        plan_name,
+        contract_number,
+        pbp_number,
+        effective_date,
+        termination_date,
+        application_date: None,
+        status_code: Some(status_code.to_string()),
+        enrollment_period: None,
+        disenrollment_reason: None,
+        premium,
+        confirmation_number: get_val("confirmation_number"),
+        enrollment_source: Some("File Import".to_string()),
+        commission_amount: None,
+        commission_status: None,
+        commission_paid_date: None,
+    };
+    let enrollment = enrollment_service::create_enrollment(conn, &input)?;
+    conn.execute(
+        "UPDATE enrollments SET import_batch_id = ?1 WHERE id = ?2",
+        rusqlite::params![import_batch_id, enrollment.id],
+    )?;
+
+    Ok(true)
+}
+
 /// Find an existing client by MBI → name+DOB → name-only cascade
 fn find_existing_client(
     conn: &Connection,
@@ -905,6 +1367,43 @@ pub fn get_all_rows(file_path: &str) -> Result<(Vec<String>, Vec<Vec<String>>),
     }
 }
 
+/// Rows of an import file yielded one at a time
+pub type RowStream = Box<dyn Iterator<Item = Result<Vec<String>, AppError>>>;
+
+/// Open a file for row-by-row reading. CSV records are read lazily from disk;
+/// XLSX sheets are loaded by calamine up front and then handed out as rows.
+pub fn stream_rows(file_path: &str) -> Result<(Vec<String>, RowStream), AppError> {
+    let lower = file_path.to_lowercase();
+    if lower.ends_with(".csv") {
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .flexible(true)
+            .from_path(file_path)
+            .map_err(|e| AppError::Import(format!("Failed to read CSV: {}", e)))?;
+
+        let headers: Vec<String> = rdr
+            .headers()
+            .map_err(|e| AppError::Import(format!("Failed to read CSV headers: {}", e)))?
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+
+        let rows = rdr.into_records().map(|result| {
+            result
+                .map(|record| record.iter().map(|f| f.to_string()).collect())
+                .map_err(|e| AppError::Import(format!("CSV parse error: {}", e)))
+        });
+        Ok((headers, Box::new(rows)))
+    } else if lower.ends_with(".xlsx") || lower.ends_with(".xls") {
+        let (headers, rows) = get_all_rows_xlsx(file_path)?;
+        Ok((headers, Box::new(rows.into_iter().map(Ok))))
+    } else {
+        Err(AppError::Import(
+            "Unsupported file format".to_string(),
+        ))
+    }
+}
+
 fn get_all_rows_csv(file_path: &str) -> Result<(Vec<String>, Vec<Vec<String>>), AppError> {
     let mut rdr = csv::ReaderBuilder::new()
         .has_headers(true)
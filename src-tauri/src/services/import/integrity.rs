@@ -143,6 +143,7 @@ pub fn import_integrity_from_json(
             _ => {
                 unmatched += 1;
                 unmatched_details.push(ImportRowDetail {
+                    row_number: None,
                     label: format!("Lead #{}", lead.leads_id.unwrap_or(0)),
                     detail: "Missing first name".to_string(),
                 });
@@ -154,6 +155,7 @@ pub fn import_integrity_from_json(
             _ => {
                 unmatched += 1;
                 unmatched_details.push(ImportRowDetail {
+                    row_number: None,
                     label: first_name.clone(),
                     detail: "Missing last name".to_string(),
                 });
@@ -249,6 +251,7 @@ pub fn import_integrity_from_json(
                     UpsertAction::Inserted => {
                         imported += 1;
                         imported_details.push(ImportRowDetail {
+                            row_number: None,
                             label: client_label,
                             detail: format!("New client + {} activities", activity_count),
                         });
@@ -256,6 +259,7 @@ pub fn import_integrity_from_json(
                     UpsertAction::Updated => {
                         imported += 1;
                         imported_details.push(ImportRowDetail {
+                            row_number: None,
                             label: client_label,
                             detail: format!("Enriched + {} activities", activity_count),
                         });
@@ -264,12 +268,14 @@ pub fn import_integrity_from_json(
                         if activity_count > 0 {
                             imported += 1;
                             imported_details.push(ImportRowDetail {
+                                row_number: None,
                                 label: client_label,
                                 detail: format!("{} new activities", activity_count),
                             });
                         } else {
                             skipped += 1;
                             skipped_details.push(ImportRowDetail {
+                                row_number: None,
                                 label: client_label,
                                 detail: "No new data".to_string(),
                             });
@@ -280,6 +286,7 @@ pub fn import_integrity_from_json(
             Err(e) => {
                 unmatched += 1;
                 unmatched_details.push(ImportRowDetail {
+                    row_number: None,
                     label: client_label,
                     detail: format!("Error: {}", e),
                 });
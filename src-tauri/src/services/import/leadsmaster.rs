@@ -94,6 +94,7 @@ pub fn enrich_from_leadsmaster(
             None => {
                 unmatched += 1;
                 unmatched_details.push(ImportRowDetail {
+                    row_number: None,
                     label,
                     detail: format!("MBI: {}", mbi.as_deref().unwrap_or("(none)")),
                 });
@@ -198,6 +199,7 @@ pub fn enrich_from_leadsmaster(
         if sets.is_empty() {
             skipped += 1;
             skipped_details.push(ImportRowDetail {
+                row_number: None,
                 label,
                 detail: "No new data".to_string(),
             });
@@ -227,6 +229,7 @@ pub fn enrich_from_leadsmaster(
 
         imported += 1;
         imported_details.push(ImportRowDetail {
+            row_number: None,
             label,
             detail: updated_fields.join(", "),
         });
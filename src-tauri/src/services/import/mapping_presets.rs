@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::error::AppError;
+
+/// A saved column mapping (source header → target field) for a file format
+/// that gets imported repeatedly
+#[derive(serde::Serialize)]
+pub struct ImportMappingPreset {
+    pub id: String,
+    pub name: String,
+    pub mapping: HashMap<String, String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+fn map_preset(row: &rusqlite::Row) -> rusqlite::Result<ImportMappingPreset> {
+    let json: String = row.get(2)?;
+    Ok(ImportMappingPreset {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        // A corrupt row shows up as an empty mapping rather than failing the list
+        mapping: serde_json::from_str(&json).unwrap_or_default(),
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+/// Save a mapping under `name`. Saving an existing name (ignoring case)
+/// replaces that preset's mapping. Skipped columns are not stored.
+pub fn save_import_mapping(
+    conn: &Connection,
+    name: &str,
+    mapping: &HashMap<String, String>,
+) -> Result<ImportMappingPreset, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Mapping name is required".into()));
+    }
+    let mapping: HashMap<&String, &String> = mapping
+        .iter()
+        .filter(|(_, target)| !target.is_empty())
+        .collect();
+    if mapping.is_empty() {
+        return Err(AppError::Validation("Mapping has no mapped columns".into()));
+    }
+    let json = serde_json::to_string(&mapping)
+        .map_err(|e| AppError::Import(format!("Failed to serialize mapping: {}", e)))?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO import_mappings (id, name, mapping_json) VALUES (?1, ?2, ?3)
+         ON CONFLICT(name) DO UPDATE SET mapping_json = excluded.mapping_json, updated_at = datetime('now')",
+        params![id, name, json],
+    )?;
+    conn.query_row(
+        "SELECT id, name, mapping_json, created_at, updated_at FROM import_mappings WHERE name = ?1",
+        [name],
+        map_preset,
+    )
+    .map_err(AppError::from)
+}
+
+pub fn list_import_mappings(conn: &Connection) -> Result<Vec<ImportMappingPreset>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, mapping_json, created_at, updated_at FROM import_mappings ORDER BY name COLLATE NOCASE",
+    )?;
+    let presets = stmt
+        .query_map([], map_preset)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(presets)
+}
+
+pub fn get_import_mapping(conn: &Connection, id: &str) -> Result<ImportMappingPreset, AppError> {
+    conn.query_row(
+        "SELECT id, name, mapping_json, created_at, updated_at FROM import_mappings WHERE id = ?1",
+        [id],
+        map_preset,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Import mapping {} not found", id)))
+}
+
+/// The preset whose mapped headers all appear in `headers` (compared ignoring
+/// case and surrounding whitespace), with its mapping re-keyed to the file's
+/// own header text. When several fit, the one covering the most columns
+/// wins, then the most recently saved.
+pub fn find_preset_for_headers(
+    conn: &Connection,
+    headers: &[String],
+) -> Result<Option<ImportMappingPreset>, AppError> {
+    let by_key: HashMap<String, &String> = headers
+        .iter()
+        .map(|h| (h.trim().to_lowercase(), h))
+        .collect();
+
+    let mut best: Option<ImportMappingPreset> = None;
+    for mut preset in list_import_mappings(conn)? {
+        let rekeyed: Option<HashMap<String, String>> = preset
+            .mapping
+            .iter()
+            .map(|(header, target)| {
+                by_key
+                    .get(&header.trim().to_lowercase())
+                    .map(|h| ((*h).clone(), target.clone()))
+            })
+            .collect();
+        let Some(rekeyed) = rekeyed.filter(|m| !m.is_empty()) else {
+            continue;
+        };
+        let better = match &best {
+            None => true,
+            Some(current) => {
+                rekeyed.len() > current.mapping.len()
+                    || (rekeyed.len() == current.mapping.len() && preset.updated_at > current.updated_at)
+            }
+        };
+        if better {
+            preset.mapping = rekeyed;
+            best = Some(preset);
+        }
+    }
+    Ok(best)
+}
@@ -4,15 +4,22 @@ mod call_log;
 mod integrity;
 mod sirem;
 mod leadsmaster;
+mod mapping_presets;
+mod undo;
 
 // Re-export all public types and functions at the module level
 // so existing `import_service::` paths continue to work.
 pub use file_import::{
-    parse_file, auto_map_columns, validate_rows, execute_import, preview_import, get_all_rows,
-    ParsedFile, ValidationResult, ErrorRow, ImportRowDetail, ImportResult,
+    parse_file, auto_map_columns, validate_rows, load_state_codes, execute_import, preview_import, get_all_rows, stream_rows,
+    ParsedFile, ValidationResult, ErrorRow, ImportRowDetail, ImportResult, ColumnGuess,
     ImportPreview, PreviewInsert, PreviewUpdate, PreviewSkipped, FieldDiff,
 };
 pub use call_log::{import_call_log_from_db, ActivityImportResult};
 pub use integrity::import_integrity_from_json;
 pub use sirem::import_sirem_from_dump;
 pub use leadsmaster::enrich_from_leadsmaster;
+pub use mapping_presets::{
+    save_import_mapping, list_import_mappings, get_import_mapping, find_preset_for_headers,
+    ImportMappingPreset,
+};
+pub use undo::{undo_import, UndoImportResult};
@@ -201,17 +201,19 @@ pub fn upsert_client(
 
         Ok((client_id, UpsertAction::Updated))
     } else {
-        let id = insert_client(conn, data, source)?;
+        let id = insert_client(conn, data, source, None)?;
         Ok((id, UpsertAction::Inserted))
     }
 }
 
 /// Insert a new client from `ImportClientData`. Returns the new client ID.
-/// Logs a CLIENT_IMPORTED system event.
+/// Logs a CLIENT_IMPORTED system event, naming `source_file` when the row
+/// came from a user-picked file.
 pub fn insert_client(
     conn: &Connection,
     data: &ImportClientData,
     source: Option<&str>,
+    source_file: Option<&str>,
 ) -> Result<String, AppError> {
     let id = Uuid::new_v4().to_string();
     conn.execute(
@@ -252,6 +254,7 @@ pub fn insert_client(
 
     let event_data = serde_json::json!({
         "source": source.unwrap_or("import"),
+        "file": source_file,
     })
     .to_string();
     let _ = conversation_service::create_system_event(
@@ -5,6 +5,7 @@ use rusqlite::Connection;
 use uuid::Uuid;
 
 use crate::error::AppError;
+use crate::services::plan_service::{self, ImportedPlan};
 use super::file_import::ImportRowDetail;
 use super::call_log::ActivityImportResult;
 use super::shared::{normalize_date, normalize_mbi, normalize_phone, upsert_client, ImportClientData, UpsertAction};
@@ -188,6 +189,7 @@ pub fn import_sirem_from_dump(
             _ => {
                 unmatched += 1;
                 unmatched_details.push(ImportRowDetail {
+                    row_number: None,
                     label: sirem_id.clone(),
                     detail: "Missing first name".to_string(),
                 });
@@ -199,6 +201,7 @@ pub fn import_sirem_from_dump(
             _ => {
                 unmatched += 1;
                 unmatched_details.push(ImportRowDetail {
+                    row_number: None,
                     label: first_name.clone(),
                     detail: "Missing last name".to_string(),
                 });
@@ -289,6 +292,7 @@ pub fn import_sirem_from_dump(
                     UpsertAction::Inserted => {
                         imported += 1;
                         imported_details.push(ImportRowDetail {
+                            row_number: None,
                             label: client_label,
                             detail: "New client".to_string(),
                         });
@@ -296,6 +300,7 @@ pub fn import_sirem_from_dump(
                     UpsertAction::Updated => {
                         imported += 1;
                         imported_details.push(ImportRowDetail {
+                            row_number: None,
                             label: client_label,
                             detail: "Enriched".to_string(),
                         });
@@ -303,6 +308,7 @@ pub fn import_sirem_from_dump(
                     UpsertAction::Skipped => {
                         skipped += 1;
                         skipped_details.push(ImportRowDetail {
+                            row_number: None,
                             label: client_label,
                             detail: "No new data".to_string(),
                         });
@@ -312,6 +318,7 @@ pub fn import_sirem_from_dump(
             Err(e) => {
                 unmatched += 1;
                 unmatched_details.push(ImportRowDetail {
+                    row_number: None,
                     label: client_label,
                     detail: format!("Error: {}", e),
                 });
@@ -399,15 +406,25 @@ pub fn import_sirem_from_dump(
             continue;
         }
 
+        let plan_id = plan_service::resolve_imported_plan(conn, &ImportedPlan {
+            carrier_id,
+            plan_type_code: Some(plan_type_code),
+            plan_name: Some(plan_name),
+            contract_number,
+            pbp_number,
+            effective_date: effective_date.as_deref(),
+        })?;
+
         let enrollment_id = Uuid::new_v4().to_string();
         conn.execute(
-            "INSERT INTO enrollments (id, client_id, carrier_id, plan_type_code, plan_name,
+            "INSERT INTO enrollments (id, client_id, plan_id, carrier_id, plan_type_code, plan_name,
              contract_number, pbp_number, effective_date, termination_date, status_code,
              premium, enrollment_source, confirmation_number)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
             rusqlite::params![
                 enrollment_id,
                 client_id,
+                plan_id,
                 carrier_id,
                 plan_type_code,
                 plan_name,
@@ -427,6 +444,7 @@ pub fn import_sirem_from_dump(
     // Add enrollment summary to result
     if enroll_imported > 0 || enroll_skipped > 0 {
         imported_details.push(ImportRowDetail {
+            row_number: None,
             label: "Enrollments".to_string(),
             detail: format!("{} imported, {} skipped", enroll_imported, enroll_skipped),
         });
@@ -0,0 +1,158 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{Map, Value};
+
+use crate::error::AppError;
+use crate::services::{audit_service, client_service, enrollment_service};
+use super::file_import::{ImportRowDetail, UPDATABLE_FIELDS};
+
+#[derive(serde::Serialize)]
+pub struct UndoImportResult {
+    pub clients_removed: usize,
+    pub clients_restored: usize,
+    pub enrollments_removed: usize,
+    /// Fields edited again after the import, which the undo leaves alone
+    pub kept_details: Vec<ImportRowDetail>,
+}
+
+/// Reverse a general file import: enrollments and clients it created are
+/// soft-deleted, and fields it overwrote get their old values back. A field
+/// is only restored while it still holds the imported value, so later edits
+/// survive. Runs in one transaction and marks the import log UNDONE.
+///
+/// Only file imports tag their rows and audit what they overwrote; an import
+/// with neither (SIREM, call logs) is refused rather than marked UNDONE with
+/// nothing reversed.
+pub fn undo_import(conn: &Connection, import_log_id: &str) -> Result<UndoImportResult, AppError> {
+    let tx = conn.unchecked_transaction()?;
+    let status: Option<String> = tx
+        .query_row(
+            "SELECT status FROM import_logs WHERE id = ?1",
+            [import_log_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Import {} not found", import_log_id)))?;
+    if status.as_deref() == Some("UNDONE") {
+        return Err(AppError::Validation("This import has already been undone".into()));
+    }
+
+    let mut result = UndoImportResult {
+        clients_removed: 0,
+        clients_restored: 0,
+        enrollments_removed: 0,
+        kept_details: Vec::new(),
+    };
+
+    let enrollment_ids: Vec<String> = tx
+        .prepare("SELECT id FROM enrollments WHERE import_batch_id = ?1 AND is_active = 1")?
+        .query_map([import_log_id], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    // Newest first, in case the same client was written twice by one file
+    let entries: Vec<(String, String)> = tx
+        .prepare(
+            "SELECT entity_id, changes FROM audit_log
+             WHERE entity_type = ?1 AND action = 'IMPORT'
+               AND json_extract(changes, '$.import_batch_id') = ?2
+             ORDER BY id DESC",
+        )?
+        .query_map(params![audit_service::CLIENT, import_log_id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .collect::<Result<_, _>>()?;
+
+    if enrollment_ids.is_empty() && entries.is_empty() {
+        return Err(AppError::Validation(
+            "This import has no recorded changes to undo; only file imports can be undone".into(),
+        ));
+    }
+
+    for id in enrollment_ids {
+        enrollment_service::delete_enrollment(&tx, &id)?;
+        result.enrollments_removed += 1;
+    }
+
+    for (client_id, changes) in entries {
+        let changes: Value = serde_json::from_str(&changes).unwrap_or_default();
+        if changes.get("created").and_then(Value::as_bool) == Some(true) {
+            match client_service::delete_client(&tx, &client_id) {
+                Ok(()) => result.clients_removed += 1,
+                // Purged since the import; nothing left to remove
+                Err(AppError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+            continue;
+        }
+        let Some(fields) = changes.get("fields").and_then(Value::as_object) else {
+            continue;
+        };
+        if restore_client_fields(&tx, &client_id, fields, &mut result.kept_details)? {
+            result.clients_restored += 1;
+        }
+    }
+
+    tx.execute(
+        "UPDATE import_logs SET status = 'UNDONE' WHERE id = ?1",
+        [import_log_id],
+    )?;
+    tx.commit()?;
+    Ok(result)
+}
+
+/// Put back each field's pre-import value if it still holds what the import
+/// wrote. Returns whether anything was restored.
+fn restore_client_fields(
+    conn: &Connection,
+    client_id: &str,
+    fields: &Map<String, Value>,
+    kept_details: &mut Vec<ImportRowDetail>,
+) -> Result<bool, AppError> {
+    let Some(name) = conn
+        .query_row(
+            "SELECT first_name || ' ' || last_name FROM clients WHERE id = ?1",
+            [client_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?
+    else {
+        return Ok(false);
+    };
+
+    let mut restored = Map::new();
+    let mut kept = Vec::new();
+    for (field, change) in fields {
+        // Column names go into the SQL, so only known columns are accepted
+        if !UPDATABLE_FIELDS.contains(&field.as_str()) {
+            continue;
+        }
+        let imported = change.get("new").and_then(Value::as_str).unwrap_or_default();
+        let old = change.get("old").and_then(Value::as_str);
+        let current: Option<String> = conn.query_row(
+            &format!("SELECT {} FROM clients WHERE id = ?1", field),
+            [client_id],
+            |row| row.get(0),
+        )?;
+        if current.as_deref().unwrap_or_default().trim() != imported.trim() {
+            kept.push(field.clone());
+            continue;
+        }
+        conn.execute(
+            &format!("UPDATE clients SET {} = ?1 WHERE id = ?2", field),
+            params![old, client_id],
+        )?;
+        restored.insert(field.clone(), serde_json::json!({ "old": current, "new": old }));
+    }
+
+    if !kept.is_empty() {
+        kept_details.push(ImportRowDetail {
+            row_number: None,
+            label: name,
+            detail: format!("Changed since the import: {}", kept.join(", ")),
+        });
+    }
+    if restored.is_empty() {
+        return Ok(false);
+    }
+    audit_service::record(conn, audit_service::CLIENT, client_id, "UPDATE", Some(Value::Object(restored)))?;
+    Ok(true)
+}
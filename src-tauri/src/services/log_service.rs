@@ -0,0 +1,41 @@
+use std::io::Write;
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::logging::{log_dir, LOG_FILE_PREFIX};
+
+/// Zip the retained daily log files into `destination` so an agent can send
+/// them to support. Returns the number of files included.
+pub fn export_logs(app_data_dir: &Path, destination: &Path) -> Result<usize, AppError> {
+    let mut files: Vec<_> = std::fs::read_dir(log_dir(app_data_dir))
+        .map_err(|e| AppError::Io(format!("Failed to read log directory: {}", e)))?
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(LOG_FILE_PREFIX))
+        .map(|entry| entry.path())
+        .collect();
+    if files.is_empty() {
+        return Err(AppError::NotFound("No log files to export".to_string()));
+    }
+    files.sort();
+
+    let out = std::fs::File::create(destination)
+        .map_err(|e| AppError::Io(format!("Failed to create {}: {}", destination.display(), e)))?;
+    let mut zip = zip::ZipWriter::new(out);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    for path in &files {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        // Today's file is still being appended to; a snapshot is fine
+        let bytes = std::fs::read(path)
+            .map_err(|e| AppError::Io(format!("Failed to read {}: {}", name, e)))?;
+        zip.start_file(name.as_ref(), options)
+            .and_then(|_| zip.write_all(&bytes).map_err(Into::into))
+            .map_err(|e| AppError::Io(format!("Failed to write log archive: {}", e)))?;
+    }
+    zip.finish()
+        .map_err(|e| AppError::Io(format!("Failed to write log archive: {}", e)))?;
+
+    tracing::info!("Exported {} log files to {}", files.len(), destination.display());
+    Ok(files.len())
+}
@@ -86,6 +86,10 @@ pub fn normalize_date(raw: &str) -> Option<String> {
     if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f") {
         return Some(dt.format("%Y-%m-%d").to_string());
     }
+    // With a UTC offset: 2025-07-01T12:30:00Z / 2025-07-01T12:30:00-04:00
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
+        return Some(dt.date_naive().format("%Y-%m-%d").to_string());
+    }
     // Already ISO date
     if let Ok(d) = chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
         return Some(d.format("%Y-%m-%d").to_string());
@@ -120,17 +124,63 @@ pub fn normalize_mbi(raw: &str) -> Option<String> {
     }
 }
 
-/// Normalize phone: strip non-digits, validate 10 digits.
+/// Validate an MBI against the CMS layout and return its stored form
+/// (dashes/spaces stripped, uppercased; empty stays empty). Shared by the
+/// import preview and client create/update so both apply the same rules.
+/// Position 1 is 1-9; 2, 5, 8, 9 are letters; 3 and 6 are a letter or digit;
+/// 4, 7, 10, 11 are digits. Letters S, L, O, I, B, Z are never used.
+/// e.g. "1eg4-te5-mk73" → "1EG4TE5MK73"
+pub fn validate_mbi(raw: &str) -> Result<String, AppError> {
+    let mbi: String = raw
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase();
+    if mbi.is_empty() {
+        return Ok(mbi);
+    }
+    let chars: Vec<char> = mbi.chars().collect();
+    if chars.len() != 11 {
+        return Err(AppError::Validation(format!("MBI must be 11 characters, got {}", chars.len())));
+    }
+
+    let is_letter = |c: char| c.is_ascii_uppercase() && !"SLOIBZ".contains(c);
+    for (i, &c) in chars.iter().enumerate() {
+        let (ok, expected) = match i + 1 {
+            1 => (('1'..='9').contains(&c), "a digit 1-9"),
+            2 | 5 | 8 | 9 => (is_letter(c), "a letter (not S, L, O, I, B, Z)"),
+            3 | 6 => (is_letter(c) || c.is_ascii_digit(), "a letter (not S, L, O, I, B, Z) or digit"),
+            _ => (c.is_ascii_digit(), "a digit"),
+        };
+        if !ok {
+            return Err(AppError::Validation(format!(
+                "MBI position {} must be {}, got '{}'",
+                i + 1,
+                expected,
+                c
+            )));
+        }
+    }
+    Ok(mbi)
+}
+
+/// Normalize phone to 10 digits: strips punctuation and a leading +1/1.
+/// "(614) 555-1212", "614.555.1212", "+1 614 555 1212" → "6145551212".
+/// Returns None unless the result is a plausible NANP number (area code and
+/// exchange can't start with 0 or 1, and not a single repeated digit).
 pub fn normalize_phone(raw: &str) -> Option<String> {
     let digits: String = raw.chars().filter(|c| c.is_ascii_digit()).collect();
-    if digits.len() == 11 && digits.starts_with('1') {
-        return Some(digits[1..].to_string());
-    }
-    if digits.len() == 10 {
-        Some(digits)
-    } else {
-        None
+    let digits = match digits.len() {
+        11 if digits.starts_with('1') => digits[1..].to_string(),
+        10 => digits,
+        _ => return None,
+    };
+    let bytes = digits.as_bytes();
+    let valid_lead = |b: u8| (b'2'..=b'9').contains(&b);
+    if !valid_lead(bytes[0]) || !valid_lead(bytes[3]) || bytes.iter().all(|&b| b == bytes[0]) {
+        return None;
     }
+    Some(digits)
 }
 
 /// Strip a trailing single-letter middle initial from a first name, lowercase.
@@ -188,6 +238,48 @@ pub fn levenshtein(a: &str, b: &str) -> usize {
     prev[n]
 }
 
+/// Jaro-Winkler similarity in 0.0..=1.0; rewards a shared prefix, so
+/// "jon smith" vs "john smith" scores higher than plain edit distance would.
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0usize;
+    for i in 0..a.len() {
+        let lo = i.saturating_sub(window);
+        let hi = (i + window + 1).min(b.len());
+        for j in lo..hi {
+            if !b_matched[j] && a[i] == b[j] {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_seq = a.iter().zip(&a_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let b_seq = b.iter().zip(&b_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+
+    let m = matches as f64;
+    let jaro = (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0;
+    let prefix = a.iter().zip(&b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
 // ── Canonical matching ──────────────────────────────────────────────────────
 
 /// Find a single best client match using a tiered cascade:
@@ -282,6 +374,73 @@ pub fn find_client_match(
     None
 }
 
+// ── Carrier matching ────────────────────────────────────────────────────────
+
+/// Lowercase and strip everything but letters/digits ("Anthem/Elevance" → "anthemelevance").
+fn compact_carrier_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Resolve a free-text carrier name from an import file to a carrier ID.
+/// Tries, in order: exact name/short_name, containment either way, then
+/// edit distance ≤ 2 against active carriers.
+pub fn find_carrier_id(conn: &Connection, raw: &str) -> Option<String> {
+    let needle = compact_carrier_name(raw);
+    if needle.is_empty() {
+        return None;
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT id, name, short_name FROM carriers WHERE is_active = 1")
+        .ok()?;
+    let carriers: Vec<(String, String, Option<String>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .ok()?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let candidates: Vec<(&str, String)> = carriers
+        .iter()
+        .flat_map(|(id, name, short)| {
+            let mut keys = vec![(id.as_str(), compact_carrier_name(name))];
+            if let Some(s) = short {
+                keys.push((id.as_str(), compact_carrier_name(s)));
+            }
+            keys
+        })
+        .filter(|(_, key)| !key.is_empty())
+        .collect();
+
+    // Exact
+    if let Some((id, _)) = candidates.iter().find(|(_, key)| *key == needle) {
+        return Some(id.to_string());
+    }
+
+    // Containment ("humanainc" contains "humana"); prefer the longest key
+    // so a full carrier name beats a shorter accidental hit
+    if let Some((id, _)) = candidates
+        .iter()
+        .filter(|(_, key)| {
+            (key.len() >= 4 && needle.contains(key.as_str()))
+                || (needle.len() >= 4 && key.contains(&needle))
+        })
+        .max_by_key(|(_, key)| key.len())
+    {
+        return Some(id.to_string());
+    }
+
+    // Edit distance for typos ("humanna", "wellcar")
+    candidates
+        .iter()
+        .map(|(id, key)| (id, levenshtein(key, &needle)))
+        .filter(|(_, d)| *d <= 2)
+        .min_by_key(|(_, d)| *d)
+        .map(|(id, _)| id.to_string())
+}
+
 // ── Single-client duplicate check (for create form) ─────────────────────────
 
 /// Check for potential duplicate clients across tiers 1-3.
@@ -410,7 +569,8 @@ pub fn find_duplicate_clients(conn: &Connection) -> Result<Vec<DuplicateGroup>,
                + CASE WHEN email IS NOT NULL AND email != '' THEN 1 ELSE 0 END
                + CASE WHEN mbi IS NOT NULL AND mbi != '' THEN 1 ELSE 0 END
                + CASE WHEN address_line1 IS NOT NULL AND address_line1 != '' THEN 1 ELSE 0 END) as non_null_count
-         FROM clients"
+         FROM clients
+         WHERE is_active = 1"
     )?;
 
     let clients: Vec<ClientRow> = stmt
@@ -464,12 +624,12 @@ pub fn find_duplicate_clients(conn: &Connection) -> Result<Vec<DuplicateGroup>,
         }
     }
 
-    // Index by (lowercase last_name, normalized DOB)
+    // Index by (trimmed lowercase last_name, normalized DOB)
     let mut name_dob_index: HashMap<(String, String), Vec<usize>> = HashMap::new();
     for (i, c) in clients.iter().enumerate() {
         if let Some(ref dob) = c.dob {
             if let Some(dob_norm) = normalize_date(dob) {
-                let key = (c.last_name.to_ascii_lowercase(), dob_norm);
+                let key = (c.last_name.trim().to_ascii_lowercase(), dob_norm);
                 name_dob_index.entry(key).or_default().push(i);
             }
         }
@@ -540,3 +700,71 @@ pub fn find_duplicate_clients(conn: &Connection) -> Result<Vec<DuplicateGroup>,
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mbi_error(raw: &str) -> String {
+        match validate_mbi(raw) {
+            Err(AppError::Validation(msg)) => msg,
+            other => panic!("expected a validation error for {:?}, got {:?}", raw, other),
+        }
+    }
+
+    #[test]
+    fn test_validate_mbi_normalizes() {
+        assert_eq!(validate_mbi("1EG4TE5MK73").unwrap(), "1EG4TE5MK73");
+        assert_eq!(validate_mbi("1eg4-te5-mk73").unwrap(), "1EG4TE5MK73");
+        assert_eq!(validate_mbi(" 1EG4 TE5 MK73 ").unwrap(), "1EG4TE5MK73");
+        assert_eq!(validate_mbi("").unwrap(), "");
+        assert_eq!(validate_mbi(" - ").unwrap(), "");
+        // Positions 3 and 6 may be digits
+        assert_eq!(validate_mbi("1E24T05MK73").unwrap(), "1E24T05MK73");
+    }
+
+    #[test]
+    fn test_validate_mbi_length() {
+        assert_eq!(mbi_error("1EG4TE5MK7"), "MBI must be 11 characters, got 10");
+        assert_eq!(mbi_error("1EG4TE5MK733"), "MBI must be 11 characters, got 12");
+    }
+
+    #[test]
+    fn test_jaro_winkler_reference_values() {
+        // Textbook pairs from the Jaro-Winkler literature
+        assert!((jaro_winkler("MARTHA", "MARHTA") - 0.9611).abs() < 1e-4);
+        assert!((jaro_winkler("DWAYNE", "DUANE") - 0.84).abs() < 1e-4);
+        assert!((jaro_winkler("DIXON", "DICKSONX") - 0.8133).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_jaro_winkler_edges() {
+        assert_eq!(jaro_winkler("", ""), 1.0);
+        assert_eq!(jaro_winkler("smith", ""), 0.0);
+        assert_eq!(jaro_winkler("", "smith"), 0.0);
+        assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+        assert_eq!(jaro_winkler("john smith", "john smith"), 1.0);
+        // Symmetric
+        assert_eq!(jaro_winkler("jon smith", "john smith"), jaro_winkler("john smith", "jon smith"));
+        // The prefix bonus stops at four characters
+        let four = jaro_winkler("abcdx", "abcdy");
+        let five = jaro_winkler("abcdex", "abcdey");
+        assert!(four < 1.0 && five < 1.0);
+        assert!(five > four);
+    }
+
+    #[test]
+    fn test_validate_mbi_positions() {
+        // Position 1 is 1-9, never 0
+        assert!(mbi_error("0EG4TE5MK73").starts_with("MBI position 1 "));
+        // Letter positions reject digits and the excluded letters
+        assert!(mbi_error("12G4TE5MK73").starts_with("MBI position 2 "));
+        assert!(mbi_error("1SG4TE5MK73").starts_with("MBI position 2 "));
+        assert!(mbi_error("1EG4TE5MZ73").starts_with("MBI position 9 "));
+        // Letter-or-digit positions still exclude S, L, O, I, B, Z
+        assert!(mbi_error("1EO4TE5MK73").starts_with("MBI position 3 "));
+        // Digit positions reject letters
+        assert!(mbi_error("1EGATE5MK73").starts_with("MBI position 4 "));
+        assert!(mbi_error("1EG4TE5MK7A").starts_with("MBI position 11 "));
+    }
+}
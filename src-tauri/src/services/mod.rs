@@ -1,4 +1,6 @@
+pub mod audit_service;
 pub mod auth_service;
+pub mod backup_service;
 pub mod carrier_sync_service;
 pub mod client_service;
 pub mod commission_importers;
@@ -11,6 +13,9 @@ pub mod import_service {
     pub use super::import::*;
 }
 mod import;
+pub mod log_service;
 pub mod matching;
+pub mod plan_service;
 pub mod provider_service;
 pub mod report_service;
+pub mod sep_service;
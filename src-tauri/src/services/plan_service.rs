@@ -0,0 +1,184 @@
+use rusqlite::{Connection, OptionalExtension};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::{CreatePlanInput, Plan, PlanListItem, UpdatePlanInput};
+use crate::repositories::{enrollment_repo, plan_repo};
+
+pub fn get_plans(conn: &Connection, carrier_id: Option<&str>, plan_year: Option<i32>) -> Result<Vec<PlanListItem>, AppError> {
+    plan_repo::get_plans(conn, carrier_id, plan_year)
+}
+
+pub fn get_plan(conn: &Connection, id: &str) -> Result<Plan, AppError> {
+    plan_repo::get_plan(conn, id)
+}
+
+/// Contract numbers are stored upper-case ("H1234")
+pub fn normalize_contract(contract: &str) -> String {
+    contract.trim().to_uppercase()
+}
+
+/// PBPs are stored as three digits ("1" becomes "001")
+pub fn normalize_pbp(pbp: &str) -> String {
+    let pbp = pbp.trim();
+    if !pbp.is_empty() && pbp.len() < 3 && pbp.chars().all(|c| c.is_ascii_digit()) {
+        format!("{:0>3}", pbp)
+    } else {
+        pbp.to_string()
+    }
+}
+
+/// Look up a catalog plan for import matching; inputs are normalized first
+pub fn find_plan_by_contract_pbp(
+    conn: &Connection,
+    contract_number: &str,
+    pbp_number: &str,
+    plan_year: Option<i32>,
+) -> Result<Option<Plan>, AppError> {
+    plan_repo::find_plan_by_contract_pbp(
+        conn,
+        &normalize_contract(contract_number),
+        &normalize_pbp(pbp_number),
+        plan_year,
+    )
+}
+
+/// App setting that, when "false", stops imports from adding stub plans
+const CREATE_STUB_PLANS_SETTING: &str = "create_stub_plans";
+
+/// Plan details carried by an imported enrollment
+pub struct ImportedPlan<'a> {
+    pub carrier_id: Option<&'a str>,
+    pub plan_type_code: Option<&'a str>,
+    pub plan_name: Option<&'a str>,
+    pub contract_number: Option<&'a str>,
+    pub pbp_number: Option<&'a str>,
+    /// YYYY-MM-DD; its year picks the plan year
+    pub effective_date: Option<&'a str>,
+}
+
+/// Find the catalog plan for an imported enrollment by contract + PBP and
+/// plan year. If there is none, add a stub plan from the enrollment's own
+/// details so later enrollments link to the same record. Returns `None`
+/// when the enrollment has no contract/PBP, or a stub can't be made because
+/// the carrier or plan type is unknown.
+pub fn resolve_imported_plan(conn: &Connection, imported: &ImportedPlan) -> Result<Option<String>, AppError> {
+    let (Some(contract), Some(pbp)) = (
+        imported.contract_number.map(normalize_contract).filter(|c| !c.is_empty()),
+        imported.pbp_number.map(normalize_pbp).filter(|p| !p.is_empty()),
+    ) else {
+        return Ok(None);
+    };
+    let plan_year = imported
+        .effective_date
+        .and_then(|d| d.get(..4))
+        .and_then(|y| y.parse::<i32>().ok());
+
+    if let Some(plan) = plan_repo::find_plan_by_contract_pbp(conn, &contract, &pbp, plan_year)? {
+        return Ok(Some(plan.id));
+    }
+
+    if !stub_plans_enabled(conn)? {
+        return Ok(None);
+    }
+    let Some(carrier_id) = imported.carrier_id else {
+        return Ok(None);
+    };
+    let plan_type_code = match imported.plan_type_code {
+        Some(code) => code.to_string(),
+        None => match contract_plan_type(&contract) {
+            Some(code) => code.to_string(),
+            None => return Ok(None),
+        },
+    };
+    if !enrollment_repo::plan_type_exists(conn, &plan_type_code)? {
+        return Ok(None);
+    }
+
+    let plan_name = imported
+        .plan_name
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}-{}", contract, pbp));
+    let id = Uuid::new_v4().to_string();
+    let created = plan_repo::create_plan(conn, &id, &CreatePlanInput {
+        carrier_id: carrier_id.to_string(),
+        plan_type_code,
+        plan_name,
+        contract_number: Some(contract.clone()),
+        pbp_number: Some(pbp.clone()),
+        segment_id: None,
+        plan_year,
+        state: None,
+        county_fips: None,
+        premium: None,
+    });
+    // A deactivated plan with the same contract/PBP/year blocks the insert;
+    // the enrollment still imports, just unlinked
+    if let Err(e) = created {
+        tracing::warn!("Could not add stub plan {}-{}: {}", contract, pbp, e);
+        return Ok(None);
+    }
+    Ok(Some(id))
+}
+
+fn stub_plans_enabled(conn: &Connection) -> Result<bool, AppError> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            [CREATE_STUB_PLANS_SETTING],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(value.as_deref() != Some("false"))
+}
+
+/// CMS contract prefixes: S is a standalone PDP, H and R are Medicare
+/// Advantage (assumed to include drug coverage)
+fn contract_plan_type(contract: &str) -> Option<&'static str> {
+    match contract.chars().next()? {
+        'S' => Some("PDP"),
+        'H' | 'R' => Some("MAPD"),
+        _ => None,
+    }
+}
+
+fn validate_plan_type(conn: &Connection, plan_type_code: Option<&str>) -> Result<(), AppError> {
+    if let Some(code) = plan_type_code {
+        if !enrollment_repo::plan_type_exists(conn, code)? {
+            return Err(AppError::Validation(format!("Unknown plan type: {}", code)));
+        }
+    }
+    Ok(())
+}
+
+pub fn create_plan(conn: &Connection, input: &CreatePlanInput) -> Result<Plan, AppError> {
+    if input.plan_name.trim().is_empty() {
+        return Err(AppError::Validation("Plan name is required".to_string()));
+    }
+    validate_plan_type(conn, Some(&input.plan_type_code))?;
+
+    let mut input = input.clone();
+    input.plan_name = input.plan_name.trim().to_string();
+    input.contract_number = input.contract_number.as_deref().map(normalize_contract);
+    input.pbp_number = input.pbp_number.as_deref().map(normalize_pbp);
+
+    let id = Uuid::new_v4().to_string();
+    plan_repo::create_plan(conn, &id, &input)?;
+    plan_repo::get_plan(conn, &id)
+}
+
+pub fn update_plan(conn: &Connection, id: &str, input: &UpdatePlanInput) -> Result<Plan, AppError> {
+    if input.plan_name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        return Err(AppError::Validation("Plan name cannot be empty".to_string()));
+    }
+    validate_plan_type(conn, input.plan_type_code.as_deref())?;
+
+    let mut input = input.clone();
+    input.plan_name = input.plan_name.as_deref().map(|n| n.trim().to_string());
+    input.contract_number = input.contract_number.as_deref().map(normalize_contract);
+    input.pbp_number = input.pbp_number.as_deref().map(normalize_pbp);
+
+    plan_repo::update_plan(conn, id, &input)?;
+    plan_repo::get_plan(conn, id)
+}
@@ -1,84 +1,64 @@
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 use crate::error::AppError;
 use crate::models::report::ReportDefinition;
+use crate::models::{Client, ClientFilters};
+use crate::repositories::{client_repo, report_repo};
+use crate::services::client_service;
 
-/// Execute a report query and return results as JSON
-pub fn run_report(conn: &Connection, definition: &ReportDefinition) -> Result<serde_json::Value, AppError> {
-    let mut conditions = Vec::new();
-    let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-    let mut idx = 1;
-
-    let filters = &definition.filters;
-
-    if let Some(ref search) = filters.search {
-        if !search.is_empty() {
-            conditions.push(format!(
-                "c.rowid IN (SELECT rowid FROM clients_fts WHERE clients_fts MATCH ?{})",
-                idx
-            ));
-            params.push(Box::new(format!("{}*", search.replace('"', ""))));
-            idx += 1;
-        }
-    }
+/// Font directories and family names probed for PDF output, in order. These
+/// are Linux paths; elsewhere `EMBEDDED_FONT` is used.
+const FONT_CANDIDATES: &[(&str, &str)] = &[
+    ("/usr/share/fonts/TTF/", "DejaVuSans"),
+    ("/usr/share/fonts/truetype/dejavu/", "DejaVuSans"),
+    ("/usr/share/fonts/", "DejaVuSans"),
+    ("", "LiberationSans"),
+];
 
-    if let Some(ref state) = filters.state {
-        conditions.push(format!("c.state = ?{}", idx));
-        params.push(Box::new(state.clone()));
-        idx += 1;
-    }
+/// DejaVu Sans (see `fonts/LICENSE-DejaVu.txt`), used for every style when no
+/// system font is found, as on a stock Windows or macOS install
+const EMBEDDED_FONT: &[u8] = include_bytes!("../../fonts/DejaVuSans.ttf");
 
-    if let Some(ref zip) = filters.zip {
-        conditions.push(format!("c.zip = ?{}", idx));
-        params.push(Box::new(zip.clone()));
-        idx += 1;
-    }
+/// Rows printed in a PDF report unless the `report_pdf_max_rows` setting
+/// says otherwise ("0" prints every row)
+const DEFAULT_PDF_MAX_ROWS: usize = 500;
 
-    if let Some(is_dual) = filters.is_dual_eligible {
-        conditions.push(format!("c.is_dual_eligible = ?{}", idx));
-        params.push(Box::new(if is_dual { 1i32 } else { 0i32 }));
-        idx += 1;
-    }
+/// Columns that fit at the default font size, per orientation; wider tables
+/// get a smaller font
+const PDF_PORTRAIT_COLUMNS: usize = 6;
+const PDF_LANDSCAPE_COLUMNS: usize = 9;
+const PDF_FONT_SIZE: usize = 12;
+const PDF_MIN_FONT_SIZE: usize = 7;
 
-    if let Some(is_active) = filters.is_active {
-        conditions.push(format!("c.is_active = ?{}", idx));
-        params.push(Box::new(if is_active { 1i32 } else { 0i32 }));
-        idx += 1;
-    } else {
-        conditions.push("c.is_active = 1".to_string());
-    }
+/// Chart bars beyond this are dropped (breakdowns come sorted largest first)
+const CHART_MAX_BARS: usize = 12;
 
-    if let Some(ref carrier_id) = filters.carrier_id {
-        conditions.push(format!(
-            "c.id IN (SELECT DISTINCT client_id FROM enrollments WHERE carrier_id = ?{} AND is_active = 1)",
-            idx
-        ));
-        params.push(Box::new(carrier_id.clone()));
-        idx += 1;
-    }
+/// `clients` columns a report may select or sort by. Report definitions come
+/// from the frontend, so column names are checked against this list before
+/// they go into the SQL.
+const REPORT_CLIENT_COLUMNS: &[&str] = &[
+    "id", "first_name", "last_name", "middle_name", "dob", "gender", "phone", "phone2", "email",
+    "address_line1", "address_line2", "city", "state", "zip", "county", "mbi", "part_a_date",
+    "part_b_date", "orec", "is_dual_eligible", "dual_status_code", "lis_level", "medicaid_id",
+    "lead_source", "member_record_locator", "is_active", "tags", "notes", "created_at", "updated_at",
+];
 
-    if let Some(ref plan_type_code) = filters.plan_type_code {
-        conditions.push(format!(
-            "c.id IN (SELECT DISTINCT client_id FROM enrollments WHERE plan_type_code = ?{} AND is_active = 1)",
-            idx
-        ));
-        params.push(Box::new(plan_type_code.clone()));
-        idx += 1;
-    }
+fn report_column(col: &str) -> Result<&str, AppError> {
+    REPORT_CLIENT_COLUMNS
+        .iter()
+        .find(|c| **c == col)
+        .copied()
+        .ok_or_else(|| AppError::Validation(format!("Unknown report column: {}", col)))
+}
 
-    if let Some(ref status_code) = filters.status_code {
-        conditions.push(format!(
-            "c.id IN (SELECT DISTINCT client_id FROM enrollments WHERE status_code = ?{} AND is_active = 1)",
-            idx
-        ));
-        params.push(Box::new(status_code.clone()));
-        idx += 1;
-    }
+/// Execute a report query and return results as JSON
+pub fn run_report(conn: &Connection, definition: &ReportDefinition) -> Result<serde_json::Value, AppError> {
+    client_service::validate_filters(&definition.filters)?;
 
-    let where_clause = if conditions.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", conditions.join(" AND "))
-    };
+    // Same conditions as the client list, except that reports cover active
+    // clients unless the definition says otherwise
+    let mut filters = definition.filters.clone();
+    filters.is_active.get_or_insert(true);
+    let (where_clause, params) = client_repo::filter_clause(&filters);
 
     // Build column list from definition, defaulting to common fields
     let columns = if definition.columns.is_empty() {
@@ -87,20 +67,33 @@ pub fn run_report(conn: &Connection, definition: &ReportDefinition) -> Result<se
         definition
             .columns
             .iter()
-            .map(|col| format!("c.{}", col))
-            .collect::<Vec<_>>()
+            .map(|col| match col.as_str() {
+                // Enrollment-level figure rolled up per client, as text like
+                // every other report cell
+                "commission_amount" => Ok("(SELECT printf('%.2f', SUM(e.commission_amount)) FROM enrollments e
+                     WHERE e.client_id = c.id AND e.is_active = 1
+                           AND e.commission_amount IS NOT NULL) AS commission_amount"
+                    .to_string()),
+                _ => report_column(col).map(|c| format!("c.{}", c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?
             .join(", ")
     };
 
     let sort = if let Some(ref sort_by) = definition.sort_by {
-        let dir = definition.sort_dir.as_deref().unwrap_or("ASC");
-        format!("ORDER BY c.{} {}", sort_by, dir)
+        let dir = match definition.sort_dir.as_deref().map(str::to_ascii_uppercase).as_deref() {
+            None | Some("ASC") => "ASC",
+            Some("DESC") => "DESC",
+            Some(other) => {
+                return Err(AppError::Validation(format!("Invalid sort direction: {}", other)))
+            }
+        };
+        format!("ORDER BY c.{} {}", report_column(sort_by)?, dir)
     } else {
         "ORDER BY c.last_name, c.first_name".to_string()
     };
 
     let sql = format!("SELECT {} FROM clients c {} {}", columns, where_clause, sort);
-    let _ = idx; // suppress unused warning
 
     let params_refs: Vec<&dyn rusqlite::types::ToSql> =
         params.iter().map(|p| p.as_ref()).collect();
@@ -149,51 +142,84 @@ pub fn generate_pdf(
         .and_then(|c| c.as_array())
         .ok_or_else(|| AppError::Import("No columns".to_string()))?;
 
-    // Try multiple common font paths
-    let font_family = genpdf::fonts::from_files("/usr/share/fonts/TTF/", "DejaVuSans", None)
-        .or_else(|_| {
-            genpdf::fonts::from_files(
-                "/usr/share/fonts/truetype/dejavu/",
-                "DejaVuSans",
-                None,
-            )
-        })
-        .or_else(|_| {
-            genpdf::fonts::from_files("/usr/share/fonts/", "DejaVuSans", None)
-        })
-        .or_else(|_| genpdf::fonts::from_files("", "LiberationSans", None))
-        .map_err(|e| {
-            AppError::Import(format!(
-                "Could not find any fonts for PDF generation: {}",
-                e
-            ))
-        })?;
+    // Prefer a system font, falling back to the bundled one
+    let font_family = match FONT_CANDIDATES
+        .iter()
+        .find_map(|(dir, name)| genpdf::fonts::from_files(dir, name, None).ok())
+    {
+        Some(family) => family,
+        None => embedded_font_family()?,
+    };
 
     let mut doc = genpdf::Document::new(font_family);
     doc.set_title(&definition.name);
     doc.set_minimal_conformance();
 
+    let col_count = columns.len();
+    let landscape = match definition.orientation.as_deref() {
+        Some("landscape") => true,
+        Some("portrait") => false,
+        _ => col_count > PDF_PORTRAIT_COLUMNS,
+    };
+    if landscape {
+        // A4 turned sideways
+        doc.set_paper_size(genpdf::Size::new(297.0, 210.0));
+    }
+    let fits = if landscape { PDF_LANDSCAPE_COLUMNS } else { PDF_PORTRAIT_COLUMNS };
+    if col_count > fits {
+        let size = (PDF_FONT_SIZE * fits / col_count).max(PDF_MIN_FONT_SIZE);
+        doc.set_font_size(size as u8);
+    }
+
     // Add title
     let mut title = genpdf::elements::Paragraph::new(&definition.name);
     title.set_alignment(genpdf::Alignment::Center);
     doc.push(title);
     doc.push(genpdf::elements::Break::new(1));
 
-    // Add summary
+    // Add summary. A cut-off table says so at the top and the bottom, so a
+    // printed copy can't pass for the full list.
+    let max_rows = pdf_max_rows(conn)?;
+    let shown = data.len().min(max_rows);
+    let truncation_note = (shown < data.len()).then(|| {
+        format!(
+            "Showing {} of {} records. Export to Excel for the full data.",
+            shown,
+            data.len()
+        )
+    });
     doc.push(genpdf::elements::Paragraph::new(format!(
         "Total records: {}",
         data.len()
     )));
+    if let Some(ref note) = truncation_note {
+        doc.push(genpdf::elements::Paragraph::new(note));
+    }
     doc.push(genpdf::elements::Break::new(1));
 
-    // Add table (limit columns for PDF readability)
-    let col_count = columns.len().min(6);
-    let mut table = genpdf::elements::TableLayout::new(vec![1; col_count]);
+    // Bar chart of the matching dashboard breakdown, when the report is
+    // grouped by one; the table below is always included. The temp file
+    // has to outlive rendering, since genpdf reads images lazily.
+    let mut chart_file = None;
+    if let Some(group_by) = definition.group_by.as_deref().filter(|g| !g.is_empty()) {
+        match build_chart(conn, group_by) {
+            Ok(Some((file, image))) => {
+                doc.push(image);
+                doc.push(genpdf::elements::Break::new(1));
+                chart_file = Some(file);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Skipping report chart: {}", e),
+        }
+    }
+
+    // Add table, each column as wide as its header or longest value needs
+    let mut table = genpdf::elements::TableLayout::new(column_weights(columns, data));
     table.set_cell_decorator(genpdf::elements::FrameCellDecorator::new(true, true, false));
 
     // Header row
     let mut header_row = table.row();
-    for col in columns.iter().take(col_count) {
+    for col in columns {
         let col_name = col.as_str().unwrap_or("");
         header_row.push_element(genpdf::elements::Paragraph::new(col_name));
     }
@@ -201,10 +227,10 @@ pub fn generate_pdf(
         .push()
         .map_err(|_| AppError::Import("PDF table error".to_string()))?;
 
-    // Data rows (limit to 500 rows for PDF)
-    for row_val in data.iter().take(500) {
+    // Data rows; genpdf carries the table across pages
+    for row_val in data.iter().take(max_rows) {
         let mut row = table.row();
-        for col in columns.iter().take(col_count) {
+        for col in columns {
             let col_name = col.as_str().unwrap_or("");
             let cell_val = row_val
                 .get(col_name)
@@ -217,15 +243,367 @@ pub fn generate_pdf(
     }
 
     doc.push(table);
+    if let Some(note) = truncation_note {
+        doc.push(genpdf::elements::Break::new(1));
+        doc.push(genpdf::elements::Paragraph::new(note));
+    }
 
     // Write to file
-    let filename = format!(
-        "{}.pdf",
-        definition.name.replace(' ', "_").to_lowercase()
-    );
-    let path = output_dir.join(&filename);
+    let path = output_dir.join(report_file_name(definition, "pdf"));
     doc.render_to_file(&path)
         .map_err(|e| AppError::Import(format!("Failed to generate PDF: {}", e)))?;
+    drop(chart_file);
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Report columns written to XLSX as numbers rather than text
+const XLSX_NUMERIC_COLUMNS: &[&str] = &["commission_amount"];
+
+/// Generate an XLSX copy of a report and return its path. The header row is
+/// bold, frozen and filterable; numeric columns (see `XLSX_NUMERIC_COLUMNS`)
+/// are typed as numbers, everything else as text.
+pub fn generate_xlsx(
+    conn: &Connection,
+    definition: &ReportDefinition,
+    output_dir: &std::path::Path,
+) -> Result<String, AppError> {
+    let report_data = run_report(conn, definition)?;
+    let data = report_data
+        .get("data")
+        .and_then(|d| d.as_array())
+        .ok_or_else(|| AppError::Import("No report data".to_string()))?;
+    let columns: Vec<&str> = report_data
+        .get("columns")
+        .and_then(|c| c.as_array())
+        .ok_or_else(|| AppError::Import("No columns".to_string()))?
+        .iter()
+        .map(|c| c.as_str().unwrap_or(""))
+        .collect();
+
+    let xlsx_err = |e: rust_xlsxwriter::XlsxError| AppError::Io(format!("Failed to write XLSX: {}", e));
+    let mut workbook = rust_xlsxwriter::Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Report").map_err(xlsx_err)?;
+    let bold = rust_xlsxwriter::Format::new().set_bold();
+
+    for (c, name) in columns.iter().enumerate() {
+        sheet.write_string_with_format(0, c as u16, *name, &bold).map_err(xlsx_err)?;
+    }
+    for (r, row) in data.iter().enumerate() {
+        let r = r as u32 + 1;
+        for (c, name) in columns.iter().enumerate() {
+            let value = row.get(*name).and_then(|v| v.as_str()).unwrap_or("");
+            if value.is_empty() {
+                continue;
+            }
+            let numeric = XLSX_NUMERIC_COLUMNS.contains(name);
+            match value.parse::<f64>() {
+                Ok(number) if numeric => sheet.write_number(r, c as u16, number),
+                _ => sheet.write_string(r, c as u16, value),
+            }
+            .map_err(xlsx_err)?;
+        }
+    }
+
+    if !columns.is_empty() {
+        sheet.set_freeze_panes(1, 0).map_err(xlsx_err)?;
+        sheet
+            .autofilter(0, 0, data.len() as u32, columns.len() as u16 - 1)
+            .map_err(xlsx_err)?;
+        sheet.autofit();
+    }
+
+    let path = output_dir.join(report_file_name(definition, "xlsx"));
+    workbook.save(&path).map_err(xlsx_err)?;
 
     Ok(path.to_string_lossy().to_string())
 }
+
+fn report_file_name(definition: &ReportDefinition, extension: &str) -> String {
+    format!(
+        "{}.{}",
+        definition.name.replace(' ', "_").to_lowercase(),
+        extension
+    )
+}
+
+/// Relative column widths: the longer of the header and the longest value in
+/// the first rows, clamped so one long note can't squeeze everything else
+fn column_weights(columns: &[serde_json::Value], data: &[serde_json::Value]) -> Vec<usize> {
+    columns
+        .iter()
+        .map(|col| {
+            let name = col.as_str().unwrap_or("");
+            let longest_value = data
+                .iter()
+                .take(100)
+                .filter_map(|row| row.get(name).and_then(|v| v.as_str()))
+                .map(|v| v.chars().count())
+                .max()
+                .unwrap_or(0);
+            name.chars().count().max(longest_value).clamp(4, 30)
+        })
+        .collect()
+}
+
+/// Row cap for PDF tables; 0 in the setting means no cap
+fn pdf_max_rows(conn: &Connection) -> Result<usize, AppError> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'report_pdf_max_rows'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(match value.and_then(|v| v.trim().parse::<usize>().ok()) {
+        Some(0) => usize::MAX,
+        Some(n) => n,
+        None => DEFAULT_PDF_MAX_ROWS,
+    })
+}
+
+fn embedded_font_family() -> Result<genpdf::fonts::FontFamily<genpdf::fonts::FontData>, AppError> {
+    let font = genpdf::fonts::FontData::new(EMBEDDED_FONT.to_vec(), None)
+        .map_err(|e| AppError::Import(format!("Failed to load the bundled PDF font: {}", e)))?;
+    Ok(genpdf::fonts::FontFamily {
+        regular: font.clone(),
+        bold: font.clone(),
+        italic: font.clone(),
+        bold_italic: font,
+    })
+}
+
+/// Render the `group_by` breakdown to a PNG and load it as a PDF image.
+/// `None` when there is nothing to chart: an unknown grouping or no data.
+fn build_chart(
+    conn: &Connection,
+    group_by: &str,
+) -> Result<Option<(tempfile::NamedTempFile, genpdf::elements::Image)>, AppError> {
+    let Some(mut data) = report_repo::get_breakdown(conn, group_by)? else {
+        return Ok(None);
+    };
+    data.retain(|(_, count)| *count > 0);
+    data.truncate(CHART_MAX_BARS);
+    if data.is_empty() {
+        return Ok(None);
+    }
+    register_chart_font();
+
+    let file = tempfile::Builder::new().suffix(".png").tempfile()?;
+    let title = format!("Active clients by {}", group_by.replace('_', " "));
+    render_bar_chart(file.path(), &title, &data)
+        .map_err(|e| AppError::Io(format!("Failed to render chart: {}", e)))?;
+    let image = genpdf::elements::Image::from_path(file.path())
+        .map_err(|e| AppError::Io(format!("Failed to load chart: {}", e)))?
+        .with_alignment(genpdf::Alignment::Center)
+        .with_dpi(150.0);
+    Ok(Some((file, image)))
+}
+
+/// Give plotters, which has no font of its own, a regular face once: the
+/// first found in `FONT_CANDIDATES`, or the bundled one
+fn register_chart_font() {
+    static REGISTERED: std::sync::Once = std::sync::Once::new();
+    REGISTERED.call_once(|| {
+        let system = FONT_CANDIDATES.iter().find_map(|(dir, name)| {
+            ["-Regular.ttf", ".ttf"]
+                .iter()
+                .find_map(|suffix| std::fs::read(std::path::Path::new(dir).join(format!("{}{}", name, suffix))).ok())
+        });
+        let register = |bytes: &'static [u8]| {
+            plotters::style::register_font("sans-serif", plotters::style::FontStyle::Normal, bytes).is_ok()
+        };
+        // plotters keeps the font for the life of the process
+        let registered = system.is_some_and(|bytes| register(Box::leak(bytes.into_boxed_slice())));
+        if !registered {
+            register(EMBEDDED_FONT);
+        }
+    });
+}
+
+fn render_bar_chart(
+    path: &std::path::Path,
+    title: &str,
+    data: &[(String, i64)],
+) -> Result<(), Box<dyn std::error::Error>> {
+    use plotters::prelude::*;
+
+    let root = BitMapBackend::new(path, (1000, 500)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let max = data.iter().map(|(_, count)| *count).max().unwrap_or(1);
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 28))
+        .margin(20)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d((0u32..data.len() as u32).into_segmented(), 0i64..max + max / 10 + 1)?;
+
+    let label = |v: &SegmentValue<u32>| match v {
+        SegmentValue::CenterOf(i) => data
+            .get(*i as usize)
+            .map(|(name, _)| name.chars().take(14).collect())
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(data.len())
+        .x_label_formatter(&label)
+        .label_style(("sans-serif", 15))
+        .draw()?;
+
+    chart.draw_series(
+        Histogram::vertical(&chart)
+            .style(BLUE.mix(0.7).filled())
+            .margin(12)
+            .data(data.iter().enumerate().map(|(i, (_, count))| (i as u32, *count))),
+    )?;
+    root.present()?;
+    Ok(())
+}
+
+/// Write every client matching `filters` to a vCard 3.0 file, one card per
+/// client. Fields the client doesn't have are left out of their card.
+/// Returns the number of cards written.
+pub fn export_clients_vcard(
+    conn: &Connection,
+    filters: &ClientFilters,
+    output_path: &std::path::Path,
+) -> Result<usize, AppError> {
+    client_service::validate_filters(filters)?;
+    let clients = client_repo::get_all_filtered_clients(conn, filters)?;
+
+    let mut out = String::new();
+    for c in &clients {
+        write_vcard(&mut out, c);
+    }
+    std::fs::write(output_path, out)?;
+
+    Ok(clients.len())
+}
+
+/// Column order of `export_clients_csv`: every column of the `clients` table
+const CLIENT_CSV_HEADERS: [&str; 30] = [
+    "id", "first_name", "last_name", "middle_name", "dob", "gender", "phone", "phone2", "email",
+    "address_line1", "address_line2", "city", "state", "zip", "county", "mbi", "part_a_date",
+    "part_b_date", "orec", "is_dual_eligible", "dual_status_code", "lis_level", "medicaid_id",
+    "lead_source", "member_record_locator", "is_active", "tags", "notes", "created_at", "updated_at",
+];
+
+/// Write every client matching `filters` to CSV with all client fields, for
+/// spreadsheet work or re-import. Unlike the report export this isn't
+/// limited to a preset's columns. Returns the number of rows written.
+pub fn export_clients_csv(
+    conn: &Connection,
+    filters: &ClientFilters,
+    output_path: &std::path::Path,
+) -> Result<usize, AppError> {
+    client_service::validate_filters(filters)?;
+    let clients = client_repo::get_all_filtered_clients(conn, filters)?;
+
+    let csv_err = |e: csv::Error| AppError::Io(format!("Failed to write CSV: {}", e));
+    let mut wtr = csv::Writer::from_path(output_path).map_err(csv_err)?;
+    wtr.write_record(CLIENT_CSV_HEADERS).map_err(csv_err)?;
+    for c in &clients {
+        let text = |v: &Option<String>| v.clone().unwrap_or_default();
+        let flag = |v: Option<bool>| v.map(|b| if b { "1" } else { "0" }).unwrap_or("").to_string();
+        wtr.write_record([
+            c.id.clone(),
+            c.first_name.clone(),
+            c.last_name.clone(),
+            text(&c.middle_name),
+            text(&c.dob),
+            text(&c.gender),
+            text(&c.phone),
+            text(&c.phone2),
+            text(&c.email),
+            text(&c.address_line1),
+            text(&c.address_line2),
+            text(&c.city),
+            text(&c.state),
+            text(&c.zip),
+            text(&c.county),
+            text(&c.mbi),
+            text(&c.part_a_date),
+            text(&c.part_b_date),
+            text(&c.orec),
+            flag(c.is_dual_eligible),
+            text(&c.dual_status_code),
+            text(&c.lis_level),
+            text(&c.medicaid_id),
+            text(&c.lead_source),
+            text(&c.member_record_locator),
+            flag(c.is_active),
+            text(&c.tags),
+            text(&c.notes),
+            text(&c.created_at),
+            text(&c.updated_at),
+        ])
+        .map_err(csv_err)?;
+    }
+    wtr.flush()?;
+
+    Ok(clients.len())
+}
+
+fn write_vcard(out: &mut String, c: &Client) {
+    let field = |v: &Option<String>| vcard_escape(v.as_deref().unwrap_or("").trim());
+    let present = |v: &Option<String>| v.as_deref().is_some_and(|s| !s.trim().is_empty());
+
+    out.push_str("BEGIN:VCARD\r\nVERSION:3.0\r\n");
+    out.push_str(&format!(
+        "N:{};{};{};;\r\n",
+        vcard_escape(c.last_name.trim()),
+        vcard_escape(c.first_name.trim()),
+        field(&c.middle_name)
+    ));
+    let full_name = [Some(c.first_name.as_str()), c.middle_name.as_deref(), Some(c.last_name.as_str())]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    out.push_str(&format!("FN:{}\r\n", vcard_escape(&full_name)));
+
+    for phone in [&c.phone, &c.phone2] {
+        if present(phone) {
+            out.push_str(&format!("TEL;TYPE=VOICE:{}\r\n", field(phone)));
+        }
+    }
+    if present(&c.email) {
+        out.push_str(&format!("EMAIL;TYPE=INTERNET:{}\r\n", field(&c.email)));
+    }
+    if [&c.address_line1, &c.address_line2, &c.city, &c.state, &c.zip]
+        .into_iter()
+        .any(present)
+    {
+        let street = [&c.address_line1, &c.address_line2]
+            .into_iter()
+            .filter(|v| present(v))
+            .map(field)
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.push_str(&format!(
+            "ADR;TYPE=HOME:;;{};{};{};{};\r\n",
+            street,
+            field(&c.city),
+            field(&c.state),
+            field(&c.zip)
+        ));
+    }
+    out.push_str("END:VCARD\r\n");
+}
+
+/// Escape a vCard text value (RFC 2426 section 4)
+fn vcard_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,124 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rusqlite::Connection;
+use crate::error::AppError;
+use crate::models::{SepHint, SepHints};
+use crate::repositories::{client_repo, conversation_repo, enrollment_repo};
+use crate::services::conversation_service::parse_date;
+
+const DISCLAIMER: &str = "These hints are derived from the data stored in Compass and are not a \
+determination of eligibility or legal advice. Confirm each SEP against current CMS rules and the \
+client's circumstances before submitting an election.";
+
+/// Statuses that mean the client lost coverage through no choice of their own
+const LOST_COVERAGE_STATUSES: &[&str] = &["DISENROLLED_INVOLUNTARY", "DISENROLLED_PLAN_TERM"];
+
+pub fn get_sep_hints(conn: &Connection, client_id: &str) -> Result<SepHints, AppError> {
+    let today = chrono::Local::now().date_naive();
+    let client = client_repo::get_client(conn, client_id)?;
+    let mut hints = Vec::new();
+
+    let is_dual = client.is_dual_eligible.unwrap_or(false) || present(&client.dual_status_code);
+    if is_dual || present(&client.lis_level) {
+        let basis = match (is_dual, present(&client.lis_level)) {
+            (true, true) => "is dual-eligible and receives Extra Help (LIS)",
+            (true, false) => "is dual-eligible",
+            _ => "receives Extra Help (LIS)",
+        };
+        hints.push(SepHint {
+            code: "DUAL_LIS".to_string(),
+            title: "Dual-eligible / LIS monthly SEP".to_string(),
+            explanation: format!(
+                "The client {}, which allows one election per month, generally into a standalone \
+                 Part D plan or an integrated D-SNP.",
+                basis
+            ),
+            evidence_date: None,
+            window_ends: None,
+        });
+    }
+
+    if let Some(moved) = conversation_repo::get_last_system_event_at(conn, client_id, "ADDRESS_CHANGED")?
+        .as_deref()
+        .and_then(parse_date)
+    {
+        let ends = two_full_months_after(moved);
+        if today <= ends {
+            hints.push(SepHint {
+                code: "MOVED".to_string(),
+                title: "Change of residence".to_string(),
+                explanation: "The client's address changed recently. If the move left the current \
+                              plan's service area or brought new plan options, the client can change \
+                              plans through the end of the second month after the move."
+                    .to_string(),
+                evidence_date: Some(fmt(moved)),
+                window_ends: Some(fmt(ends)),
+            });
+        }
+    }
+
+    for e in enrollment_repo::get_enrollment_history(conn, client_id)? {
+        let lost = e.is_active
+            && e.status_code.as_deref().is_some_and(|s| LOST_COVERAGE_STATUSES.contains(&s));
+        let Some(terminated) = e.termination_date.as_deref().and_then(parse_date).filter(|_| lost) else {
+            continue;
+        };
+        let ends = two_full_months_after(terminated);
+        if today <= ends {
+            hints.push(SepHint {
+                code: "LOST_COVERAGE".to_string(),
+                title: "Involuntary loss of coverage".to_string(),
+                explanation: format!(
+                    "{} ended ({}). Losing a plan involuntarily allows a new election through the \
+                     end of the second month after coverage ends.",
+                    e.plan_name.as_deref().unwrap_or("A plan"),
+                    e.status.as_deref().unwrap_or("terminated")
+                ),
+                evidence_date: Some(fmt(terminated)),
+                window_ends: Some(fmt(ends)),
+            });
+        }
+    }
+
+    if let Some(part_b) = client.part_b_date.as_deref().and_then(parse_date) {
+        // Initial coverage election: three months before Part B starts
+        // through the last day of the third month after
+        let starts = part_b - Months::new(3);
+        let ends = last_of_month(part_b + Months::new(3));
+        if starts <= today && today <= ends {
+            hints.push(SepHint {
+                code: "NEW_TO_MEDICARE".to_string(),
+                title: "New to Medicare".to_string(),
+                explanation: "Part B starts (or started) recently, so the client is likely in their \
+                              initial coverage election period."
+                    .to_string(),
+                evidence_date: Some(fmt(part_b)),
+                window_ends: Some(fmt(ends)),
+            });
+        }
+    }
+
+    Ok(SepHints {
+        client_id: client_id.to_string(),
+        hints,
+        disclaimer: DISCLAIMER.to_string(),
+    })
+}
+
+fn present(value: &Option<String>) -> bool {
+    value.as_deref().is_some_and(|v| !v.trim().is_empty())
+}
+
+fn fmt(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn last_of_month(date: NaiveDate) -> NaiveDate {
+    let first = date.with_day(1).expect("day 1 exists");
+    first + Months::new(1) - chrono::Duration::days(1)
+}
+
+/// Most move and loss-of-coverage SEPs run through the end of the second
+/// full month after the event
+fn two_full_months_after(date: NaiveDate) -> NaiveDate {
+    last_of_month(date + Months::new(2))
+}
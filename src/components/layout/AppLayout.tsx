@@ -1,6 +1,8 @@
 import { useEffect, useMemo } from "react";
 import { NavLink, Outlet, useLocation, useNavigate } from "react-router-dom";
 import { useQuery } from "@tanstack/react-query";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
 import {
   LayoutDashboard,
   Users,
@@ -110,6 +112,18 @@ export function AppLayout() {
     }
   }, [hasClients, statsLoading, location.pathname, navigate]);
 
+  // The backend drops the connection after the idle timeout
+  useEffect(() => {
+    const unlisten = listen("session-locked", () => {
+      useAuthStore.getState().reset();
+      navigate("/login", { replace: true });
+      toast.info("Locked after a period of inactivity");
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [navigate]);
+
   const handleLogout = async () => {
     try {
       await tauriInvoke("logout");
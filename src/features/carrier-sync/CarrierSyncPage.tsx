@@ -1,6 +1,7 @@
 import { useState, useEffect, useCallback } from "react";
 import { listen } from "@tauri-apps/api/event";
-import { AlertTriangle, ArrowRightLeft, Loader2 } from "lucide-react";
+import { useQueryClient } from "@tanstack/react-query";
+import { AlertTriangle, ArrowRightLeft, Loader2, X } from "lucide-react";
 import { Button } from "@/components/ui/button";
 import {
   Card,
@@ -12,46 +13,69 @@ import {
 import {
   useOpenCarrierLogin,
   useTriggerCarrierFetch,
+  useCancelCarrierFetch,
+  useParsePortalMembers,
+  usePreviewPortalSync,
   useProcessPortalMembers,
   useSyncLogs,
   useUpdateExpectedActive,
   useCarrierSyncInfo,
+  useResolveSyncReviewItem,
 } from "@/hooks/useCarrierSync";
-import { useCarriers } from "@/hooks/useClients";
+import { useCarriersWithCounts } from "@/hooks/useClients";
 import { CARRIERS } from "./utils";
 import { CarrierTable } from "./CarrierTable";
 import { SyncResultsPanel } from "./SyncResultsPanel";
-import type { SyncResult } from "@/types";
+import { SyncReviewQueue } from "./SyncReviewQueue";
+import { LostAorList } from "./LostAorList";
+import { PortalFetchConfirm } from "./PortalFetchConfirm";
+import type { PortalFetchSummary, SyncResult } from "@/types";
 
-type SyncPhase = "idle" | "login" | "fetching" | "processing";
+type SyncPhase = "idle" | "login" | "fetching" | "confirming" | "processing";
+
+/** Fetched members held between parse and commit */
+interface PendingFetch {
+  carrierId: string;
+  membersJson: string;
+  emptyConfirmed: boolean;
+  summary: PortalFetchSummary;
+  preview: SyncResult | null;
+}
 
 export function CarrierSyncPage() {
   const [selectedCarrier, setSelectedCarrier] = useState<string | null>(null);
   const [lastResult, setLastResult] = useState<SyncResult | null>(null);
   const [syncPhase, setSyncPhase] = useState<SyncPhase>("idle");
   const [syncError, setSyncError] = useState<string | null>(null);
+  const [progress, setProgress] = useState<{ done: number; total: number | null } | null>(null);
+  const [pendingFetch, setPendingFetch] = useState<PendingFetch | null>(null);
 
   const openLogin = useOpenCarrierLogin();
   const triggerFetch = useTriggerCarrierFetch();
+  const cancelFetch = useCancelCarrierFetch();
+  const parseMembers = useParsePortalMembers();
+  const previewSync = usePreviewPortalSync();
   const processMembers = useProcessPortalMembers();
+  const queryClient = useQueryClient();
+  const resolveReviewItem = useResolveSyncReviewItem();
   const { data: syncLogs } = useSyncLogs();
-  const { data: dbCarriers } = useCarriers();
+  const { data: dbCarriers } = useCarriersWithCounts();
   const updateExpectedActive = useUpdateExpectedActive();
   const { data: syncInfo } = useCarrierSyncInfo(selectedCarrier);
 
   const isAutoFetch = syncInfo?.auto_fetch ?? false;
 
-  // Listen for data coming back from the carrier webview
+  // Data came back from the carrier webview: show what was fetched and wait
+  // for the agent before comparing anything
   const handleSyncData = useCallback(
-    (carrierId: string, membersJson: string) => {
-      setSyncPhase("processing");
+    (carrierId: string, membersJson: string, emptyConfirmed: boolean) => {
       setSyncError(null);
-      processMembers.mutate(
+      parseMembers.mutate(
         { carrierId, membersJson },
         {
-          onSuccess: (result) => {
-            setLastResult(result);
-            setSyncPhase("idle");
+          onSuccess: (summary) => {
+            setPendingFetch({ carrierId, membersJson, emptyConfirmed, summary, preview: null });
+            setSyncPhase("confirming");
           },
           onError: (err) => {
             setSyncError(String(err));
@@ -60,32 +84,87 @@ export function CarrierSyncPage() {
         }
       );
     },
-    [processMembers]
+    [parseMembers]
   );
 
+  const handlePreview = () => {
+    if (!pendingFetch) return;
+    const { carrierId, membersJson, emptyConfirmed } = pendingFetch;
+    setSyncError(null);
+    previewSync.mutate(
+      { carrierId, membersJson, emptyConfirmed },
+      {
+        onSuccess: (preview) => setPendingFetch((prev) => (prev ? { ...prev, preview } : prev)),
+        onError: (err) => setSyncError(String(err)),
+      }
+    );
+  };
+
+  const handleDiscard = () => {
+    setPendingFetch(null);
+    setSyncPhase("idle");
+  };
+
+  const handleCommit = () => {
+    if (!pendingFetch) return;
+    const { carrierId, membersJson, emptyConfirmed } = pendingFetch;
+    setPendingFetch(null);
+    setSyncPhase("processing");
+    setSyncError(null);
+    processMembers.mutate(
+      { carrierId, membersJson, emptyConfirmed },
+      {
+        onSuccess: (result) => {
+          setLastResult(result);
+          setSyncPhase("idle");
+        },
+        onError: (err) => {
+          setSyncError(String(err));
+          setSyncPhase("idle");
+        },
+      }
+    );
+  };
+
   // Set up Tauri event listeners
   useEffect(() => {
-    const unlistenData = listen<string>("carrier-sync-data", (event) => {
-      if (selectedCarrier) {
-        handleSyncData(selectedCarrier, event.payload);
+    const unlistenData = listen<{ members: string; empty_confirmed: boolean }>(
+      "carrier-sync-data",
+      (event) => {
+        if (selectedCarrier) {
+          handleSyncData(
+            selectedCarrier,
+            event.payload.members,
+            event.payload.empty_confirmed
+          );
+        }
       }
-    });
+    );
 
     const unlistenError = listen<string>("carrier-sync-error", (event) => {
       setSyncError(event.payload);
       setSyncPhase("idle");
+      queryClient.invalidateQueries({ queryKey: ["sync-logs"] });
     });
 
+    const unlistenProgress = listen<{ done: number; total: number | null }>(
+      "carrier-sync-progress",
+      (event) => setProgress(event.payload)
+    );
+
     return () => {
       unlistenData.then((fn) => fn());
       unlistenError.then((fn) => fn());
+      unlistenProgress.then((fn) => fn());
     };
-  }, [selectedCarrier, handleSyncData]);
+  }, [selectedCarrier, handleSyncData, queryClient]);
 
   const handleOpenPortal = (carrierId: string) => {
     setSelectedCarrier(carrierId);
     setSyncError(null);
     setLastResult(null);
+    setProgress(null);
+    setPendingFetch(null);
     setSyncPhase("login");
     openLogin.mutate(carrierId, {
       onError: (err) => {
@@ -99,6 +178,7 @@ export function CarrierSyncPage() {
     if (!selectedCarrier) return;
     setSyncPhase("fetching");
     setSyncError(null);
+    setProgress(null);
     triggerFetch.mutate(selectedCarrier, {
       onError: (err) => {
         setSyncError(String(err));
@@ -107,6 +187,15 @@ export function CarrierSyncPage() {
     });
   };
 
+  const handleCancelSync = () => {
+    cancelFetch.mutate(undefined, {
+      onSettled: () => {
+        setSyncPhase("idle");
+        setSyncError("Sync cancelled. Open the portal again to retry.");
+      },
+    });
+  };
+
   // Auto-fetch timeout: if stuck in "login" phase for 45s, transition to idle
   // so the user sees the Sync Now button is available
   useEffect(() => {
@@ -119,7 +208,15 @@ export function CarrierSyncPage() {
 
   // Description text based on phase and auto_fetch
   const getDescription = () => {
-    if (syncPhase === "fetching") return "Fetching member data from the carrier portal...";
+    if (syncPhase === "fetching") {
+      if (progress) {
+        return progress.total
+          ? `Fetching member data from the carrier portal... ${progress.done} of ${progress.total}`
+          : `Fetching member data from the carrier portal... ${progress.done} so far`;
+      }
+      return "Fetching member data from the carrier portal...";
+    }
+    if (syncPhase === "confirming") return "Check what the portal returned, then run the sync.";
     if (syncPhase === "processing") return "Comparing portal data against local enrollments...";
     if (syncPhase === "idle" && lastResult) return "Sync complete. You can run another sync or open a different carrier.";
 
@@ -137,6 +234,10 @@ export function CarrierSyncPage() {
         onSelectCarrier={handleOpenPortal}
       />
 
+      <SyncReviewQueue />
+
+      <LostAorList />
+
       {/* Sync controls */}
       {selectedCarrier && (
         <Card>
@@ -157,21 +258,44 @@ export function CarrierSyncPage() {
             )}
 
             {/* Always show Sync Now as a manual fallback */}
-            <Button
-              onClick={handleTriggerSync}
-              disabled={syncPhase === "fetching" || syncPhase === "processing"}
-            >
-              {syncPhase === "fetching" || syncPhase === "processing" ? (
-                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
-              ) : (
-                <ArrowRightLeft className="mr-2 h-4 w-4" />
+            <div className="flex items-center gap-2">
+              <Button
+                onClick={handleTriggerSync}
+                disabled={syncPhase === "fetching" || syncPhase === "confirming" || syncPhase === "processing"}
+              >
+                {syncPhase === "fetching" || syncPhase === "processing" ? (
+                  <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                ) : (
+                  <ArrowRightLeft className="mr-2 h-4 w-4" />
+                )}
+                {syncPhase === "fetching"
+                  ? "Fetching from portal..."
+                  : syncPhase === "processing"
+                    ? "Processing..."
+                    : "Sync Now"}
+              </Button>
+              {syncPhase === "fetching" && (
+                <Button
+                  variant="outline"
+                  onClick={handleCancelSync}
+                  disabled={cancelFetch.isPending}
+                >
+                  <X className="mr-2 h-4 w-4" />
+                  Cancel
+                </Button>
               )}
-              {syncPhase === "fetching"
-                ? "Fetching from portal..."
-                : syncPhase === "processing"
-                  ? "Processing..."
-                  : "Sync Now"}
-            </Button>
+            </div>
+
+            {syncPhase === "confirming" && pendingFetch && (
+              <PortalFetchConfirm
+                summary={pendingFetch.summary}
+                preview={pendingFetch.preview}
+                previewing={previewSync.isPending}
+                onPreview={handlePreview}
+                onCommit={handleCommit}
+                onDiscard={handleDiscard}
+              />
+            )}
 
             {syncError && (
               <div className="flex items-start gap-2 rounded-md border border-destructive/50 bg-destructive/10 p-3 text-sm text-destructive">
@@ -225,6 +349,49 @@ export function CarrierSyncPage() {
               };
             });
           }}
+          onResolvePossibleMatch={(match, samePerson) => {
+            // "Different" leaves the queue item pending until the member is
+            // imported or ignored
+            if (samePerson && match.review_item_id) {
+              resolveReviewItem.mutate({ id: match.review_item_id, action: "confirm_match" });
+            }
+            setLastResult((prev) => {
+              if (!prev) return prev;
+              const possible_matches = prev.possible_matches.filter(
+                (m) => m.enrollment_id !== match.enrollment_id
+              );
+              if (samePerson) {
+                return {
+                  ...prev,
+                  possible_matches,
+                  matched: prev.matched + 1,
+                  matched_members: [
+                    ...prev.matched_members,
+                    {
+                      client_name: match.client_name,
+                      client_id: match.client_id,
+                      portal_member: match.portal_member,
+                      match_tier: "confirmed_similar",
+                    },
+                  ],
+                };
+              }
+              return {
+                ...prev,
+                possible_matches,
+                disenrolled: [
+                  ...prev.disenrolled,
+                  {
+                    client_name: match.client_name,
+                    client_id: match.client_id,
+                    enrollment_id: match.enrollment_id,
+                    plan_name: match.plan_name,
+                  },
+                ],
+                new_in_portal: [...prev.new_in_portal, match.portal_member],
+              };
+            });
+          }}
         />
       )}
     </div>
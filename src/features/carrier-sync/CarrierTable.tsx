@@ -1,10 +1,11 @@
 import { useState } from "react";
-import { KeyRound } from "lucide-react";
+import { AlertTriangle, FileJson, KeyRound } from "lucide-react";
+import { toast } from "sonner";
 import { Button } from "@/components/ui/button";
 import { CARRIERS, relativeTime } from "./utils";
-import { useCarriersWithCredentials } from "@/hooks/useCarrierSync";
+import { useCarriersWithCredentials, useFetchSyncSnapshot } from "@/hooks/useCarrierSync";
 import { CredentialsDialog } from "./CredentialsDialog";
-import type { Carrier, SyncLogEntry } from "@/types";
+import type { CarrierWithCounts, SyncLogEntry } from "@/types";
 
 export function CarrierTable({
   syncLogs,
@@ -13,16 +14,35 @@ export function CarrierTable({
   onSelectCarrier,
 }: {
   syncLogs?: SyncLogEntry[];
-  dbCarriers?: Carrier[];
+  dbCarriers?: CarrierWithCounts[];
   selectedCarrier: string | null;
   onSelectCarrier: (carrierId: string) => void;
 }) {
   const { data: carriersWithCreds } = useCarriersWithCredentials();
+  const fetchSnapshot = useFetchSyncSnapshot();
   const [credDialogCarrier, setCredDialogCarrier] = useState<{
     id: string;
     name: string;
   } | null>(null);
 
+  const handleSaveSnapshot = async (log: SyncLogEntry) => {
+    try {
+      const snapshot = await fetchSnapshot.mutateAsync(log.id);
+      const { save } = await import("@tauri-apps/plugin-dialog");
+      const filePath = await save({
+        filters: [{ name: "JSON", extensions: ["json"] }],
+        defaultPath: `${log.carrier_id}-${log.synced_at.slice(0, 10)}.json`,
+      });
+      if (filePath) {
+        const { writeTextFile } = await import("@tauri-apps/plugin-fs");
+        await writeTextFile(filePath, JSON.stringify(snapshot, null, 2));
+        toast.success(`Snapshot saved to ${filePath}`);
+      }
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Failed to save snapshot");
+    }
+  };
+
   return (
     <>
       <div className="rounded-md border">
@@ -34,12 +54,18 @@ export function CarrierTable({
               <th className="h-10 px-4 text-right font-medium text-muted-foreground">Found</th>
               <th className="h-10 px-4 text-right font-medium text-muted-foreground">Active</th>
               <th className="h-10 px-4 text-right font-medium text-muted-foreground">Expected</th>
+              <th className="h-10 px-4 text-right font-medium text-muted-foreground" title="Active enrollments recorded in Compass">In CRM</th>
               <th className="h-10 px-4 text-right font-medium text-muted-foreground">+/−</th>
             </tr>
           </thead>
           <tbody>
             {CARRIERS.map((carrier) => {
-              const latestLog = syncLogs?.find((l) => l.carrier_id === carrier.id);
+              // Failed attempts carry no counts, so the numbers come from the last good sync
+              const lastAttempt = syncLogs?.find((l) => l.carrier_id === carrier.id);
+              const latestLog = syncLogs?.find(
+                (l) => l.carrier_id === carrier.id && l.status !== "FAILED"
+              );
+              const lastFailure = lastAttempt?.status === "FAILED" ? lastAttempt : null;
               const dbCarrier = dbCarriers?.find((c) => c.id === carrier.id);
               const expected = dbCarrier?.expected_active ?? 0;
               const found = latestLog?.portal_count ?? null;
@@ -86,10 +112,28 @@ export function CarrierTable({
                         <span className="ml-2 text-xs opacity-60">
                           {relativeTime(latestLog.synced_at)}
                         </span>
+                        {latestLog.has_snapshot && (
+                          <button
+                            type="button"
+                            title="Save the member list the portal returned"
+                            onClick={() => handleSaveSnapshot(latestLog)}
+                            className="ml-2 rounded p-1 align-middle text-muted-foreground/60 transition-colors hover:bg-muted hover:text-muted-foreground"
+                          >
+                            <FileJson className="h-3.5 w-3.5" />
+                          </button>
+                        )}
                       </>
                     ) : (
                       "—"
                     )}
+                    {lastFailure && (
+                      <span
+                        title={`Last sync failed ${relativeTime(lastFailure.synced_at)}: ${lastFailure.error_message ?? "unknown error"}`}
+                        className="ml-2 inline-flex align-middle text-destructive"
+                      >
+                        <AlertTriangle className="h-3.5 w-3.5" />
+                      </span>
+                    )}
                   </td>
                   <td className="px-4 py-3 text-right tabular-nums">
                     {found ?? "—"}
@@ -100,6 +144,9 @@ export function CarrierTable({
                   <td className="px-4 py-3 text-right tabular-nums">
                     {expected > 0 ? expected : "—"}
                   </td>
+                  <td className="px-4 py-3 text-right tabular-nums">
+                    {dbCarrier?.enrollment_count ?? "—"}
+                  </td>
                   <td className="px-4 py-3 text-right tabular-nums">
                     {diff !== null ? (
                       <span
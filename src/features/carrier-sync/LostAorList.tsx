@@ -0,0 +1,61 @@
+import { UserX } from "lucide-react";
+import { useNavigate } from "react-router-dom";
+import { ScrollArea } from "@/components/ui/scroll-area";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "@/components/ui/card";
+import { useLostAorEnrollments } from "@/hooks/useCarrierSync";
+
+/** Active enrollments where the carrier shows the agent is no longer the AOR */
+export function LostAorList() {
+  const { data: items } = useLostAorEnrollments();
+  const navigate = useNavigate();
+
+  if (!items || items.length === 0) return null;
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2 text-base">
+          <UserX className="h-5 w-5 text-red-500" />
+          Not Agent of Record ({items.length})
+        </CardTitle>
+        <CardDescription>
+          Members still enrolled with the carrier whose agent-of-record status shows the agent of
+          record has changed. Commission on these is likely going to another agent.
+        </CardDescription>
+      </CardHeader>
+      <CardContent>
+        <ScrollArea className="h-48">
+          <div className="space-y-1">
+            {items.map((item) => (
+              <div
+                key={item.enrollment_id}
+                className="flex items-center gap-3 rounded-md border p-2 text-sm"
+              >
+                <span className="min-w-[110px] text-muted-foreground">
+                  {item.carrier_name ?? "—"}
+                </span>
+                <button
+                  type="button"
+                  onClick={() => navigate(`/clients/${item.client_id}`)}
+                  className="min-w-[140px] text-left font-medium hover:underline"
+                >
+                  {item.client_name}
+                </button>
+                <span className="flex-1 truncate text-muted-foreground">
+                  {item.plan_name ?? "—"}
+                </span>
+                <span className="text-red-600">{item.aor_status}</span>
+              </div>
+            ))}
+          </div>
+        </ScrollArea>
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,84 @@
+import { ArrowRightLeft, Eye, Loader2, X } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import type { PortalFetchSummary, SyncResult } from "@/types";
+
+interface PortalFetchConfirmProps {
+  summary: PortalFetchSummary;
+  preview: SyncResult | null;
+  previewing: boolean;
+  onPreview: () => void;
+  onCommit: () => void;
+  onDiscard: () => void;
+}
+
+/** Fetched portal data waiting for the agent to preview, commit, or discard */
+export function PortalFetchConfirm({
+  summary,
+  preview,
+  previewing,
+  onPreview,
+  onCommit,
+  onDiscard,
+}: PortalFetchConfirmProps) {
+  const hidden = summary.member_count - summary.sample.length;
+
+  return (
+    <div className="space-y-3 rounded-md border p-3 text-sm">
+      <p>
+        <span className="font-medium">
+          {summary.carrier_name} returned {summary.member_count.toLocaleString()} members.
+        </span>{" "}
+        <span className="text-muted-foreground">
+          {summary.local_count.toLocaleString()} active or pending enrollments on file will be
+          compared against them.
+        </span>
+      </p>
+
+      {summary.sample.length > 0 && (
+        <ul className="space-y-0.5 text-muted-foreground">
+          {summary.sample.map((m, i) => (
+            <li key={i}>
+              {m.first_name} {m.last_name}
+              {m.dob && ` · ${m.dob}`}
+              {m.plan_name && ` · ${m.plan_name}`}
+            </li>
+          ))}
+          {hidden > 0 && <li>...and {hidden.toLocaleString()} more</li>}
+        </ul>
+      )}
+
+      {preview && (
+        <div className="rounded-md bg-muted/50 p-2">
+          <p className="font-medium">If you run this sync:</p>
+          <p className="text-muted-foreground">
+            {preview.matched} matched, {preview.disenrolled.length} marked disenrolled,{" "}
+            {preview.terminated_in_portal.length} termed in the portal,{" "}
+            {preview.new_in_portal.length} new in the portal,{" "}
+            {preview.possible_matches.length} possible matches to review
+          </p>
+        </div>
+      )}
+
+      <div className="flex items-center gap-2">
+        <Button onClick={onCommit}>
+          <ArrowRightLeft className="mr-2 h-4 w-4" />
+          Run Sync
+        </Button>
+        {!preview && (
+          <Button variant="outline" onClick={onPreview} disabled={previewing}>
+            {previewing ? (
+              <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+            ) : (
+              <Eye className="mr-2 h-4 w-4" />
+            )}
+            Preview Changes
+          </Button>
+        )}
+        <Button variant="ghost" onClick={onDiscard}>
+          <X className="mr-2 h-4 w-4" />
+          Discard
+        </Button>
+      </div>
+    </div>
+  );
+}
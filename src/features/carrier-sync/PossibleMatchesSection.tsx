@@ -0,0 +1,61 @@
+import { Check, HelpCircle, X } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Badge } from "@/components/ui/badge";
+import { ScrollArea } from "@/components/ui/scroll-area";
+import type { SyncPossibleMatch } from "@/types";
+
+export function PossibleMatchesSection({
+  matches,
+  onResolve,
+}: {
+  matches: SyncPossibleMatch[];
+  onResolve: (match: SyncPossibleMatch, samePerson: boolean) => void;
+}) {
+  if (matches.length === 0) return null;
+
+  return (
+    <div>
+      <div className="mb-2">
+        <h4 className="flex items-center gap-2 text-sm font-medium">
+          <HelpCircle className="h-4 w-4 text-amber-500" />
+          Possible Matches ({matches.length})
+        </h4>
+        <p className="text-xs text-muted-foreground">
+          Similar names only. Confirm each pair before it counts as matched; rejected
+          pairs move to the disenrollment and new-member lists.
+        </p>
+      </div>
+
+      <ScrollArea className="h-40">
+        <div className="space-y-1">
+          {matches.map((m) => (
+            <div
+              key={m.enrollment_id}
+              className="flex items-center gap-3 rounded-md border border-amber-200 bg-amber-50 p-2 text-sm dark:border-amber-900 dark:bg-amber-950"
+            >
+              <span className="min-w-[140px] font-medium">{m.client_name}</span>
+              <span className="text-muted-foreground">≈</span>
+              <span className="min-w-[140px] font-medium">
+                {m.portal_member.first_name} {m.portal_member.last_name}
+              </span>
+              <span className="flex-1 text-muted-foreground">
+                {m.plan_name ?? m.portal_member.plan_name ?? "—"}
+              </span>
+              <Badge variant="outline" className="text-xs text-amber-600">
+                {Math.round(m.score * 100)}%
+              </Badge>
+              <Button size="sm" variant="outline" onClick={() => onResolve(m, true)}>
+                <Check className="mr-1 h-3.5 w-3.5" />
+                Same
+              </Button>
+              <Button size="sm" variant="ghost" onClick={() => onResolve(m, false)}>
+                <X className="mr-1 h-3.5 w-3.5" />
+                Different
+              </Button>
+            </div>
+          ))}
+        </div>
+      </ScrollArea>
+    </div>
+  );
+}
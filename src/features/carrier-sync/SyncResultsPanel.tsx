@@ -11,9 +11,10 @@ import {
 import { isPortalMemberActive } from "./utils";
 import { DisenrollmentSection } from "./DisenrollmentSection";
 import { NewInPortalSection } from "./NewInPortalSection";
-import type { Carrier, SyncResult, PortalMember, ImportPortalResult } from "@/types";
+import { PossibleMatchesSection } from "./PossibleMatchesSection";
+import type { Carrier, SyncResult, PortalMember, ImportPortalResult, SyncPossibleMatch } from "@/types";
 
-type StatView = "portal" | "active" | "inactive" | "matched" | "disenrolled" | null;
+type StatView = "portal" | "active" | "inactive" | "matched" | "termed" | "disenrolled" | null;
 
 function MatchTierBadge({ tier }: { tier: string }) {
   switch (tier) {
@@ -23,6 +24,8 @@ function MatchTierBadge({ tier }: { tier: string }) {
       return <Badge variant="outline" className="text-xs text-amber-600">Fuzzy Match</Badge>;
     case "mbi":
       return <Badge variant="outline" className="text-xs text-blue-600">MBI Match</Badge>;
+    case "confirmed_similar":
+      return <Badge variant="outline" className="text-xs text-amber-600">Confirmed Match</Badge>;
     default:
       return <Badge variant="outline" className="text-xs text-green-700">Matched</Badge>;
   }
@@ -35,6 +38,7 @@ export function SyncResultsPanel({
   onUpdateExpected,
   onImported,
   onDisenrolled,
+  onResolvePossibleMatch,
 }: {
   result: SyncResult;
   carrierId: string;
@@ -42,10 +46,12 @@ export function SyncResultsPanel({
   onUpdateExpected: (count: number) => void;
   onImported: (result: ImportPortalResult, members: PortalMember[]) => void;
   onDisenrolled: (confirmedIds: string[]) => void;
+  onResolvePossibleMatch: (match: SyncPossibleMatch, samePerson: boolean) => void;
 }) {
-  // All portal members = matched + new
+  // All portal members = matched + possible + new
   const allPortalMembers: PortalMember[] = [
     ...result.matched_members.map((m) => m.portal_member),
+    ...result.possible_matches.map((m) => m.portal_member),
     ...result.new_in_portal,
   ];
   const activeMembers = allPortalMembers.filter((m) => isPortalMemberActive(m));
@@ -124,7 +130,7 @@ export function SyncResultsPanel({
       </CardHeader>
       <CardContent className="space-y-4">
         {/* Summary stat boxes — clickable */}
-        <div className="grid grid-cols-2 gap-4 sm:grid-cols-5">
+        <div className="grid grid-cols-2 gap-4 sm:grid-cols-6">
           {statBox("In Portal", result.portal_count, "portal", "")}
           <div
             className={`cursor-pointer rounded-md border p-3 text-center transition-colors hover:bg-muted/50 ${
@@ -177,6 +183,7 @@ export function SyncResultsPanel({
           </div>
           {statBox("Inactive", inactiveMembers.length, "inactive", "text-red-600")}
           {statBox("Matched", result.matched, "matched", "text-green-600")}
+          {statBox("Termed in Portal", result.terminated_in_portal.length, "termed", "text-amber-600")}
           {statBox("To Disenroll", result.disenrolled.length, "disenrolled", "text-red-600")}
         </div>
 
@@ -236,6 +243,29 @@ export function SyncResultsPanel({
           </ScrollArea>
         )}
 
+        {expandedStat === "termed" && (
+          <ScrollArea className="h-48">
+            <div className="space-y-1">
+              {result.terminated_in_portal.length === 0 ? (
+                <p className="py-4 text-center text-sm text-muted-foreground">
+                  No terminations reported by the portal.
+                </p>
+              ) : (
+                result.terminated_in_portal.map((t) => (
+                  <div
+                    key={t.enrollment_id}
+                    className="flex items-center justify-between rounded-md border p-2 text-sm"
+                  >
+                    <span className="font-medium">{t.client_name}</span>
+                    <span className="text-muted-foreground">{t.plan_name ?? "—"}</span>
+                    <span className="text-muted-foreground">Termed {t.termination_date}</span>
+                  </div>
+                ))
+              )}
+            </div>
+          </ScrollArea>
+        )}
+
         {expandedStat === "disenrolled" && (
           <DisenrollmentSection
             disenrolled={result.disenrolled}
@@ -243,6 +273,27 @@ export function SyncResultsPanel({
           />
         )}
 
+        {result.aor_lost.length > 0 && (
+          <div className="space-y-1 rounded-md border border-red-200 bg-red-50 p-3 text-sm dark:border-red-900 dark:bg-red-950">
+            <p className="font-medium text-red-700 dark:text-red-400">
+              No longer agent of record on {result.aor_lost.length}{" "}
+              {result.aor_lost.length === 1 ? "enrollment" : "enrollments"}
+            </p>
+            {result.aor_lost.map((a) => (
+              <div key={a.enrollment_id} className="flex items-center justify-between">
+                <span className="font-medium">{a.client_name}</span>
+                <span className="text-muted-foreground">{a.plan_name ?? "—"}</span>
+                <span className="text-muted-foreground">{a.aor_status}</span>
+              </div>
+            ))}
+          </div>
+        )}
+
+        <PossibleMatchesSection
+          matches={result.possible_matches}
+          onResolve={onResolvePossibleMatch}
+        />
+
         {/* New in portal list with import */}
         <NewInPortalSection
           members={result.new_in_portal}
@@ -251,7 +302,9 @@ export function SyncResultsPanel({
         />
 
         {result.disenrolled.length === 0 &&
+          result.terminated_in_portal.length === 0 &&
           result.new_in_portal.length === 0 &&
+          result.possible_matches.length === 0 &&
           result.matched_members.length === 0 && (
             <p className="text-sm text-muted-foreground">
               No portal data to display.
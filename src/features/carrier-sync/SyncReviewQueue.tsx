@@ -0,0 +1,114 @@
+import { Check, ClipboardList, EyeOff, Loader2, UserPlus } from "lucide-react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Badge } from "@/components/ui/badge";
+import { ScrollArea } from "@/components/ui/scroll-area";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "@/components/ui/card";
+import {
+  useResolveSyncReviewItem,
+  useSyncReviewItems,
+  type SyncReviewAction,
+} from "@/hooks/useCarrierSync";
+import type { SyncReviewItem } from "@/types";
+
+/** Sync results still waiting on a decision, across all carriers */
+export function SyncReviewQueue() {
+  const { data: items } = useSyncReviewItems();
+  const resolve = useResolveSyncReviewItem();
+
+  if (!items || items.length === 0) return null;
+
+  const handle = (item: SyncReviewItem, action: SyncReviewAction) => {
+    resolve.mutate(
+      { id: item.id, action },
+      {
+        onError: (err) => toast.error(typeof err === "string" ? err : "Failed to resolve item"),
+      }
+    );
+  };
+  const busy = (item: SyncReviewItem) => resolve.isPending && resolve.variables?.id === item.id;
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2 text-base">
+          <ClipboardList className="h-5 w-5 text-amber-500" />
+          Review Queue ({items.length})
+        </CardTitle>
+        <CardDescription>
+          Portal members from recent syncs that need a decision. Each carrier's list is
+          replaced on its next sync; ignored members stay ignored.
+        </CardDescription>
+      </CardHeader>
+      <CardContent>
+        <ScrollArea className="h-56">
+          <div className="space-y-1">
+            {items.map((item) => (
+              <div
+                key={item.id}
+                className="flex items-center gap-3 rounded-md border p-2 text-sm"
+              >
+                <span className="min-w-[110px] text-muted-foreground">
+                  {item.carrier_name ?? item.carrier_id}
+                </span>
+                <span className="min-w-[140px] font-medium">
+                  {item.portal_member.first_name} {item.portal_member.last_name}
+                </span>
+                <span className="flex-1 text-muted-foreground">
+                  {item.kind === "POSSIBLE_MATCH" ? (
+                    <>
+                      ≈ {item.client_name ?? "client"}
+                      {item.score != null && (
+                        <Badge variant="outline" className="ml-2 text-xs text-amber-600">
+                          {Math.round(item.score * 100)}%
+                        </Badge>
+                      )}
+                    </>
+                  ) : (
+                    item.portal_member.plan_name ?? "New in portal"
+                  )}
+                </span>
+                {busy(item) && <Loader2 className="h-4 w-4 animate-spin" />}
+                {item.kind === "POSSIBLE_MATCH" && (
+                  <Button
+                    size="sm"
+                    variant="outline"
+                    disabled={resolve.isPending}
+                    onClick={() => handle(item, "confirm_match")}
+                  >
+                    <Check className="mr-1 h-3.5 w-3.5" />
+                    Same
+                  </Button>
+                )}
+                <Button
+                  size="sm"
+                  variant="outline"
+                  disabled={resolve.isPending}
+                  onClick={() => handle(item, "create_new")}
+                >
+                  <UserPlus className="mr-1 h-3.5 w-3.5" />
+                  Create New
+                </Button>
+                <Button
+                  size="sm"
+                  variant="ghost"
+                  disabled={resolve.isPending}
+                  onClick={() => handle(item, "ignore")}
+                >
+                  <EyeOff className="mr-1 h-3.5 w-3.5" />
+                  Ignore
+                </Button>
+              </div>
+            ))}
+          </div>
+        </ScrollArea>
+      </CardContent>
+    </Card>
+  );
+}
@@ -44,6 +44,18 @@ export const CARRIERS: CarrierConfig[] = [
     description: "Broker portal, BOB",
     status: "available",
   },
+  {
+    id: "carrier-aetna",
+    name: "Aetna",
+    description: "Producer World, BOB",
+    status: "available",
+  },
+  {
+    id: "carrier-wellcare",
+    name: "WellCare",
+    description: "Centene agent portal",
+    status: "available",
+  },
 ];
 
 export function relativeTime(dateStr: string): string {
@@ -1,14 +1,15 @@
 import { useState } from "react";
 import { useNavigate, useParams } from "react-router-dom";
 import { useClient, useUpdateClient, useHardDeleteClient } from "@/hooks/useClients";
-import { useEnrollments } from "@/hooks/useEnrollments";
+import { useEnrollments, useDeleteEnrollment, useRestoreEnrollment } from "@/hooks/useEnrollments";
+import { useClientNextAction } from "@/hooks/useConversations";
 import { Button } from "@/components/ui/button";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Separator } from "@/components/ui/separator";
-import { ArrowLeft, Pencil, Loader2, Phone, MapPin, CreditCard, Info, UserX, UserCheck, Trash2 } from "lucide-react";
+import { ArrowLeft, Pencil, Loader2, Phone, MapPin, CreditCard, Info, UserX, UserCheck, Trash2, CalendarClock } from "lucide-react";
 import { toast } from "sonner";
 import { ClientEngagementSection } from "@/features/engagement";
-import { formatMbi, formatPhone, formatTimestamp } from "@/lib/utils";
+import { formatDaysAgo, formatMbi, formatPhone, formatTimestamp } from "@/lib/utils";
 import { tauriInvoke } from "@/lib/tauri";
 
 const OREC_LABELS: Record<string, string> = {
@@ -37,10 +38,29 @@ export function ClientDetailPage() {
   const navigate = useNavigate();
   const { data: client, isLoading } = useClient(id);
   const { data: enrollments } = useEnrollments(id);
+  const deleteEnrollment = useDeleteEnrollment();
+  const restoreEnrollment = useRestoreEnrollment();
+  const { data: nextAction } = useClientNextAction(id);
   const updateClient = useUpdateClient();
   const hardDelete = useHardDeleteClient();
   const [confirmingDelete, setConfirmingDelete] = useState(false);
 
+  const handleDeleteEnrollment = (enrollmentId: string) => {
+    deleteEnrollment.mutate(enrollmentId, {
+      onSuccess: () =>
+        toast.success("Enrollment deleted", {
+          action: {
+            label: "Undo",
+            onClick: () =>
+              restoreEnrollment.mutate(enrollmentId, {
+                onError: (err) => toast.error(String(err)),
+              }),
+          },
+        }),
+      onError: (err) => toast.error(String(err)),
+    });
+  };
+
   if (isLoading) {
     return (
       <div className="flex items-center justify-center h-64">
@@ -82,6 +102,21 @@ export function ClientDetailPage() {
                 </span>
               )}
             </div>
+            {nextAction && (
+              <p className="mt-1 flex items-center gap-1.5 text-sm">
+                <CalendarClock className="h-3.5 w-3.5 text-muted-foreground" />
+                <span>
+                  Next: {nextAction.subject || nextAction.entry_type.toLowerCase()}{" "}
+                  {new Date(nextAction.follow_up_date + "T00:00:00").toLocaleDateString("en-US", { month: "short", day: "numeric" })}
+                  {nextAction.follow_up_note && ` \u2014 ${nextAction.follow_up_note}`}
+                </span>
+                {nextAction.last_entry_at && (
+                  <span className="text-muted-foreground">
+                    · last contact {formatTimestamp(nextAction.last_entry_at)}
+                  </span>
+                )}
+              </p>
+            )}
           </div>
         </div>
         <Button onClick={() => navigate(`/clients/${id}/edit`)}>
@@ -191,8 +226,13 @@ export function ClientDetailPage() {
         <CardContent>
           <dl className="grid grid-cols-2 md:grid-cols-4 gap-4">
             <Field label="Date of Birth" value={client.dob} />
+            <Field label="Age" value={client.age} />
+            {client.age != null && client.age < 65 && (
+              <Field label="Turns 65" value={client.turns_65_on} />
+            )}
             <Field label="Gender" value={client.gender} />
             <Field label="Lead Source" value={client.lead_source} />
+            <Field label="Last Contact" value={client.last_contact_at ? formatDaysAgo(client.last_contact_at) : "Never"} />
           </dl>
         </CardContent>
       </Card>
@@ -228,6 +268,7 @@ export function ClientDetailPage() {
                   <th className="h-10 px-4 text-left font-medium text-muted-foreground">Type</th>
                   <th className="h-10 px-4 text-left font-medium text-muted-foreground">Status</th>
                   <th className="h-10 px-4 text-left font-medium text-muted-foreground">Effective</th>
+                  <th className="h-10 w-12 px-4" />
                 </tr>
               </thead>
               <tbody>
@@ -238,6 +279,18 @@ export function ClientDetailPage() {
                     <td className="px-4 py-3">{e.plan_type || "\u2014"}</td>
                     <td className="px-4 py-3">{e.status || "\u2014"}</td>
                     <td className="px-4 py-3">{e.effective_date || "\u2014"}</td>
+                    <td className="px-2 py-3 text-right">
+                      <Button
+                        variant="ghost"
+                        size="icon"
+                        className="h-7 w-7 text-muted-foreground hover:text-destructive"
+                        title="Delete enrollment"
+                        disabled={deleteEnrollment.isPending}
+                        onClick={() => handleDeleteEnrollment(e.id)}
+                      >
+                        <Trash2 className="h-4 w-4" />
+                      </Button>
+                    </td>
                   </tr>
                 ))}
               </tbody>
@@ -10,6 +10,9 @@ import {
   type SortingState,
 } from "@tanstack/react-table";
 import { useClients } from "@/hooks/useClients";
+import { tauriInvoke } from "@/lib/tauri";
+import { formatDaysAgo, formatTimestamp } from "@/lib/utils";
+import { toast } from "sonner";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import {
@@ -20,10 +23,33 @@ import {
   SelectValue,
 } from "@/components/ui/select";
 import type { ClientListItem, ClientFilters } from "@/types";
-import { Plus, Search, X, ChevronLeft, ChevronRight, Loader2, ArrowUp, ArrowDown, ArrowUpDown } from "lucide-react";
+import { Plus, Search, X, ChevronLeft, ChevronRight, Loader2, ArrowUp, ArrowDown, ArrowUpDown, Contact, FileSpreadsheet } from "lucide-react";
 
 const columnHelper = createColumnHelper<ClientListItem>();
 
+/** Render an FTS snippet, bolding the `<mark>`-wrapped matches without innerHTML */
+function MatchSnippet({ snippet }: { snippet: string }) {
+  const parts = snippet.split(/<mark>|<\/mark>/);
+  return (
+    <span className="text-xs text-muted-foreground">
+      {parts.map((part, i) =>
+        i % 2 === 1 ? (
+          <mark key={i} className="bg-yellow-200/60 text-foreground rounded-sm dark:bg-yellow-500/30">{part}</mark>
+        ) : (
+          part
+        )
+      )}
+    </span>
+  );
+}
+
+const SORT_OPTIONS: Record<string, { label: string; filters: Pick<ClientFilters, "sort_by" | "sort_dir"> }> = {
+  name: { label: "Name", filters: {} },
+  newest: { label: "Newest first", filters: { sort_by: "created_at", sort_dir: "desc" } },
+  city: { label: "City", filters: { sort_by: "city" } },
+  state: { label: "State", filters: { sort_by: "state" } },
+};
+
 export function ClientsPage() {
   const navigate = useNavigate();
   const setPageSubtitle = useAppStore((s) => s.setPageSubtitle);
@@ -39,6 +65,9 @@ export function ClientsPage() {
   const [page, setPage] = useState(initialPage);
   const [perPageOption, setPerPageOption] = useState(initialPerPage);
   const [showInactive, setShowInactive] = useState(false);
+  const [sortOption, setSortOption] = useState("name");
+  // YYYY-MM-DD; "" shows everyone
+  const [noContactSince, setNoContactSince] = useState("");
 
   // Sync state changes to URL (replace, not push)
   useEffect(() => {
@@ -63,8 +92,11 @@ export function ClientsPage() {
 
   const filters: ClientFilters = useMemo(() => ({
     search: debouncedSearch || undefined,
+    include_snippets: debouncedSearch ? true : undefined,
     is_active: showInactive ? undefined : true,
-  }), [debouncedSearch, showInactive]);
+    no_contact_since: noContactSince || undefined,
+    ...SORT_OPTIONS[sortOption].filters,
+  }), [debouncedSearch, showInactive, noContactSince, sortOption]);
 
   const [sorting, setSorting] = useState<SortingState>([]);
   const perPage = perPageOption === "all" ? 9999 : Number(perPageOption);
@@ -106,7 +138,30 @@ export function ClientsPage() {
       header: "Plan",
       cell: (info) => info.getValue() || "\u2014",
     }),
-  ], []);
+    ...(debouncedSearch
+      ? [
+          columnHelper.accessor("match_snippet", {
+            header: "Matched",
+            enableSorting: false,
+            cell: (info) => {
+              const snippet = info.getValue();
+              return snippet ? <MatchSnippet snippet={snippet} /> : "\u2014";
+            },
+          }),
+        ]
+      : []),
+    columnHelper.accessor("last_contact_at", {
+      header: "Last Contact",
+      cell: (info) => {
+        const value = info.getValue();
+        return value ? (
+          <span title={formatTimestamp(value)}>{formatDaysAgo(value)}</span>
+        ) : (
+          <span className="text-muted-foreground">Never</span>
+        );
+      },
+    }),
+  ], [debouncedSearch]);
 
   const table = useReactTable({
     data: data?.items ?? [],
@@ -117,7 +172,25 @@ export function ClientsPage() {
     getSortedRowModel: getSortedRowModel(),
   });
 
-  const totalPages = data ? Math.ceil(data.total / perPage) : 0;
+  // The backend caps the page size (max_page_size), so page by what it served
+  const totalPages = data ? Math.ceil(data.total / data.per_page) : 0;
+
+  // Exports every client matching the current filters, not just this page
+  const handleExport = async (format: "vcard" | "csv") => {
+    const [name, extension] = format === "vcard" ? ["vCard", "vcf"] : ["CSV", "csv"];
+    try {
+      const { save } = await import("@tauri-apps/plugin-dialog");
+      const outputPath = await save({
+        filters: [{ name, extensions: [extension] }],
+        defaultPath: `clients.${extension}`,
+      });
+      if (!outputPath) return;
+      const count = await tauriInvoke<number>(`export_clients_${format}`, { filters, outputPath });
+      toast.success(`Exported ${count} clients to ${outputPath}`);
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : `Failed to export ${name}`);
+    }
+  };
 
   return (
     <div className="space-y-4">
@@ -148,6 +221,36 @@ export function ClientsPage() {
           />
           Show inactive
         </label>
+        <label className="flex items-center gap-2 text-sm text-muted-foreground whitespace-nowrap">
+          No contact since
+          <Input
+            type="date"
+            value={noContactSince}
+            onChange={(e) => { setNoContactSince(e.target.value); setPage(1); }}
+            className="h-9 w-[150px]"
+          />
+        </label>
+        <Select
+          value={sortOption}
+          onValueChange={(val) => { setSortOption(val); setPage(1); }}
+        >
+          <SelectTrigger className="h-9 w-[150px]">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            {Object.entries(SORT_OPTIONS).map(([key, opt]) => (
+              <SelectItem key={key} value={key}>{opt.label}</SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+        <Button variant="outline" onClick={() => handleExport("csv")}>
+          <FileSpreadsheet className="mr-2 h-4 w-4" />
+          CSV
+        </Button>
+        <Button variant="outline" onClick={() => handleExport("vcard")}>
+          <Contact className="mr-2 h-4 w-4" />
+          vCard
+        </Button>
         <Button onClick={() => navigate("/clients/new")}>
           <Plus className="mr-2 h-4 w-4" />
           New Client
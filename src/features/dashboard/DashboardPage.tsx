@@ -1,11 +1,20 @@
-import { useQuery } from "@tanstack/react-query";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
 import { tauriInvoke } from "@/lib/tauri";
+import { toast } from "sonner";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { OUTCOME_LABELS } from "@/features/engagement";
 import {
   PieChart, Pie, Cell, AreaChart, Area,
   XAxis, YAxis, CartesianGrid, Tooltip, ResponsiveContainer, Legend,
 } from "recharts";
-import { Users, UserPlus, UserMinus, Clock, Loader2 } from "lucide-react";
+import { Users, UserPlus, UserMinus, Clock, Loader2, AlertCircle, CalendarClock, DollarSign } from "lucide-react";
 import type { DashboardStats } from "@/types";
 
 const COLORS = ["#3B82F6", "#10B981", "#F59E0B", "#EF4444", "#8B5CF6", "#EC4899", "#06B6D4", "#84CC16"];
@@ -50,6 +59,71 @@ function StatCard({ title, value, icon: Icon, description }: {
   );
 }
 
+function CallStatsCard({ stats }: { stats: DashboardStats }) {
+  const queryClient = useQueryClient();
+  const totalCalls = stats.call_stats.reduce((sum, [, count]) => sum + count, 0);
+  const answered = stats.call_stats.find(([outcome]) => outcome === "ANSWERED")?.[1] ?? 0;
+
+  const handleWindowChange = async (days: string) => {
+    try {
+      await tauriInvoke("update_settings", { settings: { call_stats_window_days: days } });
+      queryClient.invalidateQueries({ queryKey: ["dashboard-stats"] });
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Failed to save settings");
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader className="flex flex-row items-center justify-between space-y-0">
+        <CardTitle className="text-lg">Calls</CardTitle>
+        <Select value={String(stats.call_stats_window_days)} onValueChange={handleWindowChange}>
+          <SelectTrigger className="h-8 w-[130px]">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            {[7, 30, 90].map((d) => (
+              <SelectItem key={d} value={String(d)}>Last {d} days</SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </CardHeader>
+      <CardContent>
+        {totalCalls > 0 ? (
+          <div className="space-y-4">
+            <div className="flex gap-8 text-sm">
+              <div>
+                <div className="text-2xl font-bold">{totalCalls}</div>
+                <p className="text-muted-foreground">calls</p>
+              </div>
+              <div>
+                <div className="text-2xl font-bold">{Math.round((answered / totalCalls) * 100)}%</div>
+                <p className="text-muted-foreground">connect rate</p>
+              </div>
+              <div>
+                <div className="text-2xl font-bold">{stats.total_call_minutes}</div>
+                <p className="text-muted-foreground">minutes on calls</p>
+              </div>
+            </div>
+            <div className="space-y-1 text-sm">
+              {stats.call_stats.map(([outcome, count]) => (
+                <div key={outcome} className="flex justify-between">
+                  <span>{OUTCOME_LABELS[outcome] || outcome}</span>
+                  <span className="text-muted-foreground">{count}</span>
+                </div>
+              ))}
+            </div>
+          </div>
+        ) : (
+          <p className="text-sm text-muted-foreground text-center py-12">
+            No calls logged in the last {stats.call_stats_window_days} days
+          </p>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
+
 export function DashboardPage() {
   const { data: stats, isLoading } = useDashboardStats();
 
@@ -76,12 +150,55 @@ export function DashboardPage() {
 
   return (
     <div className="space-y-6">
+      {/* Open enrollment periods */}
+      {stats.active_enrollment_periods.length > 0 && (
+        <div className="flex flex-wrap gap-2">
+          {stats.active_enrollment_periods.map((p) => (
+            <div
+              key={p.code}
+              className="rounded-md border border-primary/30 bg-primary/5 px-3 py-2 text-sm"
+            >
+              <span className="font-medium">{p.name}</span>
+              <span className="text-muted-foreground">
+                {" "}
+                ·{" "}
+                {p.days_remaining === 0
+                  ? "ends today"
+                  : `${p.days_remaining} day${p.days_remaining === 1 ? "" : "s"} left`}
+              </span>
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* KPI Cards */}
       <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-4">
         <StatCard title="Active Clients" value={stats.total_active_clients} icon={Users} />
         <StatCard title="New This Month" value={stats.new_this_month} icon={UserPlus} />
         <StatCard title="Lost This Month" value={stats.lost_this_month} icon={UserMinus} />
         <StatCard title="Pending Enrollments" value={stats.pending_enrollments} icon={Clock} />
+        <StatCard title="Overdue Follow-ups" value={stats.overdue_follow_ups} icon={AlertCircle} />
+        <StatCard
+          title="Follow-ups Next 7 Days"
+          value={stats.follow_ups_next_7_days}
+          icon={CalendarClock}
+        />
+        <Card>
+          <CardHeader className="flex flex-row items-center justify-between pb-2">
+            <CardTitle className="text-sm font-medium text-muted-foreground">
+              Commission This Month
+            </CardTitle>
+            <DollarSign className="h-4 w-4 text-muted-foreground" />
+          </CardHeader>
+          <CardContent>
+            <div className="text-2xl font-bold">
+              {`$${stats.paid_commission_this_month.toFixed(2)}`}
+            </div>
+            <p className="text-xs text-muted-foreground mt-1">
+              of ${stats.projected_commission_this_month.toFixed(2)} projected
+            </p>
+          </CardContent>
+        </Card>
       </div>
 
       {/* Charts Row */}
@@ -161,6 +278,8 @@ export function DashboardPage() {
         </Card>
       </div>
 
+      <CallStatsCard stats={stats} />
+
       {/* Monthly Trend */}
       <Card>
         <CardHeader>
@@ -40,6 +40,8 @@ const entrySchema = z.object({
   occurred_at: z.string().optional(),
   follow_up_date: z.string().optional(),
   follow_up_note: z.string().optional(),
+  follow_up_recurrence: z.enum(["NONE", "WEEKLY", "MONTHLY", "QUARTERLY"]).optional(),
+  follow_up_until: z.string().optional(),
   call_direction: z.enum(["INBOUND", "OUTBOUND"]).optional(),
   call_duration: z.coerce.number().int().min(0).optional(),
   call_outcome: z
@@ -110,6 +112,8 @@ export function EntryFormDialog({
           occurred_at: toDatetimeLocal(editEntry.occurred_at),
           follow_up_date: toDateOnly(editEntry.follow_up_date),
           follow_up_note: editEntry.follow_up_note ?? "",
+          follow_up_recurrence: editEntry.follow_up_recurrence ?? undefined,
+          follow_up_until: toDateOnly(editEntry.follow_up_until),
           call_direction: editEntry.call_direction ?? undefined,
           call_duration: editEntry.call_duration ?? undefined,
           call_outcome: editEntry.call_outcome ?? undefined,
@@ -143,6 +147,8 @@ export function EntryFormDialog({
             occurred_at: values.occurred_at || undefined,
             follow_up_date: values.follow_up_date || undefined,
             follow_up_note: values.follow_up_note || undefined,
+            follow_up_recurrence: values.follow_up_recurrence || undefined,
+            follow_up_until: values.follow_up_until || undefined,
             call_direction: values.call_direction || undefined,
             call_duration: values.call_duration || undefined,
             call_outcome: values.call_outcome || undefined,
@@ -166,6 +172,8 @@ export function EntryFormDialog({
           occurred_at: values.occurred_at || undefined,
           follow_up_date: values.follow_up_date || undefined,
           follow_up_note: values.follow_up_note || undefined,
+          follow_up_recurrence: values.follow_up_recurrence || undefined,
+          follow_up_until: values.follow_up_until || undefined,
           call_direction: values.call_direction || undefined,
           call_duration: values.call_duration || undefined,
           call_outcome: values.call_outcome || undefined,
@@ -315,6 +323,35 @@ export function EntryFormDialog({
             </div>
           )}
 
+          {/* SMS-specific fields */}
+          {entryType === "SMS" && (
+            <div className="space-y-4 rounded-md border p-3">
+              <p className="text-xs font-medium uppercase text-muted-foreground">
+                Message Details
+              </p>
+              <div className="space-y-2">
+                <Label>Direction</Label>
+                <Select
+                  value={form.watch("call_direction") || ""}
+                  onValueChange={(v) =>
+                    form.setValue(
+                      "call_direction",
+                      v as "INBOUND" | "OUTBOUND"
+                    )
+                  }
+                >
+                  <SelectTrigger>
+                    <SelectValue placeholder="Select..." />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="INBOUND">Received</SelectItem>
+                    <SelectItem value="OUTBOUND">Sent</SelectItem>
+                  </SelectContent>
+                </Select>
+              </div>
+            </div>
+          )}
+
           {/* EMAIL-specific fields */}
           {entryType === "EMAIL" && (
             <div className="space-y-4 rounded-md border p-3">
@@ -404,6 +441,39 @@ export function EntryFormDialog({
                   placeholder="Remind to..."
                 />
               </div>
+              <div className="space-y-2">
+                <Label>Repeat</Label>
+                <Select
+                  value={form.watch("follow_up_recurrence") || "NONE"}
+                  onValueChange={(v) =>
+                    form.setValue(
+                      "follow_up_recurrence",
+                      v as EntryFormValues["follow_up_recurrence"]
+                    )
+                  }
+                >
+                  <SelectTrigger>
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="NONE">Does not repeat</SelectItem>
+                    <SelectItem value="WEEKLY">Weekly</SelectItem>
+                    <SelectItem value="MONTHLY">Monthly</SelectItem>
+                    <SelectItem value="QUARTERLY">Quarterly</SelectItem>
+                  </SelectContent>
+                </Select>
+              </div>
+              {form.watch("follow_up_recurrence") &&
+                form.watch("follow_up_recurrence") !== "NONE" && (
+                  <div className="space-y-2">
+                    <Label htmlFor="follow-up-until">Until (optional)</Label>
+                    <Input
+                      id="follow-up-until"
+                      type="date"
+                      {...form.register("follow_up_until")}
+                    />
+                  </div>
+                )}
             </div>
           </div>
 
@@ -44,7 +44,7 @@ function formatDate(dateStr?: string): string {
   });
 }
 
-const OUTCOME_LABELS: Record<string, string> = {
+export const OUTCOME_LABELS: Record<string, string> = {
   ANSWERED: "Answered",
   NO_ANSWER: "No Answer",
   VOICEMAIL: "Voicemail",
@@ -80,6 +80,17 @@ function EntryIcon({ entry }: { entry: Entry }) {
   }
 }
 
+function SmsDetails({ entry }: { entry: Entry }) {
+  if (!entry.call_direction) return null;
+  return (
+    <div className="flex flex-wrap items-center gap-2 text-xs">
+      <Badge variant="outline" className="text-xs">
+        {entry.call_direction === "INBOUND" ? "Received" : "Sent"}
+      </Badge>
+    </div>
+  );
+}
+
 function CallDetails({ entry }: { entry: Entry }) {
   return (
     <div className="flex flex-wrap items-center gap-2 text-xs">
@@ -212,6 +223,7 @@ export function TimelineCard({
 
             {/* Type-specific details */}
             {entry.entry_type === "CALL" && <CallDetails entry={entry} />}
+            {entry.entry_type === "SMS" && <SmsDetails entry={entry} />}
             {entry.entry_type === "EMAIL" && <EmailDetails entry={entry} />}
             {entry.entry_type === "MEETING" && (
               <MeetingDetails entry={entry} />
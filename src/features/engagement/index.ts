@@ -1,7 +1,7 @@
 export { ClientEngagementSection } from "./ClientEngagementSection";
 export { ConversationList } from "./ConversationList";
 export { ConversationDetail } from "./ConversationDetail";
-export { TimelineCard } from "./TimelineCard";
+export { TimelineCard, OUTCOME_LABELS } from "./TimelineCard";
 export { EntryFormDialog } from "./EntryFormDialog";
 export { NewConversationDialog } from "./NewConversationDialog";
 export { FollowUpBadge } from "./FollowUpBadge";
@@ -1,8 +1,15 @@
-import { useEnrollments } from "@/hooks/useEnrollments";
-import { Loader2 } from "lucide-react";
+import { useState } from "react";
+import { useEnrollmentsPage } from "@/hooks/useEnrollments";
+import { Button } from "@/components/ui/button";
+import { Loader2, ChevronLeft, ChevronRight } from "lucide-react";
+
+const PER_PAGE = 50;
 
 export function EnrollmentsPage() {
-  const { data: enrollments, isLoading } = useEnrollments();
+  const [page, setPage] = useState(1);
+  const { data, isLoading } = useEnrollmentsPage(page, PER_PAGE);
+  const enrollments = data?.items;
+  const totalPages = data ? Math.ceil(data.total / data.per_page) : 0;
 
   return (
     <div className="space-y-4">
@@ -46,6 +53,32 @@ export function EnrollmentsPage() {
           </tbody>
         </table>
       </div>
+
+      {totalPages > 1 && (
+        <div className="flex items-center justify-end gap-4">
+          <p className="text-sm text-muted-foreground">
+            Page {page} of {totalPages} ({data?.total} enrollments)
+          </p>
+          <div className="flex items-center gap-2">
+            <Button
+              variant="outline"
+              size="sm"
+              disabled={page <= 1}
+              onClick={() => setPage((p) => p - 1)}
+            >
+              <ChevronLeft className="h-4 w-4" />
+            </Button>
+            <Button
+              variant="outline"
+              size="sm"
+              disabled={page >= totalPages}
+              onClick={() => setPage((p) => p + 1)}
+            >
+              <ChevronRight className="h-4 w-4" />
+            </Button>
+          </div>
+        </div>
+      )}
     </div>
   );
 }
@@ -4,9 +4,10 @@ import { useQueryClient } from "@tanstack/react-query";
 import { Button } from "@/components/ui/button";
 import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "@/components/ui/card";
 import { tauriInvoke } from "@/lib/tauri";
+import { useImportMappings, useSaveImportMapping } from "@/hooks";
 import { toast } from "sonner";
 import { Dialog, DialogContent, DialogHeader, DialogTitle, DialogDescription } from "@/components/ui/dialog";
-import { Upload, FileSpreadsheet, CheckCircle2, AlertCircle, Loader2, ArrowRight, ArrowLeft, Check, Plus, X, ChevronDown, ChevronRight, Minus } from "lucide-react";
+import { Upload, FileSpreadsheet, CheckCircle2, AlertCircle, Loader2, ArrowRight, ArrowLeft, Check, Plus, X, ChevronDown, ChevronRight, Minus, AlertTriangle } from "lucide-react";
 
 type Step = "select" | "map" | "review" | "result";
 
@@ -15,30 +16,44 @@ interface ParseResult {
   sample_rows: string[][];
   total_rows: number;
   auto_mapping: Record<string, string>;
+  mapping_confidence: Record<string, number>;
+  /** Saved preset applied to this file's headers, if one fit */
+  preset: { id: string; name: string } | null;
 }
 
 interface ImportRowDetail {
+  row_number?: number;
   label: string;
   detail: string;
 }
 
 interface ImportResultData {
+  import_log_id: string;
   inserted: number;
   updated: number;
   skipped: number;
   errors: number;
   total: number;
+  enrollments_created: number;
   inserted_details: ImportRowDetail[];
   updated_details: ImportRowDetail[];
   skipped_details: ImportRowDetail[];
   errors_details: ImportRowDetail[];
 }
 
+interface UndoImportResult {
+  clients_removed: number;
+  clients_restored: number;
+  enrollments_removed: number;
+  kept_details: ImportRowDetail[];
+}
+
 interface ImportPreview {
   inserts: PreviewInsert[];
   updates: PreviewUpdate[];
   skipped: PreviewSkipped[];
-  errors: { row_number: number; data: string[]; errors: string[] }[];
+  errors: { row_number: number; data: string[]; errors: string[]; warnings: string[] }[];
+  warnings: { row_number: number; data: string[]; errors: string[]; warnings: string[] }[];
 }
 
 interface PreviewInsert {
@@ -57,6 +72,8 @@ interface FieldDiff {
   field: string;
   old_value: string;
   new_value: string;
+  /** The import would replace a value the client already has */
+  replaces_existing: boolean;
 }
 
 interface PreviewSkipped {
@@ -93,6 +110,12 @@ export function ImportPage() {
   const [loading, setLoading] = useState(false);
   const [detailCategory, setDetailCategory] = useState<string | null>(null);
   const [constantMappings, setConstantMappings] = useState<{ value: string; field: string }[]>([]);
+  const [onlyFillEmpty, setOnlyFillEmpty] = useState(false);
+  const [presetName, setPresetName] = useState("");
+  const [confirmUndo, setConfirmUndo] = useState(false);
+  const [undoResult, setUndoResult] = useState<UndoImportResult | null>(null);
+  const { data: presets } = useImportMappings();
+  const saveMapping = useSaveImportMapping();
 
   const handleSelectFile = useCallback(async () => {
     try {
@@ -107,6 +130,7 @@ export function ImportPage() {
         const result = await tauriInvoke<ParseResult>("parse_import_file", { filePath: selected });
         setParseResult(result);
         setMapping(result.auto_mapping);
+        setPresetName(result.preset?.name ?? "");
         setStep("map");
       }
     } catch (err) {
@@ -128,6 +152,7 @@ export function ImportPage() {
         filePath,
         columnMapping: mapping,
         constantValues: Object.keys(constants).length > 0 ? constants : null,
+        onlyFillEmpty,
       });
       setPreview(result);
       // Initialize all inserts as approved
@@ -145,7 +170,7 @@ export function ImportPage() {
     } finally {
       setLoading(false);
     }
-  }, [filePath, mapping, constantMappings]);
+  }, [filePath, mapping, constantMappings, onlyFillEmpty]);
 
   const handleImport = useCallback(async () => {
     if (!filePath || !mapping) return;
@@ -162,24 +187,83 @@ export function ImportPage() {
           serializedApproved[clientId] = Array.from(fields);
         }
       }
+      // Create enrollments too when the file carries plan/carrier columns
+      const importEnrollments = Object.values(mapping).some(
+        (field) => field === "plan_name" || field === "carrier_name"
+      );
       const result = await tauriInvoke<ImportResultData>("execute_import", {
         filePath,
         columnMapping: mapping,
         constantValues: Object.keys(constants).length > 0 ? constants : null,
         approvedUpdates: serializedApproved,
         approvedInserts: Array.from(approvedInserts),
+        importEnrollments,
+        onlyFillEmpty,
       });
       setImportResult(result);
       setStep("result");
       queryClient.invalidateQueries({ queryKey: ["clients"] });
       queryClient.invalidateQueries({ queryKey: ["dashboard-stats"] });
-      toast.success(`Imported ${result.inserted} new clients, updated ${result.updated}`);
+      queryClient.invalidateQueries({ queryKey: ["enrollments"] });
+      toast.success(
+        `Imported ${result.inserted} new clients, updated ${result.updated}` +
+          (result.enrollments_created > 0 ? `, ${result.enrollments_created} enrollments` : "")
+      );
     } catch (err) {
       toast.error(typeof err === "string" ? err : "Import failed");
     } finally {
       setLoading(false);
     }
-  }, [filePath, mapping, constantMappings, approvedInserts, approvedUpdates, queryClient]);
+  }, [filePath, mapping, constantMappings, approvedInserts, approvedUpdates, onlyFillEmpty, queryClient]);
+
+  const handleUndo = useCallback(async () => {
+    if (!importResult) return;
+    setLoading(true);
+    try {
+      const result = await tauriInvoke<UndoImportResult>("undo_import", {
+        importLogId: importResult.import_log_id,
+      });
+      setUndoResult(result);
+      queryClient.invalidateQueries({ queryKey: ["clients"] });
+      queryClient.invalidateQueries({ queryKey: ["dashboard-stats"] });
+      queryClient.invalidateQueries({ queryKey: ["enrollments"] });
+      toast.success(
+        `Import undone: removed ${result.clients_removed} clients, restored ${result.clients_restored}` +
+          (result.kept_details.length > 0 ? ` (${result.kept_details.length} kept later edits)` : "")
+      );
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Undo failed");
+    } finally {
+      setLoading(false);
+      setConfirmUndo(false);
+    }
+  }, [importResult, queryClient]);
+
+  /** Replace the mapping with a preset's, matching headers ignoring case */
+  const applyPreset = (presetId: string) => {
+    const preset = presets?.find((p) => p.id === presetId);
+    if (!preset || !parseResult) return;
+    const byKey = new Map(parseResult.headers.map((h) => [h.trim().toLowerCase(), h]));
+    const next: Record<string, string> = {};
+    for (const [header, target] of Object.entries(preset.mapping)) {
+      const fileHeader = byKey.get(header.trim().toLowerCase());
+      if (fileHeader) next[fileHeader] = target;
+    }
+    setMapping(next);
+    setPresetName(preset.name);
+  };
+
+  const handleSaveMapping = () => {
+    const name = presetName.trim();
+    if (!name) return;
+    saveMapping.mutate(
+      { name, mapping },
+      {
+        onSuccess: () => toast.success(`Saved mapping "${name}"`),
+        onError: (err) => toast.error(typeof err === "string" ? err : "Failed to save mapping"),
+      }
+    );
+  };
 
   const toggleClientApproval = (clientId: string, allFields: string[]) => {
     setApprovedUpdates((prev) => {
@@ -285,18 +369,62 @@ export function ImportPage() {
             <CardTitle>Map Columns</CardTitle>
             <CardDescription>
               {parseResult.total_rows} rows found. Match source columns to client fields.
-              Green = auto-mapped.
+              Green = auto-mapped. Amber = fuzzy guess, please check.
+              {parseResult.preset && <> Using saved mapping "{parseResult.preset.name}".</>}
             </CardDescription>
           </CardHeader>
           <CardContent className="space-y-4">
+            <div className="flex items-center gap-2">
+              {presets && presets.length > 0 && (
+                <select
+                  value=""
+                  onChange={(e) => applyPreset(e.target.value)}
+                  className="flex h-9 w-1/3 rounded-md border border-input bg-background text-foreground px-3 py-1 text-sm"
+                >
+                  <option value="">Load saved mapping...</option>
+                  {presets.map((p) => (
+                    <option key={p.id} value={p.id}>{p.name}</option>
+                  ))}
+                </select>
+              )}
+              <input
+                type="text"
+                value={presetName}
+                onChange={(e) => setPresetName(e.target.value)}
+                placeholder="Mapping name, e.g. Humana Export"
+                className="flex h-9 w-1/3 rounded-md border border-input bg-background px-3 py-1 text-sm"
+              />
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={handleSaveMapping}
+                disabled={!presetName.trim() || Object.keys(mapping).length === 0 || saveMapping.isPending}
+              >
+                Save Mapping
+              </Button>
+            </div>
+
             <div className="space-y-2">
               {parseResult.headers.map((header) => {
                 const isMapped = !!mapping[header];
+                const confidence = parseResult.mapping_confidence?.[header];
+                const isGuess =
+                  isMapped &&
+                  confidence !== undefined &&
+                  confidence < 1 &&
+                  mapping[header] === parseResult.auto_mapping[header];
                 return (
                   <div key={header} className="flex items-center gap-4">
-                    <div className={`w-1/3 text-sm font-medium truncate ${isMapped ? "text-green-600" : "text-muted-foreground"}`}>
-                      {isMapped && <Check className="inline h-3 w-3 mr-1" />}
+                    <div className={`w-1/3 text-sm font-medium truncate ${isGuess ? "text-amber-600" : isMapped ? "text-green-600" : "text-muted-foreground"}`}>
+                      {isGuess ? (
+                        <AlertCircle className="inline h-3 w-3 mr-1" />
+                      ) : (
+                        isMapped && <Check className="inline h-3 w-3 mr-1" />
+                      )}
                       {header}
+                      {isGuess && (
+                        <span className="ml-1 text-xs font-normal">({Math.round(confidence * 100)}% match)</span>
+                      )}
                     </div>
                     <ArrowRight className="h-4 w-4 text-muted-foreground flex-shrink-0" />
                     <select
@@ -378,6 +506,19 @@ export function ImportPage() {
               </div>
             )}
 
+            <label className="flex items-center gap-2 pt-4 text-sm">
+              <input
+                type="checkbox"
+                checked={onlyFillEmpty}
+                onChange={(e) => setOnlyFillEmpty(e.target.checked)}
+                className="h-4 w-4 rounded border-gray-300"
+              />
+              Only fill empty fields on existing clients
+              <span className="text-xs text-muted-foreground">
+                (values already on file are never overwritten)
+              </span>
+            </label>
+
             <div className="flex items-center gap-2 pt-4">
               <Button variant="outline" onClick={() => setStep("select")}>
                 <ArrowLeft className="mr-2 h-4 w-4" /> Back
@@ -443,6 +584,25 @@ export function ImportPage() {
               </CollapsibleSection>
             )}
 
+            {/* Warnings section (rows still import) */}
+            {preview.warnings.length > 0 && (
+              <CollapsibleSection title="Data quality warnings" count={preview.warnings.length} defaultOpen={false}>
+                <div className="max-h-48 overflow-y-auto rounded border">
+                  {preview.warnings.slice(0, 20).map((row) => (
+                    <div key={row.row_number} className="px-3 py-2 border-b last:border-b-0 text-xs">
+                      <span className="font-medium">Row {row.row_number}:</span>{" "}
+                      <span className="text-amber-600">{row.warnings.join("; ")}</span>
+                    </div>
+                  ))}
+                  {preview.warnings.length > 20 && (
+                    <div className="px-3 py-2 text-xs text-muted-foreground">
+                      ... and {preview.warnings.length - 20} more rows with warnings
+                    </div>
+                  )}
+                </div>
+              </CollapsibleSection>
+            )}
+
             {/* New clients section */}
             {preview.inserts.length > 0 && (
               <CollapsibleSection
@@ -524,6 +684,15 @@ export function ImportPage() {
                           <span className="text-xs text-muted-foreground px-2 py-0.5 rounded-full bg-blue-100 dark:bg-blue-900/40">
                             {upd.diffs.length} {upd.diffs.length === 1 ? "change" : "changes"}
                           </span>
+                          {upd.diffs.some((d) => d.replaces_existing) && (
+                            <span
+                              className="flex items-center gap-1 text-xs text-amber-600 dark:text-amber-400"
+                              title="Some fields would replace data the client already has"
+                            >
+                              <AlertTriangle className="h-3.5 w-3.5" />
+                              {upd.diffs.filter((d) => d.replaces_existing).length} overwritten
+                            </span>
+                          )}
                           {isExpanded
                             ? <ChevronDown className="h-4 w-4 text-muted-foreground" />
                             : <ChevronRight className="h-4 w-4 text-muted-foreground" />
@@ -554,6 +723,12 @@ export function ImportPage() {
                                     </td>
                                     <td className="py-1.5 pr-3 text-muted-foreground capitalize whitespace-nowrap">
                                       {fieldLabel(diff.field)}
+                                      {diff.replaces_existing && (
+                                        <AlertTriangle
+                                          className="inline h-3 w-3 ml-1 text-amber-500"
+                                          aria-label="Replaces an existing value"
+                                        />
+                                      )}
                                     </td>
                                     <td className="py-1.5 pr-2">
                                       <span className="text-red-600 dark:text-red-400 bg-red-50 dark:bg-red-950/30 px-1.5 py-0.5 rounded text-xs">
@@ -640,16 +815,56 @@ export function ImportPage() {
 
             <p className="text-xs text-muted-foreground">Click a box to see per-row details</p>
 
+            {undoResult && (
+              <div className="rounded-md border p-3 text-sm space-y-1">
+                <div className="font-medium">This import was undone</div>
+                <div className="text-muted-foreground">
+                  Removed {undoResult.clients_removed} clients and {undoResult.enrollments_removed} enrollments;
+                  restored {undoResult.clients_restored} updated clients.
+                </div>
+                {undoResult.kept_details.map((d, i) => (
+                  <div key={i} className="text-xs text-amber-600">
+                    {d.label}: {d.detail}
+                  </div>
+                ))}
+              </div>
+            )}
+
             <div className="flex items-center gap-2 pt-4">
               <Button onClick={() => navigate("/clients")}>
                 View Clients
               </Button>
-              <Button variant="outline" onClick={() => { setStep("select"); setFilePath(""); setParseResult(null); setMapping({}); setPreview(null); setApprovedInserts(new Set()); setApprovedUpdates({}); setExpandedClients(new Set()); setImportResult(null); setConstantMappings([]); }}>
+              <Button variant="outline" onClick={() => { setStep("select"); setFilePath(""); setParseResult(null); setMapping({}); setPreview(null); setApprovedInserts(new Set()); setApprovedUpdates({}); setExpandedClients(new Set()); setImportResult(null); setConstantMappings([]); setOnlyFillEmpty(false); setPresetName(""); setUndoResult(null); }}>
                 Import Another File
               </Button>
+              {!undoResult && (importResult.inserted > 0 || importResult.updated > 0) && (
+                <Button variant="ghost" className="ml-auto text-red-600" onClick={() => setConfirmUndo(true)} disabled={loading}>
+                  Undo Import
+                </Button>
+              )}
             </div>
           </CardContent>
 
+          <Dialog open={confirmUndo} onOpenChange={setConfirmUndo}>
+            <DialogContent className="max-w-md">
+              <DialogHeader>
+                <DialogTitle>Undo this import?</DialogTitle>
+                <DialogDescription>
+                  Clients and enrollments it created will be deleted, and fields it changed
+                  on existing clients will get their old values back. Fields edited since
+                  the import are left as they are.
+                </DialogDescription>
+              </DialogHeader>
+              <div className="flex justify-end gap-2">
+                <Button variant="outline" onClick={() => setConfirmUndo(false)}>Cancel</Button>
+                <Button variant="destructive" onClick={handleUndo} disabled={loading}>
+                  {loading ? <Loader2 className="mr-2 h-4 w-4 animate-spin" /> : null}
+                  Undo Import
+                </Button>
+              </div>
+            </DialogContent>
+          </Dialog>
+
           <Dialog open={detailCategory !== null} onOpenChange={(open) => { if (!open) setDetailCategory(null); }}>
             <DialogContent className="max-w-lg max-h-[80vh] flex flex-col">
               <DialogHeader>
@@ -667,6 +882,9 @@ export function ImportPage() {
                     <div className="rounded border divide-y">
                       {details.map((row, i) => (
                         <div key={i} className="px-3 py-2 text-sm">
+                          {row.row_number != null && (
+                            <span className="text-muted-foreground mr-2">Row {row.row_number}:</span>
+                          )}
                           <span className="font-medium">{row.label}</span>
                           {row.detail && <span className="text-muted-foreground ml-2">— {row.detail}</span>}
                         </div>
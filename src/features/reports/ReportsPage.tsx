@@ -1,6 +1,7 @@
 import { useState } from "react";
 import { useQuery } from "@tanstack/react-query";
 import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
 import {
   Card,
   CardContent,
@@ -20,6 +21,8 @@ import {
   MapPin,
   CalendarPlus,
   AlertTriangle,
+  DollarSign,
+  UserX,
 } from "lucide-react";
 import type { ClientFilters } from "@/types";
 
@@ -109,6 +112,34 @@ const PRESET_REPORTS: ReportDef[] = [
       "state",
     ],
   },
+  {
+    name: "No Active Enrollment",
+    description: "Prospects and orphans with no active or pending plan",
+    icon: UserX,
+    filters: { is_active: true, enrollment_status: "NONE" },
+    columns: [
+      "first_name",
+      "last_name",
+      "dob",
+      "phone",
+      "email",
+      "city",
+      "state",
+    ],
+  },
+  {
+    name: "Commission by Client",
+    description: "Total tracked enrollment commission per client",
+    icon: DollarSign,
+    filters: { is_active: true },
+    columns: [
+      "first_name",
+      "last_name",
+      "phone",
+      "state",
+      "commission_amount",
+    ],
+  },
 ];
 
 interface ReportData {
@@ -120,15 +151,28 @@ interface ReportData {
 
 export function ReportsPage() {
   const [selectedReport, setSelectedReport] = useState<ReportDef | null>(null);
+  const [createdAfter, setCreatedAfter] = useState("");
+  const [createdBefore, setCreatedBefore] = useState("");
+  // Dashboard breakdown charted at the top of the PDF ("" = table only)
+  const [pdfChart, setPdfChart] = useState("");
+  // "" lets the backend turn wide reports sideways
+  const [pdfOrientation, setPdfOrientation] = useState("");
+
+  // Preset filters narrowed to clients added within the chosen dates
+  const reportFilters: ClientFilters = {
+    ...selectedReport?.filters,
+    created_after: createdAfter || undefined,
+    created_before: createdBefore || undefined,
+  };
 
-  const { data: reportData, isLoading } = useQuery({
-    queryKey: ["report", selectedReport?.name],
+  const { data: reportData, isLoading, error } = useQuery({
+    queryKey: ["report", selectedReport?.name, createdAfter, createdBefore],
     queryFn: () => {
       if (!selectedReport) return null;
       return tauriInvoke<ReportData>("get_report", {
         definition: {
           name: selectedReport.name,
-          filters: selectedReport.filters,
+          filters: reportFilters,
           columns: selectedReport.columns,
           sort_by: "last_name",
           sort_dir: "ASC",
@@ -145,11 +189,12 @@ export function ReportsPage() {
       const path = await tauriInvoke<string>("export_report_pdf", {
         definition: {
           name: selectedReport.name,
-          filters: selectedReport.filters,
+          filters: reportFilters,
           columns: selectedReport.columns,
           sort_by: "last_name",
           sort_dir: "ASC",
-          group_by: null,
+          group_by: pdfChart || null,
+          orientation: pdfOrientation || null,
         },
       });
       toast.success(`PDF saved to ${path}`);
@@ -159,35 +204,19 @@ export function ReportsPage() {
   };
 
   const handleExportExcel = async () => {
-    if (!reportData?.data) return;
+    if (!selectedReport) return;
     try {
-      const XLSX = await import("xlsx");
-      const ws = XLSX.utils.json_to_sheet(reportData.data);
-      const wb = XLSX.utils.book_new();
-      XLSX.utils.book_append_sheet(wb, ws, "Report");
-
-      // Auto-width columns
-      const colWidths = reportData.columns.map((col) => ({
-        wch:
-          Math.max(
-            col.length,
-            ...reportData.data.map((row) => (row[col] || "").length)
-          ) + 2,
-      }));
-      ws["!cols"] = colWidths;
-
-      const { save } = await import("@tauri-apps/plugin-dialog");
-      const filePath = await save({
-        filters: [{ name: "Excel", extensions: ["xlsx"] }],
-        defaultPath: `${selectedReport?.name?.replace(/\s+/g, "_")}.xlsx`,
+      const path = await tauriInvoke<string>("export_report_xlsx", {
+        definition: {
+          name: selectedReport.name,
+          filters: reportFilters,
+          columns: selectedReport.columns,
+          sort_by: "last_name",
+          sort_dir: "ASC",
+          group_by: null,
+        },
       });
-
-      if (filePath) {
-        const buffer = XLSX.write(wb, { type: "buffer", bookType: "xlsx" });
-        const { writeFile } = await import("@tauri-apps/plugin-fs");
-        await writeFile(filePath, new Uint8Array(buffer));
-        toast.success(`Excel saved to ${filePath}`);
-      }
+      toast.success(`Excel saved to ${path}`);
     } catch (err) {
       toast.error(typeof err === "string" ? err : "Failed to export Excel");
     }
@@ -250,6 +279,41 @@ export function ReportsPage() {
                     </CardDescription>
                   </div>
                   <div className="flex items-center gap-2">
+                    <span className="text-sm text-muted-foreground">Added</span>
+                    <Input
+                      type="date"
+                      className="h-8 w-[150px]"
+                      value={createdAfter}
+                      onChange={(e) => setCreatedAfter(e.target.value)}
+                    />
+                    <span className="text-sm text-muted-foreground">to</span>
+                    <Input
+                      type="date"
+                      className="h-8 w-[150px]"
+                      value={createdBefore}
+                      onChange={(e) => setCreatedBefore(e.target.value)}
+                    />
+                    <select
+                      value={pdfChart}
+                      onChange={(e) => setPdfChart(e.target.value)}
+                      className="h-8 rounded-md border border-input bg-background text-foreground px-2 text-sm"
+                      title="Chart included in the PDF"
+                    >
+                      <option value="">No chart</option>
+                      <option value="carrier">Chart by carrier</option>
+                      <option value="plan_type">Chart by plan type</option>
+                      <option value="state">Chart by state</option>
+                    </select>
+                    <select
+                      value={pdfOrientation}
+                      onChange={(e) => setPdfOrientation(e.target.value)}
+                      className="h-8 rounded-md border border-input bg-background text-foreground px-2 text-sm"
+                      title="PDF page orientation"
+                    >
+                      <option value="">Auto</option>
+                      <option value="portrait">Portrait</option>
+                      <option value="landscape">Landscape</option>
+                    </select>
                     <Button
                       variant="outline"
                       size="sm"
@@ -275,6 +339,10 @@ export function ReportsPage() {
                   <div className="flex items-center justify-center py-12">
                     <Loader2 className="h-6 w-6 animate-spin text-muted-foreground" />
                   </div>
+                ) : error ? (
+                  <p className="text-sm text-destructive text-center py-12">
+                    {String(error)}
+                  </p>
                 ) : reportData && reportData.data.length > 0 ? (
                   <div className="rounded-md border overflow-x-auto">
                     <table className="w-full text-sm">
@@ -8,15 +8,17 @@ import { Separator } from "@/components/ui/separator";
 import { tauriInvoke } from "@/lib/tauri";
 import { cn } from "@/lib/utils";
 import { toast } from "sonner";
-import { Save, Download, Key, User, Loader2, Shield, Sun, Moon, Monitor, Palette, Database, HardDrive, Users, FileText, Clock } from "lucide-react";
+import { Save, Download, Key, User, Loader2, Shield, Sun, Moon, Monitor, Palette, Database, HardDrive, Users, FileText, Clock, ScrollText, Activity, Search } from "lucide-react";
 import { Tooltip, TooltipContent, TooltipTrigger } from "@/components/ui/tooltip";
 import { useThemeStore } from "@/stores/themeStore";
 
 interface DatabaseInfo {
   db_path: string;
   db_size_bytes: number;
+  wal_size_bytes: number;
   client_count: number;
   enrollment_count: number;
+  table_row_counts: { table: string; row_count: number }[];
   last_backup: string | null;
 }
 
@@ -41,6 +43,28 @@ function formatRelativeTime(iso: string): string {
   return date.toLocaleDateString();
 }
 
+interface OptimizeResult {
+  size_before_bytes: number;
+  size_after_bytes: number;
+}
+
+interface IntegrityReport {
+  ok: boolean;
+  integrity_errors: string[];
+  foreign_key_violations: { table: string; rowid: number | null; parent: string }[];
+}
+
+interface HealthReport {
+  app_version: string;
+  db_initialized: boolean;
+  schema_version: number | null;
+  expected_schema_version: number;
+  carrier_count: number | null;
+  state_count: number | null;
+  plan_type_count: number | null;
+  ok: boolean;
+}
+
 interface AgentProfile {
   id?: string;
   first_name?: string;
@@ -103,6 +127,16 @@ export function SettingsPage() {
   const [newPassword, setNewPassword] = useState("");
   const [confirmNewPassword, setConfirmNewPassword] = useState("");
   const [changingPassword, setChangingPassword] = useState(false);
+  const [sessionTimeoutMinutes, setSessionTimeoutMinutes] = useState("30");
+  const [optimizing, setOptimizing] = useState(false);
+  const [rebuildingSearch, setRebuildingSearch] = useState(false);
+  const [checkingIntegrity, setCheckingIntegrity] = useState(false);
+  const [integrityReport, setIntegrityReport] = useState<IntegrityReport | null>(null);
+
+  // Automatic backups
+  const [autoBackupIntervalDays, setAutoBackupIntervalDays] = useState("");
+  const [autoBackupDir, setAutoBackupDir] = useState("");
+  const [autoBackupKeep, setAutoBackupKeep] = useState("7");
 
   const {
     register,
@@ -123,14 +157,19 @@ export function SettingsPage() {
   useEffect(() => {
     async function loadData() {
       try {
-        const [profileData] = await Promise.all([
+        const [profileData, settings] = await Promise.all([
           tauriInvoke<AgentProfile | null>("get_agent_profile"),
+          tauriInvoke<Record<string, string>>("get_settings"),
           loadDbInfo(),
         ]);
         if (profileData) {
           setProfile(profileData);
           reset(profileData);
         }
+        setAutoBackupIntervalDays(settings.auto_backup_interval_days ?? "");
+        setAutoBackupDir(settings.auto_backup_dir ?? "");
+        setAutoBackupKeep(settings.auto_backup_keep || "7");
+        setSessionTimeoutMinutes(settings.session_timeout_minutes || "30");
       } catch (err) {
         console.error("Failed to load profile:", err);
       } finally {
@@ -168,10 +207,11 @@ export function SettingsPage() {
     }
     setChangingPassword(true);
     try {
-      // First verify current password by attempting login
-      // Then change password
-      // Note: change_password command would need to be implemented
-      toast.info("Password change will be available in a future update");
+      await tauriInvoke("change_password", { currentPassword, newPassword });
+      toast.success("Password changed");
+      setCurrentPassword("");
+      setNewPassword("");
+      setConfirmNewPassword("");
     } catch (err) {
       toast.error(typeof err === "string" ? err : "Failed to change password");
     } finally {
@@ -179,6 +219,17 @@ export function SettingsPage() {
     }
   };
 
+  const handleSaveSessionTimeout = async () => {
+    try {
+      await tauriInvoke("update_settings", {
+        settings: { session_timeout_minutes: sessionTimeoutMinutes.trim() },
+      });
+      toast.success("Auto-lock setting saved");
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Failed to save settings");
+    }
+  };
+
   const handleBackup = async () => {
     try {
       const { save } = await import("@tauri-apps/plugin-dialog");
@@ -196,6 +247,126 @@ export function SettingsPage() {
     }
   };
 
+  const handleExportAnonymized = async () => {
+    try {
+      const { save } = await import("@tauri-apps/plugin-dialog");
+      const destination = await save({
+        filters: [{ name: "Database", extensions: ["db"] }],
+        defaultPath: `compass_anonymized_${new Date().toISOString().slice(0, 10)}.db`,
+      });
+      if (destination) {
+        await tauriInvoke("export_anonymized_copy", { destination });
+        toast.success(`Anonymized copy saved to ${destination}`);
+      }
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Anonymized export failed");
+    }
+  };
+
+  const handleExportLogs = async () => {
+    try {
+      const { save } = await import("@tauri-apps/plugin-dialog");
+      const destination = await save({
+        filters: [{ name: "Zip", extensions: ["zip"] }],
+        defaultPath: `compass_logs_${new Date().toISOString().slice(0, 10)}.zip`,
+      });
+      if (destination) {
+        const count = await tauriInvoke<number>("export_logs", { destination });
+        toast.success(`Exported ${count} log file${count === 1 ? "" : "s"} to ${destination}`);
+      }
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Log export failed");
+    }
+  };
+
+  const handleChooseAutoBackupDir = async () => {
+    try {
+      const { open } = await import("@tauri-apps/plugin-dialog");
+      const dir = await open({ directory: true, multiple: false });
+      if (typeof dir === "string") setAutoBackupDir(dir);
+    } catch (err) {
+      console.error("Failed to choose backup folder:", err);
+    }
+  };
+
+  const handleSaveAutoBackup = async () => {
+    try {
+      await tauriInvoke("update_settings", {
+        settings: {
+          auto_backup_interval_days: autoBackupIntervalDays.trim(),
+          auto_backup_dir: autoBackupDir.trim(),
+          auto_backup_keep: autoBackupKeep.trim(),
+        },
+      });
+      toast.success("Automatic backup settings saved");
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Failed to save settings");
+    }
+  };
+
+  const handleOptimize = async () => {
+    setOptimizing(true);
+    try {
+      const result = await tauriInvoke<OptimizeResult>("optimize_database");
+      const saved = result.size_before_bytes - result.size_after_bytes;
+      toast.success(
+        saved > 0
+          ? `Database optimized, reclaimed ${formatBytes(saved)}`
+          : "Database optimized"
+      );
+      await loadDbInfo();
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Optimize failed");
+    } finally {
+      setOptimizing(false);
+    }
+  };
+
+  const handleRebuildSearch = async () => {
+    setRebuildingSearch(true);
+    try {
+      await tauriInvoke("optimize_search_index");
+      toast.success("Search index rebuilt");
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Search index rebuild failed");
+    } finally {
+      setRebuildingSearch(false);
+    }
+  };
+
+  const handleCheckIntegrity = async () => {
+    setCheckingIntegrity(true);
+    try {
+      const report = await tauriInvoke<IntegrityReport>("check_database_integrity");
+      setIntegrityReport(report);
+      if (report.ok) {
+        toast.success("Database integrity check passed");
+      } else {
+        toast.error("Database integrity check found problems");
+      }
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Integrity check failed");
+    } finally {
+      setCheckingIntegrity(false);
+    }
+  };
+
+  const handleHealthCheck = async () => {
+    try {
+      const report = await tauriInvoke<HealthReport>("health_check");
+      const summary =
+        `v${report.app_version} · schema ${report.schema_version ?? "?"}/${report.expected_schema_version} · ` +
+        `${report.carrier_count ?? 0} carriers, ${report.state_count ?? 0} states, ${report.plan_type_count ?? 0} plan types`;
+      if (report.ok) {
+        toast.success("Health check passed", { description: summary });
+      } else {
+        toast.error("Health check found problems", { description: summary });
+      }
+    } catch (err) {
+      toast.error(typeof err === "string" ? err : "Health check failed");
+    }
+  };
+
   if (loading) {
     return (
       <div className="flex items-center justify-center h-64">
@@ -307,6 +478,24 @@ export function SettingsPage() {
             {changingPassword ? <Loader2 className="mr-2 h-4 w-4 animate-spin" /> : <Shield className="mr-2 h-4 w-4" />}
             Change Password
           </Button>
+          <Separator />
+          <div className="space-y-2 max-w-md">
+            <Label htmlFor="sessionTimeoutMinutes">Auto-lock after idle (minutes)</Label>
+            <div className="flex gap-2">
+              <Input
+                id="sessionTimeoutMinutes"
+                type="number"
+                min={0}
+                value={sessionTimeoutMinutes}
+                onChange={(e) => setSessionTimeoutMinutes(e.target.value)}
+              />
+              <Button onClick={handleSaveSessionTimeout} variant="outline">
+                <Save className="mr-2 h-4 w-4" />
+                Save
+              </Button>
+            </div>
+            <p className="text-xs text-muted-foreground">Set to 0 to never lock automatically.</p>
+          </div>
         </CardContent>
       </Card>
 
@@ -343,6 +532,11 @@ export function SettingsPage() {
                   <Database className="h-3 w-3" /> Size
                 </p>
                 <p className="text-sm font-medium">{formatBytes(dbInfo.db_size_bytes)}</p>
+                {dbInfo.wal_size_bytes > 0 && (
+                  <p className="text-xs text-muted-foreground">
+                    + {formatBytes(dbInfo.wal_size_bytes)} write-ahead log
+                  </p>
+                )}
               </div>
               <div className="space-y-1">
                 <p className="text-xs text-muted-foreground flex items-center gap-1">
@@ -358,6 +552,15 @@ export function SettingsPage() {
               </div>
             </div>
           )}
+          {dbInfo && dbInfo.table_row_counts.length > 0 && (
+            <div className="flex flex-wrap gap-x-6 gap-y-1 mb-4 text-xs text-muted-foreground">
+              {dbInfo.table_row_counts.map(({ table, row_count }) => (
+                <span key={table}>
+                  <span className="font-mono">{table}</span>: {row_count.toLocaleString()} rows
+                </span>
+              ))}
+            </div>
+          )}
           <p className="text-sm text-muted-foreground mb-4">
             The backup file is encrypted with your password. You will need your password to restore it.
           </p>
@@ -366,6 +569,42 @@ export function SettingsPage() {
               <Download className="mr-2 h-4 w-4" />
               Create Backup
             </Button>
+            <Button onClick={handleOptimize} variant="outline" disabled={optimizing}>
+              {optimizing ? (
+                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+              ) : (
+                <HardDrive className="mr-2 h-4 w-4" />
+              )}
+              Optimize
+            </Button>
+            <Button onClick={handleRebuildSearch} variant="outline" disabled={rebuildingSearch}>
+              {rebuildingSearch ? (
+                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+              ) : (
+                <Search className="mr-2 h-4 w-4" />
+              )}
+              Rebuild Search
+            </Button>
+            <Button onClick={handleCheckIntegrity} variant="outline" disabled={checkingIntegrity}>
+              {checkingIntegrity ? (
+                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+              ) : (
+                <Shield className="mr-2 h-4 w-4" />
+              )}
+              Check Integrity
+            </Button>
+            <Button onClick={handleExportAnonymized} variant="outline">
+              <Users className="mr-2 h-4 w-4" />
+              Anonymized Copy
+            </Button>
+            <Button onClick={handleHealthCheck} variant="outline">
+              <Activity className="mr-2 h-4 w-4" />
+              Health Check
+            </Button>
+            <Button onClick={handleExportLogs} variant="outline">
+              <ScrollText className="mr-2 h-4 w-4" />
+              Export Logs
+            </Button>
             {dbInfo && (
               <p className="text-xs text-muted-foreground flex items-center gap-1">
                 <Clock className="h-3 w-3" />
@@ -373,6 +612,66 @@ export function SettingsPage() {
               </p>
             )}
           </div>
+          <Separator className="my-4" />
+          <div className="space-y-3">
+            <div>
+              <p className="text-sm font-medium">Automatic Backups</p>
+              <p className="text-xs text-muted-foreground">
+                Checked each time you log in. Leave the interval blank to turn automatic backups off.
+              </p>
+            </div>
+            <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
+              <div className="space-y-2">
+                <Label htmlFor="autoBackupIntervalDays">Every (days)</Label>
+                <Input
+                  id="autoBackupIntervalDays"
+                  type="number"
+                  min={1}
+                  value={autoBackupIntervalDays}
+                  onChange={(e) => setAutoBackupIntervalDays(e.target.value)}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="autoBackupKeep">Keep most recent</Label>
+                <Input
+                  id="autoBackupKeep"
+                  type="number"
+                  min={1}
+                  value={autoBackupKeep}
+                  onChange={(e) => setAutoBackupKeep(e.target.value)}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="autoBackupDir">Folder</Label>
+                <div className="flex gap-2">
+                  <Input
+                    id="autoBackupDir"
+                    value={autoBackupDir}
+                    onChange={(e) => setAutoBackupDir(e.target.value)}
+                  />
+                  <Button type="button" variant="outline" onClick={handleChooseAutoBackupDir}>
+                    Browse
+                  </Button>
+                </div>
+              </div>
+            </div>
+            <Button onClick={handleSaveAutoBackup} variant="outline">
+              <Save className="mr-2 h-4 w-4" />
+              Save Backup Settings
+            </Button>
+          </div>
+          {integrityReport && !integrityReport.ok && (
+            <div className="mt-4 rounded border border-red-200 dark:border-red-900 p-3 text-xs space-y-1">
+              {integrityReport.integrity_errors.map((msg, i) => (
+                <p key={`i-${i}`} className="text-red-600">{msg}</p>
+              ))}
+              {integrityReport.foreign_key_violations.map((v, i) => (
+                <p key={`fk-${i}`} className="text-red-600">
+                  {v.table} row {v.rowid ?? "?"} references a missing {v.parent} record
+                </p>
+              ))}
+            </div>
+          )}
         </CardContent>
       </Card>
 
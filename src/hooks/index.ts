@@ -1,5 +1,8 @@
+export * from "./useAuditLog";
 export * from "./useCarrierSync";
 export * from "./useClients";
 export * from "./useCommissions";
 export * from "./useEnrollments";
+export * from "./useImportMappings";
 export * from "./useKeyboardShortcuts";
+export * from "./usePlans";
@@ -0,0 +1,14 @@
+import { useQuery } from "@tanstack/react-query";
+import { tauriInvoke } from "@/lib/tauri";
+import type { AuditEntry } from "@/types";
+
+export function useAuditLog(entityType: AuditEntry["entity_type"], entityId?: string) {
+  return useQuery({
+    queryKey: ["audit_log", entityType, entityId],
+    queryFn: () =>
+      tauriInvoke<AuditEntry[]>("get_audit_log", {
+        entityType,
+        entityId: entityId ?? null,
+      }),
+  });
+}
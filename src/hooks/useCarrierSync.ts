@@ -1,6 +1,6 @@
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { tauriInvoke } from "@/lib/tauri";
-import type { SyncResult, SyncLogEntry, ImportPortalResult, ConfirmDisenrollmentResult, CarrierSyncInfo } from "@/types";
+import type { PortalFetchSummary, SyncResult, SyncLogEntry, SyncSnapshot, SyncReviewItem, SyncAorLoss, ImportPortalResult, ConfirmDisenrollmentResult, CarrierSyncInfo } from "@/types";
 
 export function useOpenCarrierLogin() {
   return useMutation({
@@ -16,26 +16,68 @@ export function useTriggerCarrierFetch() {
   });
 }
 
+export function useCancelCarrierFetch() {
+  return useMutation({
+    mutationFn: () => tauriInvoke<void>("cancel_carrier_fetch"),
+  });
+}
+
+export function useParsePortalMembers() {
+  return useMutation({
+    mutationFn: ({ carrierId, membersJson }: { carrierId: string; membersJson: string }) =>
+      tauriInvoke<PortalFetchSummary>("parse_portal_members", { carrierId, membersJson }),
+  });
+}
+
+/** Dry run of process_portal_members; nothing is written */
+export function usePreviewPortalSync() {
+  return useMutation({
+    mutationFn: ({
+      carrierId,
+      membersJson,
+      emptyConfirmed,
+    }: {
+      carrierId: string;
+      membersJson: string;
+      emptyConfirmed?: boolean;
+    }) =>
+      tauriInvoke<SyncResult>("preview_portal_sync", {
+        carrierId,
+        membersJson,
+        emptyConfirmed: emptyConfirmed ?? false,
+      }),
+  });
+}
+
 export function useProcessPortalMembers() {
   const queryClient = useQueryClient();
   return useMutation({
     mutationFn: ({
       carrierId,
       membersJson,
+      emptyConfirmed,
     }: {
       carrierId: string;
       membersJson: string;
+      emptyConfirmed?: boolean;
     }) =>
       tauriInvoke<SyncResult>("process_portal_members", {
         carrierId,
         membersJson,
+        emptyConfirmed: emptyConfirmed ?? false,
       }),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["enrollments"] });
       queryClient.invalidateQueries({ queryKey: ["clients"] });
       queryClient.invalidateQueries({ queryKey: ["sync-logs"] });
+      queryClient.invalidateQueries({ queryKey: ["sync-review-items"] });
+      queryClient.invalidateQueries({ queryKey: ["lost-aor-enrollments"] });
       queryClient.invalidateQueries({ queryKey: ["dashboard-stats"] });
     },
+    // The failed attempt is recorded in the sync log
+    onError: () => {
+      queryClient.invalidateQueries({ queryKey: ["sync-logs"] });
+    },
   });
 }
 
@@ -57,6 +99,7 @@ export function useImportPortalMembers() {
       queryClient.invalidateQueries({ queryKey: ["carriers"] });
       queryClient.invalidateQueries({ queryKey: ["enrollments"] });
       queryClient.invalidateQueries({ queryKey: ["clients"] });
+      queryClient.invalidateQueries({ queryKey: ["sync-review-items"] });
       queryClient.invalidateQueries({ queryKey: ["dashboard-stats"] });
     },
   });
@@ -119,6 +162,13 @@ export function useSyncLogs(carrierId?: string) {
   });
 }
 
+export function useFetchSyncSnapshot() {
+  return useMutation({
+    mutationFn: (syncLogId: string) =>
+      tauriInvoke<SyncSnapshot>("get_sync_snapshot", { syncLogId }),
+  });
+}
+
 export function useSavePortalCredentials() {
   const queryClient = useQueryClient();
   return useMutation({
@@ -173,3 +223,39 @@ export function useCarriersWithCredentials() {
       tauriInvoke<string[]>("get_carriers_with_credentials"),
   });
 }
+
+export function useSyncReviewItems(carrierId?: string) {
+  return useQuery({
+    queryKey: ["sync-review-items", carrierId],
+    queryFn: () =>
+      tauriInvoke<SyncReviewItem[]>("get_sync_review_items", {
+        carrierId: carrierId ?? null,
+      }),
+  });
+}
+
+export function useLostAorEnrollments() {
+  return useQuery({
+    queryKey: ["lost-aor-enrollments"],
+    queryFn: () => tauriInvoke<SyncAorLoss[]>("get_lost_aor_enrollments"),
+  });
+}
+
+export type SyncReviewAction = "confirm_match" | "create_new" | "ignore";
+
+export function useResolveSyncReviewItem() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ id, action }: { id: string; action: SyncReviewAction }) =>
+      tauriInvoke<SyncReviewItem>("resolve_sync_review_item", { id, action }),
+    onSuccess: (_data, vars) => {
+      queryClient.invalidateQueries({ queryKey: ["sync-review-items"] });
+      if (vars.action === "create_new") {
+        queryClient.invalidateQueries({ queryKey: ["carriers"] });
+        queryClient.invalidateQueries({ queryKey: ["enrollments"] });
+        queryClient.invalidateQueries({ queryKey: ["clients"] });
+        queryClient.invalidateQueries({ queryKey: ["dashboard-stats"] });
+      }
+    },
+  });
+}
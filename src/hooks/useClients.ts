@@ -1,6 +1,6 @@
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { tauriInvoke } from "@/lib/tauri";
-import type { Client, ClientListItem, ClientFilters, PaginatedResult, Carrier, DuplicateCandidate, DuplicateGroup } from "@/types";
+import type { Client, ClientListItem, ClientFilters, PaginatedResult, Carrier, CarrierWithCounts, DuplicateCandidate, DuplicateGroup, SepHints } from "@/types";
 
 export function useClients(filters: ClientFilters, page: number, perPage: number) {
   return useQuery({
@@ -14,6 +14,14 @@ export function useClients(filters: ClientFilters, page: number, perPage: number
   });
 }
 
+/** Match count for `filters` without loading a page of clients */
+export function useClientCount(filters: ClientFilters) {
+  return useQuery({
+    queryKey: ["clients", "count", filters],
+    queryFn: () => tauriInvoke<number>("count_clients", { filters }),
+  });
+}
+
 export function useClient(id: string | undefined) {
   return useQuery({
     queryKey: ["client", id],
@@ -22,6 +30,14 @@ export function useClient(id: string | undefined) {
   });
 }
 
+export function useSepHints(clientId: string | undefined) {
+  return useQuery({
+    queryKey: ["client", clientId, "sep-hints"],
+    queryFn: () => tauriInvoke<SepHints>("get_sep_hints", { clientId }),
+    enabled: !!clientId,
+  });
+}
+
 export function useCreateClient() {
   const queryClient = useQueryClient();
   return useMutation({
@@ -45,6 +61,18 @@ export function useUpdateClient() {
   });
 }
 
+export function useBulkTagClients() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ clientIds, tag, add }: { clientIds: string[]; tag: string; add: boolean }) =>
+      tauriInvoke<number>("bulk_tag_clients", { clientIds, tag, add }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["clients"] });
+      queryClient.invalidateQueries({ queryKey: ["client"] });
+    },
+  });
+}
+
 export function useDeleteClient() {
   const queryClient = useQueryClient();
   return useMutation({
@@ -109,3 +137,10 @@ export function useCarriers() {
     staleTime: 5 * 60 * 1000, // 5 minutes
   });
 }
+
+export function useCarriersWithCounts() {
+  return useQuery({
+    queryKey: ["carriers", "with_counts"],
+    queryFn: () => tauriInvoke<CarrierWithCounts[]>("get_carriers_with_counts"),
+  });
+}
@@ -4,7 +4,9 @@ import type {
   Conversation,
   ConversationListItem,
   ConversationEntry,
+  ConversationStatus,
   TimelineEntry,
+  ClientNextAction,
   CreateConversationInput,
   UpdateConversationInput,
   CreateConversationEntryInput,
@@ -89,6 +91,23 @@ export function useCreateConversationEntry() {
       });
       queryClient.invalidateQueries({ queryKey: ["conversations"] });
       queryClient.invalidateQueries({ queryKey: ["client_timeline"] });
+      // Last contact date on the client and the client list
+      queryClient.invalidateQueries({ queryKey: ["client", variables.client_id] });
+      queryClient.invalidateQueries({ queryKey: ["clients"] });
+    },
+  });
+}
+
+export function useBulkUpdateConversationStatus() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ ids, status }: { ids: string[]; status: ConversationStatus }) =>
+      tauriInvoke<number>("bulk_update_conversation_status", { ids, status }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["conversations"] });
+      queryClient.invalidateQueries({ queryKey: ["conversation"] });
+      queryClient.invalidateQueries({ queryKey: ["client_timeline"] });
+      queryClient.invalidateQueries({ queryKey: ["pending_follow_ups"] });
     },
   });
 }
@@ -111,6 +130,8 @@ export function useUpdateConversationEntry() {
       queryClient.invalidateQueries({ queryKey: ["conversation_entries"] });
       queryClient.invalidateQueries({ queryKey: ["conversations"] });
       queryClient.invalidateQueries({ queryKey: ["client_timeline"] });
+      queryClient.invalidateQueries({ queryKey: ["client"] });
+      queryClient.invalidateQueries({ queryKey: ["clients"] });
     },
   });
 }
@@ -134,13 +155,24 @@ export function useClientTimeline(
   });
 }
 
-export function usePendingFollowUps(clientId?: string) {
+export function usePendingFollowUps(clientId?: string, includeOverdue = false) {
   return useQuery({
-    queryKey: ["pending_follow_ups", clientId],
+    queryKey: ["pending_follow_ups", clientId, includeOverdue],
     queryFn: () =>
       tauriInvoke<TimelineEntry[]>("get_pending_follow_ups", {
         clientId: clientId ?? null,
+        includeOverdue,
       }),
     enabled: clientId !== undefined,
   });
 }
+
+export function useClientNextAction(clientId: string | undefined) {
+  return useQuery({
+    // Under pending_follow_ups so entry mutations refresh it too
+    queryKey: ["pending_follow_ups", "next_action", clientId],
+    queryFn: () =>
+      tauriInvoke<ClientNextAction | null>("get_client_next_action", { clientId }),
+    enabled: !!clientId,
+  });
+}
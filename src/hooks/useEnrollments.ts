@@ -1,6 +1,11 @@
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { tauriInvoke } from "@/lib/tauri";
-import type { Enrollment, EnrollmentListItem } from "@/types";
+import type {
+  Enrollment,
+  EnrollmentHistoryItem,
+  EnrollmentListItem,
+  PaginatedResult,
+} from "@/types";
 
 export function useEnrollments(clientId?: string) {
   return useQuery({
@@ -12,6 +17,27 @@ export function useEnrollments(clientId?: string) {
   });
 }
 
+/** Every enrollment the client has had, including terminated and deleted ones */
+export function useEnrollmentHistory(clientId: string) {
+  return useQuery({
+    queryKey: ["enrollments", "history", clientId],
+    queryFn: () =>
+      tauriInvoke<EnrollmentHistoryItem[]>("get_enrollment_history", { clientId }),
+    enabled: !!clientId,
+  });
+}
+
+export function useEnrollmentsPage(page: number, perPage: number) {
+  return useQuery({
+    queryKey: ["enrollments", "page", page, perPage],
+    queryFn: () =>
+      tauriInvoke<PaginatedResult<EnrollmentListItem>>("get_enrollments_page", {
+        page,
+        perPage,
+      }),
+  });
+}
+
 export function useCreateEnrollment() {
   const queryClient = useQueryClient();
   return useMutation({
@@ -34,3 +60,45 @@ export function useUpdateEnrollment() {
     },
   });
 }
+
+export function useDeleteEnrollment() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => tauriInvoke<void>("delete_enrollment", { id }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["enrollments"] });
+      queryClient.invalidateQueries({ queryKey: ["client_timeline"] });
+    },
+  });
+}
+
+export function useRestoreEnrollment() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => tauriInvoke<Enrollment>("restore_enrollment", { id }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["enrollments"] });
+      queryClient.invalidateQueries({ queryKey: ["client_timeline"] });
+    },
+  });
+}
+
+export function useRenewableEnrollments(planYear: number) {
+  return useQuery({
+    queryKey: ["enrollments", "renewable", planYear],
+    queryFn: () =>
+      tauriInvoke<EnrollmentListItem[]>("detect_renewable_enrollments", { planYear }),
+  });
+}
+
+export function useRenewEnrollment() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ id, newEffectiveDate }: { id: string; newEffectiveDate: string }) =>
+      tauriInvoke<Enrollment>("renew_enrollment", { id, newEffectiveDate }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["enrollments"] });
+      queryClient.invalidateQueries({ queryKey: ["client_timeline"] });
+    },
+  });
+}
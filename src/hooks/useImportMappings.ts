@@ -0,0 +1,21 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { tauriInvoke } from "@/lib/tauri";
+import type { ImportMappingPreset } from "@/types";
+
+export function useImportMappings() {
+  return useQuery({
+    queryKey: ["import-mappings"],
+    queryFn: () => tauriInvoke<ImportMappingPreset[]>("list_import_mappings"),
+  });
+}
+
+export function useSaveImportMapping() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ name, mapping }: { name: string; mapping: Record<string, string> }) =>
+      tauriInvoke<ImportMappingPreset>("save_import_mapping", { name, mapping }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["import-mappings"] });
+    },
+  });
+}
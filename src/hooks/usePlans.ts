@@ -0,0 +1,44 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { tauriInvoke } from "@/lib/tauri";
+import type { Plan, PlanListItem } from "@/types";
+
+export function usePlans(carrierId?: string, planYear?: number) {
+  return useQuery({
+    queryKey: ["plans", carrierId, planYear],
+    queryFn: () =>
+      tauriInvoke<PlanListItem[]>("get_plans", {
+        carrierId: carrierId ?? null,
+        planYear: planYear ?? null,
+      }),
+  });
+}
+
+export function usePlan(id: string | undefined) {
+  return useQuery({
+    queryKey: ["plan", id],
+    queryFn: () => tauriInvoke<Plan>("get_plan", { id }),
+    enabled: !!id,
+  });
+}
+
+export function useCreatePlan() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (input: Partial<Plan>) => tauriInvoke<Plan>("create_plan", { input }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["plans"] });
+    },
+  });
+}
+
+export function useUpdatePlan() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ id, input }: { id: string; input: Partial<Plan> }) =>
+      tauriInvoke<Plan>("update_plan", { id, input }),
+    onSuccess: (_data, vars) => {
+      queryClient.invalidateQueries({ queryKey: ["plans"] });
+      queryClient.invalidateQueries({ queryKey: ["plan", vars.id] });
+    },
+  });
+}
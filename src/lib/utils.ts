@@ -50,3 +50,13 @@ export function formatTimestamp(utcStr?: string | null): string {
     minute: "2-digit",
   });
 }
+
+/** "Today", "1 day ago", "94 days ago" for a UTC `YYYY-MM-DD HH:MM:SS` timestamp */
+export function formatDaysAgo(utcStr?: string | null): string {
+  if (!utcStr) return "\u2014";
+  const d = new Date(utcStr.replace(" ", "T") + "Z");
+  if (isNaN(d.getTime())) return utcStr;
+  const days = Math.floor((Date.now() - d.getTime()) / 86400000);
+  if (days <= 0) return "Today";
+  return days === 1 ? "1 day ago" : `${days} days ago`;
+}
@@ -25,9 +25,12 @@ export interface Client {
   lead_source?: string;
   member_record_locator?: string;
   is_active: boolean;
-  tags?: string[];
+  tags?: string; // comma-separated
   created_at: string;
   updated_at: string;
+  age?: number;
+  turns_65_on?: string;
+  last_contact_at?: string;
 }
 
 export interface ClientListItem {
@@ -38,6 +41,8 @@ export interface ClientListItem {
   carrier_name?: string;
   plan_name?: string;
   is_active?: boolean;
+  last_contact_at?: string;
+  match_snippet?: string; // matches wrapped in <mark>
 }
 
 export interface ClientFilters {
@@ -47,8 +52,17 @@ export interface ClientFilters {
   status_code?: string;
   state?: string;
   zip?: string;
+  county?: string;
+  tag?: string;
   is_dual_eligible?: boolean;
   is_active?: boolean;
+  enrollment_status?: "NONE";
+  created_after?: string;
+  created_before?: string;
+  no_contact_since?: string;
+  include_snippets?: boolean;
+  sort_by?: "last_name" | "first_name" | "created_at" | "city" | "state" | "dob";
+  sort_dir?: "asc" | "desc";
 }
 
 export interface PaginatedResult<T> {
@@ -76,6 +90,11 @@ export interface Enrollment {
   premium?: number;
   confirmation_number?: string;
   enrollment_source?: string;
+  commission_amount?: number;
+  commission_status?: "PENDING" | "PAID" | "CHARGEBACK";
+  commission_paid_date?: string;
+  /** Agent-of-record status from the last carrier sync */
+  aor_status?: string | null;
   is_active: boolean;
   created_at: string;
   updated_at: string;
@@ -89,15 +108,62 @@ export interface Carrier {
   expected_active?: number;
 }
 
+export interface CarrierWithCounts extends Carrier {
+  enrollment_count: number;
+}
+
+export interface Plan {
+  id: string;
+  carrier_id: string;
+  plan_type_code: string;
+  plan_name: string;
+  contract_number?: string;
+  pbp_number?: string;
+  segment_id?: string;
+  plan_year?: number;
+  state?: string;
+  county_fips?: string;
+  premium?: number;
+  is_active?: boolean;
+  created_at?: string;
+  updated_at?: string;
+}
+
+export interface PlanListItem {
+  id: string;
+  carrier_name?: string;
+  plan_type_code: string;
+  plan_name: string;
+  contract_number?: string;
+  pbp_number?: string;
+  plan_year?: number;
+  premium?: number;
+}
+
 export interface DashboardStats {
   total_active_clients: number;
   new_this_month: number;
   lost_this_month: number;
   pending_enrollments: number;
+  overdue_follow_ups: number;
+  follow_ups_next_7_days: number;
+  projected_commission_this_month: number;
+  paid_commission_this_month: number;
+  call_stats: [string, number][];
+  total_call_minutes: number;
+  call_stats_window_days: number;
   by_plan_type: [string, number][];
   by_carrier: [string, number, number][];
   by_state: [string, number][];
   monthly_trend: MonthlyTrend[];
+  active_enrollment_periods: ActiveEnrollmentPeriod[];
+}
+
+export interface ActiveEnrollmentPeriod {
+  code: string;
+  name: string;
+  end_date: string;
+  days_remaining: number;
 }
 
 export interface MonthlyTrend {
@@ -118,6 +184,36 @@ export interface EnrollmentListItem {
   termination_date?: string;
 }
 
+export interface EnrollmentHistoryItem {
+  id: string;
+  plan_name?: string;
+  carrier_name?: string;
+  plan_type?: string;
+  status_code?: string;
+  status?: string;
+  effective_date?: string;
+  termination_date?: string;
+  enrollment_source?: string;
+  is_active: boolean;
+}
+
+// ── SEP Hints ────────────────────────────────────────────────────────────────
+
+export interface SepHint {
+  code: "DUAL_LIS" | "MOVED" | "LOST_COVERAGE" | "NEW_TO_MEDICARE";
+  title: string;
+  explanation: string;
+  evidence_date?: string | null;
+  window_ends?: string | null;
+}
+
+export interface SepHints {
+  client_id: string;
+  hints: SepHint[];
+  /** Heuristic only; show alongside the hints */
+  disclaimer: string;
+}
+
 // ── Conversations ────────────────────────────────────────────────────────────
 
 export type ConversationStatus = "OPEN" | "CLOSED" | "ARCHIVED";
@@ -125,6 +221,7 @@ export type EntryType = "CALL" | "EMAIL" | "MEETING" | "SMS" | "NOTE" | "SYSTEM"
 export type CallDirection = "INBOUND" | "OUTBOUND";
 export type CallOutcome = "ANSWERED" | "NO_ANSWER" | "VOICEMAIL" | "BUSY" | "CALLBACK_REQUESTED" | "WRONG_NUMBER";
 export type MeetingType = "IN_PERSON" | "VIDEO" | "PHONE";
+export type FollowUpRecurrence = "NONE" | "WEEKLY" | "MONTHLY" | "QUARTERLY";
 
 export interface Conversation {
   id: string;
@@ -158,6 +255,8 @@ export interface ConversationEntry {
   occurred_at?: string;
   follow_up_date?: string;
   follow_up_note?: string;
+  follow_up_recurrence?: FollowUpRecurrence;
+  follow_up_until?: string;
   call_direction?: CallDirection;
   call_duration?: number;
   call_outcome?: CallOutcome;
@@ -173,6 +272,16 @@ export interface ConversationEntry {
   updated_at?: string;
 }
 
+export interface ClientNextAction {
+  entry_id: string;
+  conversation_id: string;
+  entry_type: EntryType;
+  subject?: string;
+  follow_up_date: string;
+  follow_up_note?: string;
+  last_entry_at?: string;
+}
+
 export interface TimelineEntry {
   id: string;
   conversation_id: string;
@@ -184,6 +293,8 @@ export interface TimelineEntry {
   occurred_at?: string;
   follow_up_date?: string;
   follow_up_note?: string;
+  follow_up_recurrence?: FollowUpRecurrence;
+  follow_up_until?: string;
   call_direction?: CallDirection;
   call_duration?: number;
   call_outcome?: CallOutcome;
@@ -218,6 +329,8 @@ export interface CreateConversationEntryInput {
   occurred_at?: string;
   follow_up_date?: string;
   follow_up_note?: string;
+  follow_up_recurrence?: FollowUpRecurrence;
+  follow_up_until?: string;
   call_direction?: CallDirection;
   call_duration?: number;
   call_outcome?: CallOutcome;
@@ -234,6 +347,8 @@ export interface UpdateConversationEntryInput {
   occurred_at?: string;
   follow_up_date?: string;
   follow_up_note?: string;
+  follow_up_recurrence?: FollowUpRecurrence;
+  follow_up_until?: string;
   call_direction?: CallDirection;
   call_duration?: number;
   call_outcome?: CallOutcome;
@@ -282,6 +397,8 @@ export interface PortalMember {
   end_date?: string;
   status?: string;
   policy_status?: string;
+  /** Agent-of-record status, from portals that report it separately */
+  aor_status?: string | null;
   state?: string;
   city?: string;
   phone?: string;
@@ -315,6 +432,14 @@ export interface ClientProvider {
   updated_at?: string;
 }
 
+/** What a portal fetch returned, before it is compared */
+export interface PortalFetchSummary {
+  carrier_name: string;
+  member_count: number;
+  local_count: number;
+  sample: PortalMember[];
+}
+
 export interface SyncResult {
   carrier_name: string;
   portal_count: number;
@@ -322,7 +447,10 @@ export interface SyncResult {
   matched: number;
   matched_members: SyncMatch[];
   disenrolled: SyncDisenrollment[];
+  terminated_in_portal: SyncTermination[];
   new_in_portal: PortalMember[];
+  possible_matches: SyncPossibleMatch[];
+  aor_lost: SyncAorLoss[];
 }
 
 export interface SyncMatch {
@@ -332,6 +460,34 @@ export interface SyncMatch {
   match_tier: string;
 }
 
+/** Name-similarity pairing awaiting the agent's confirmation */
+export interface SyncPossibleMatch {
+  client_name: string;
+  client_id: string;
+  enrollment_id: string;
+  plan_name?: string;
+  portal_member: PortalMember;
+  score: number;
+  review_item_id?: string | null;
+}
+
+/** A sync result kept in the review queue until the agent resolves it */
+export interface SyncReviewItem {
+  id: string;
+  carrier_id: string;
+  carrier_name?: string | null;
+  sync_log_id?: string | null;
+  kind: "NEW_IN_PORTAL" | "POSSIBLE_MATCH";
+  portal_member: PortalMember;
+  client_id?: string | null;
+  client_name?: string | null;
+  enrollment_id?: string | null;
+  score?: number | null;
+  status: "PENDING" | "MATCHED" | "CREATED" | "IGNORED";
+  created_at?: string | null;
+  resolved_at?: string | null;
+}
+
 export interface SyncDisenrollment {
   client_name: string;
   client_id: string;
@@ -339,6 +495,25 @@ export interface SyncDisenrollment {
   plan_name?: string;
 }
 
+export interface SyncTermination {
+  client_name: string;
+  client_id: string;
+  enrollment_id: string;
+  plan_name?: string;
+  status_code: string;
+  termination_date: string;
+}
+
+/** An active enrollment whose carrier shows the agent is no longer the AOR */
+export interface SyncAorLoss {
+  client_name: string;
+  client_id: string;
+  enrollment_id: string;
+  carrier_name?: string | null;
+  plan_name?: string | null;
+  aor_status: string;
+}
+
 export interface ImportPortalResult {
   imported: number;
   imported_names: string[];
@@ -365,6 +540,15 @@ export interface SyncLogEntry {
   disenrolled: number;
   new_found: number;
   status: string;
+  has_snapshot: boolean;
+  error_message?: string | null;
+}
+
+export interface SyncSnapshot {
+  sync_log_id: string;
+  carrier_id: string;
+  created_at: string;
+  members: PortalMember[];
 }
 
 // ── Commissions ──────────────────────────────────────────────────────────────
@@ -541,3 +725,22 @@ export interface CarrierMonthSummary {
   ok_count: number;
   issue_count: number;
 }
+
+export interface AuditEntry {
+  id: number;
+  entity_type: "client" | "enrollment" | "conversation" | "conversation_entry";
+  entity_id: string;
+  action: "CREATE" | "UPDATE" | "DELETE" | "RESTORE" | "PURGE";
+  /** JSON: fields on CREATE, { field: { old, new } } on UPDATE */
+  changes: string | null;
+  created_at: string | null;
+}
+
+/** A saved column mapping (source header -> target field) for file imports */
+export interface ImportMappingPreset {
+  id: string;
+  name: string;
+  mapping: Record<string, string>;
+  created_at?: string | null;
+  updated_at?: string | null;
+}